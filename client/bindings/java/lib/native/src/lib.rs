@@ -82,10 +82,11 @@ pub extern "system" fn Java_org_iota_apis_NativeApi_sendCommand(
 
     let guard = MESSAGE_HANDLER.lock().unwrap();
 
-    let response = block_on(guard.as_ref().unwrap().send_message(message));
+    let message_handler = guard.as_ref().unwrap();
+    let response = block_on(message_handler.send_message(message));
 
     let output = env
-        .new_string(serde_json::to_string(&response).unwrap())
+        .new_string(message_handler.response_to_json(&response).unwrap())
         .expect("Couldn't create java string!");
 
     output.into_raw()
@@ -782,7 +782,7 @@ public class Client extends NativeApi {
      * @return The network ID of the current network.
      * @throws ClientException on error.
      */
-    public int getNetworkId() throws ClientException {
+    public String getNetworkId() throws ClientException {
         return miscellaneousApi.getNetworkId();
     }
 
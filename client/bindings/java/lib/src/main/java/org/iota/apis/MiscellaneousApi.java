@@ -99,8 +99,8 @@ public class MiscellaneousApi {
         return responsePayload;
     }
 
-    public int getNetworkId() throws ClientException {
-        Integer responsePayload = nativeApi.sendCommand(new ClientCommand("getNetworkId")).getAsInt();
+    public String getNetworkId() throws ClientException {
+        String responsePayload = nativeApi.sendCommand(new ClientCommand("getNetworkId")).getAsString();
         return responsePayload;
     }
 
@@ -46,7 +46,9 @@ async function run() {
         ]);
 
         // Get outputs by their IDs
-        const addressOutputs = await client.getOutputs(outputIdsResponse.items);
+        const addressOutputs = (
+            await client.getOutputs(outputIdsResponse.items)
+        ).items.map(({ item }) => item);
 
         // Calculate the total amount and native tokens
         let totalAmount = 0;
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 import { Client, initLogger } from '@iota/client';
+import type { MqttEvent } from '@iota/client';
 require('dotenv').config({ path: '../.env' });
 
 // Run with command:
@@ -24,7 +25,8 @@ async function run() {
     const topics = ['blocks'];
 
     const callback = function (error: Error, data: string) {
-        console.log(JSON.parse(data));
+        const event: MqttEvent = JSON.parse(data);
+        console.log(event.topic, event.payloadType, event.payload);
     };
 
     await client.listen(topics, callback);
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 import { MessageHandler } from './MessageHandler';
 import type {
+    IAddressOutputEvent,
+    IAddressOutputIdsOptions,
     IClientOptions,
+    MqttStatus,
+    PayloadType,
     IGenerateAddressesOptions,
     IBuildBlockOptions,
     QueryParameter,
@@ -22,6 +26,12 @@ import type {
     LedgerNanoStatus,
     IInputSigningData,
     OutputIdsResponse,
+    IBlockMetadataWithState,
+    ITypedBlock,
+    IBatchResponse,
+    ITransactionInclusionProof,
+    IConsolidationCheckpoint,
+    IDoubleSpendEvent,
 } from '../types';
 import type {
     IUTXOInput,
@@ -103,8 +113,13 @@ export class Client {
         return JSON.parse(response).payload;
     }
 
-    /** Fetch OutputResponse from provided OutputIds (requests are sent in parallel) */
-    async getOutputs(outputIds: string[]): Promise<IOutputResponse[]> {
+    /**
+     * Fetch OutputResponse from provided OutputIds (requests are sent in parallel). Failed requests are handled
+     * according to the `batchErrorMode` of the client, with `collect` their errors are returned next to the outputs.
+     */
+    async getOutputs(
+        outputIds: string[],
+    ): Promise<IBatchResponse<IOutputResponse>> {
         const response = await this.messageHandler.sendMessage({
             name: 'getOutputs',
             data: {
@@ -220,6 +235,22 @@ export class Client {
         return JSON.parse(response).payload;
     }
 
+    /**
+     * Post a block with only a tagged data payload, to store data without moving funds. The tag and the data
+     * are decoded as hex if they start with `0x`, otherwise their UTF-8 bytes are used. The tag must not be empty.
+     */
+    async postTaggedData(tag: string, data: string): Promise<BlockId> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'postTaggedData',
+            data: {
+                tag,
+                data,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
     /**
      * Returns tips that are ideal for attaching a block.
      * The tips can be considered as non-lazy and are therefore ideal for attaching a block.
@@ -249,7 +280,7 @@ export class Client {
     /**
      * Get block as JSON.
      */
-    async getBlock(blockId: BlockId): Promise<IBlock> {
+    async getBlock(blockId: BlockId): Promise<ITypedBlock> {
         const response = await this.messageHandler.sendMessage({
             name: 'getBlock',
             data: {
@@ -263,7 +294,7 @@ export class Client {
     /**
      * Get block metadata.
      */
-    async getBlockMetadata(blockId: BlockId): Promise<IBlockMetadata> {
+    async getBlockMetadata(blockId: BlockId): Promise<IBlockMetadataWithState> {
         const response = await this.messageHandler.sendMessage({
             name: 'getBlockMetadata',
             data: {
@@ -274,6 +305,24 @@ export class Client {
         return JSON.parse(response).payload;
     }
 
+    /**
+     * Get the IDs of all unspent outputs owned by an address, following the pagination cursor of the indexer.
+     */
+    async addressOutputIds(
+        address: string,
+        options?: IAddressOutputIdsOptions,
+    ): Promise<string[]> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'addressOutputIds',
+            data: {
+                address,
+                ...options,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
     /**
      * Find inputs from addresses for a provided amount (useful for offline signing)
      */
@@ -430,6 +479,34 @@ export class Client {
         return JSON.parse(response).payload;
     }
 
+    /**
+     * Returns a valid Address parsed from a bech32 or a `0x` prefixed hex encoded Ed25519 address.
+     */
+    async parseAddress(address: string): Promise<AddressTypes> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'parseAddress',
+            data: {
+                address,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
+    /**
+     * Returns a UTXO input parsed from a hex encoded output ID.
+     */
+    async parseOutputId(outputId: string): Promise<IUTXOInput> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'parseOutputId',
+            data: {
+                outputId,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
     /**
      * Returns a block ID (Blake2b256 hash of the block bytes)
      */
@@ -456,9 +533,9 @@ export class Client {
     }
 
     /**
-     * Get the network id of the node we're connecting to.
+     * Get the network id of the node we're connecting to, as a string because it can exceed the safe integer range.
      */
-    async getNetworkId(): Promise<number> {
+    async getNetworkId(): Promise<string> {
         const response = await this.messageHandler.sendMessage({
             name: 'getNetworkId',
         });
@@ -707,7 +784,7 @@ export class Client {
     /**
      * Returns the included block of the transaction.
      */
-    async getIncludedBlock(transactionId: string): Promise<IBlock> {
+    async getIncludedBlock(transactionId: string): Promise<ITypedBlock> {
         const response = await this.messageHandler.sendMessage({
             name: 'getIncludedBlock',
             data: {
@@ -721,7 +798,9 @@ export class Client {
     /**
      * Returns the metadata of the included block of the transaction.
      */
-    async getIncludedBlockMetadata(transactionId: string): Promise<IBlock> {
+    async getIncludedBlockMetadata(
+        transactionId: string,
+    ): Promise<IBlockMetadataWithState> {
         const response = await this.messageHandler.sendMessage({
             name: 'getIncludedBlockMetadata',
             data: {
@@ -732,6 +811,41 @@ export class Client {
         return JSON.parse(response).payload;
     }
 
+    /**
+     * Returns a proof that the transaction was included in the ledger by a milestone.
+     */
+    async getTransactionInclusionProof(
+        transactionId: string,
+    ): Promise<ITransactionInclusionProof> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'getTransactionInclusionProof',
+            data: {
+                transactionId,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
+    /**
+     * Checks that the block of a transaction inclusion proof contains the transaction and that the audit path
+     * leads to the inclusion merkle root of the milestone. The milestone signatures aren't checked.
+     */
+    async verifyTransactionInclusionProof(
+        proof: ITransactionInclusionProof,
+        transactionId: string,
+    ): Promise<boolean> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'verifyTransactionInclusionProof',
+            data: {
+                proof,
+                transactionId,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
     /**
      * Transforms bech32 to hex.
      */
@@ -813,19 +927,37 @@ export class Client {
     }
 
     /**
-     * Checks if a String is a valid bech32 encoded address.
+     * Checks if a String is a valid bech32 encoded address, of the network with the expected bech32 HRP if it's set.
      */
-    async isAddressValid(address: string): Promise<boolean> {
+    async isAddressValid(
+        address: string,
+        expectedHrp?: string,
+    ): Promise<boolean> {
         const response = await this.messageHandler.sendMessage({
             name: 'isAddressValid',
             data: {
                 address,
+                expectedHrp,
             },
         });
 
         return JSON.parse(response).payload;
     }
 
+    /**
+     * Validates a bech32 encoded address like `isAddressValid()`, but throws the reason if it's invalid, for
+     * example a different bech32 HRP than the expected one.
+     */
+    async validateAddress(address: string, expectedHrp?: string): Promise<void> {
+        await this.messageHandler.sendMessage({
+            name: 'validateAddress',
+            data: {
+                address,
+                expectedHrp,
+            },
+        });
+    }
+
     /**
      * Fetch alias output IDs
      */
@@ -932,13 +1064,48 @@ export class Client {
     }
 
     /**
-     * Find all blocks by provided block IDs.
+     * Get the metadata of the blocks referenced by a milestone.
+     */
+    async getBlocksReferencedByMilestone(
+        index: number,
+    ): Promise<IBlockMetadata[]> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'getBlocksReferencedByMilestone',
+            data: {
+                index,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
+    /**
+     * Iterate over the metadata of the blocks referenced by the milestones from `startMilestone` to `endMilestone`
+     * (inclusive). The blocks are requested per milestone, so large ranges aren't kept in memory.
      */
-    async findBlocks(blockIds: BlockId[]): Promise<IBlock[]> {
+    async *blocksReferencedByMilestones(
+        startMilestone: number,
+        endMilestone: number,
+    ): AsyncGenerator<IBlockMetadata[]> {
+        for (let index = startMilestone; index <= endMilestone; index++) {
+            yield await this.getBlocksReferencedByMilestone(index);
+        }
+    }
+
+    /**
+     * Find all blocks by provided block IDs, optionally only the blocks with one of the payload types. Failed
+     * requests are handled according to the `batchErrorMode` of the client, with `collect` their errors are returned
+     * next to the blocks.
+     */
+    async findBlocks(
+        blockIds: BlockId[],
+        payloadTypes?: PayloadType[],
+    ): Promise<IBatchResponse<IBlock>> {
         const response = await this.messageHandler.sendMessage({
             name: 'findBlocks',
             data: {
                 blockIds,
+                payloadTypes,
             },
         });
 
@@ -1001,6 +1168,44 @@ export class Client {
         return JSON.parse(response).payload;
     }
 
+    /**
+     * Collects the outputs of a range of addresses that need to be consolidated to the address with the lowest
+     * index in that range. The checkpoint can be stored to resume an interrupted consolidation.
+     */
+    async consolidationCheckpoint(
+        secretManager: SecretManager,
+        generateAddressesOptions: IGenerateAddressesOptions,
+    ): Promise<IConsolidationCheckpoint> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'consolidationCheckpoint',
+            data: {
+                secretManager,
+                generateAddressesOptions,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
+    /**
+     * Consolidates the remaining outputs of a checkpoint and returns the checkpoint without remaining outputs.
+     * Outputs that already got spent are skipped, so the same checkpoint can be used again after an interruption.
+     */
+    async consolidateFundsFromCheckpoint(
+        secretManager: SecretManager,
+        checkpoint: IConsolidationCheckpoint,
+    ): Promise<IConsolidationCheckpoint> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'consolidateFundsFromCheckpoint',
+            data: {
+                secretManager,
+                checkpoint,
+            },
+        });
+
+        return JSON.parse(response).payload;
+    }
+
     /**
      * Reattaches blocks for provided block id. Blocks can be reattached only if they are valid and haven't been
      * confirmed for a while.
@@ -1142,7 +1347,10 @@ export class Client {
     }
 
     /**
-     * Listen to MQTT topics.
+     * Listen to MQTT topics, throws for unknown topics. The result is a JSON serialized `MqttEvent`.
+     *
+     * New milestones are published on `milestone-info/latest` and `milestone-info/confirmed` with the index, timestamp
+     * and milestone ID, and on `milestones` with the milestone payload.
      */
     async listen(
         topics: string[],
@@ -1152,9 +1360,59 @@ export class Client {
     }
 
     /**
-     * Stop listening for provided MQTT topics.
+     * Listen to new outputs of the bech32 addresses, throws if an address is invalid. The event contains the address
+     * that received the output.
+     */
+    async listenAddressOutputs(
+        addresses: string[],
+        callback: (error: Error, event?: IAddressOutputEvent) => void,
+    ): Promise<void> {
+        const topics = addresses.map(
+            (address) => `outputs/unlock/address/${address}`,
+        );
+        return this.listen(topics, (error, result) => {
+            if (error) {
+                return callback(error);
+            }
+            const { topic, payload } = JSON.parse(result);
+            callback(error, {
+                address: topic.split('/')[3],
+                topic,
+                output: payload,
+            });
+        });
+    }
+
+    /**
+     * Call the callback when the MQTT connection is lost and reconnecting, reconnected or disconnected after the
+     * maximum reconnection attempts. Topics are resubscribed after reconnecting.
+     */
+    listenMqttStatus(callback: (status: MqttStatus) => void): void {
+        this.messageHandler.listenMqttStatus(callback);
+    }
+
+    /**
+     * Watch outputs that are expected to be spent by the given transactions, mapped from output ID to transaction ID.
+     * The callback is called with a `doubleSpendDetected` event if an output gets spent by another transaction. The
+     * node is polled in the interval if no MQTT update was received. Resolves once all watched outputs are spent.
+     */
+    async watchDoubleSpends(
+        watchedOutputs: { [outputId: string]: string },
+        intervalMs: number,
+        callback: (event: IDoubleSpendEvent) => void,
+    ): Promise<void> {
+        return this.messageHandler.watchDoubleSpends(
+            watchedOutputs,
+            intervalMs,
+            (_error, event) => callback(JSON.parse(event)),
+        );
+    }
+
+    /**
+     * Stop listening for provided MQTT topics, or for all topics if none are provided. Resolves once the broker
+     * acknowledged it, topics without listeners are ignored.
      */
-    async clearListeners(topics: string[]): Promise<void> {
+    async clearListeners(topics: string[] = []): Promise<void> {
         await this.messageHandler.sendMessage({
             name: 'clearListeners',
             data: {
@@ -1162,4 +1420,15 @@ export class Client {
             },
         });
     }
+
+    /**
+     * Get the MQTT topics with listeners.
+     */
+    async getSubscribedTopics(): Promise<string[]> {
+        const response = await this.messageHandler.sendMessage({
+            name: 'getSubscribedTopics',
+        });
+
+        return JSON.parse(response).payload;
+    }
 }
@@ -1,8 +1,18 @@
 // Copyright 2021-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-import { sendMessageAsync, messageHandlerNew, listen } from './bindings';
-import type { IClientOptions, __ClientMessages__ } from '../types';
+import {
+    sendMessageAsync,
+    messageHandlerNew,
+    listen,
+    listenMqttStatus,
+    watchDoubleSpends,
+} from './bindings';
+import type {
+    IClientOptions,
+    MqttStatus,
+    __ClientMessages__,
+} from '../types';
 
 /** The MessageHandler which sends the commands to the Rust side. */
 export class MessageHandler {
@@ -23,4 +33,24 @@ export class MessageHandler {
     ): Promise<void> {
         return listen(topics, callback, this.messageHandler);
     }
+
+    listenMqttStatus(callback: (status: MqttStatus) => void): void {
+        listenMqttStatus(
+            (_error: Error, status: MqttStatus) => callback(status),
+            this.messageHandler,
+        );
+    }
+
+    async watchDoubleSpends(
+        watchedOutputs: { [outputId: string]: string },
+        intervalMs: number,
+        callback: (error: Error, event: string) => void,
+    ): Promise<void> {
+        return watchDoubleSpends(
+            JSON.stringify(watchedOutputs),
+            intervalMs,
+            callback,
+            this.messageHandler,
+        );
+    }
 }
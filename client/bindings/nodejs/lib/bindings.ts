@@ -6,20 +6,32 @@ import type { MessageHandler } from './MessageHandler';
 // @ts-ignore: path is set to match runtime transpiled js path
 import addon = require('../../build/Release/index.node');
 
-const { initLogger, sendMessage, messageHandlerNew, listen } = addon;
+const {
+    initLogger,
+    sendMessage,
+    messageHandlerNew,
+    clientCount,
+    listen,
+    listenMqttStatus,
+    watchDoubleSpends,
+    initRuntime,
+    shutdown,
+} = addon;
 
+// Without a callback the native function returns a promise
 const sendMessageAsync = (
     message: string,
     handler: MessageHandler,
-): Promise<string> =>
-    new Promise((resolve, reject) => {
-        sendMessage(message, handler, (error: Error, result: string) => {
-            if (error) {
-                reject(error);
-            } else {
-                resolve(result);
-            }
-        });
-    });
+): Promise<string> => sendMessage(message, handler);
 
-export { initLogger, sendMessageAsync, messageHandlerNew, listen };
+export {
+    initLogger,
+    sendMessageAsync,
+    messageHandlerNew,
+    clientCount,
+    listen,
+    listenMqttStatus,
+    watchDoubleSpends,
+    initRuntime,
+    shutdown,
+};
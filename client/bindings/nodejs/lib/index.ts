@@ -6,4 +6,5 @@ export * from './Client';
 export * from './constants';
 export * from './utils';
 export * from './logger';
+export * from './runtime';
 export * from '../types';
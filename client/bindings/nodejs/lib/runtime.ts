@@ -0,0 +1,27 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import type { IRuntimeOptions } from '../types/runtimeOptions';
+import {
+    clientCount as clientCountBinding,
+    initRuntime as initRuntimeBinding,
+    shutdown as shutdownBinding,
+} from './bindings';
+
+/**
+ * Initialize the native runtime with custom options, this has to be called before a client is used, also after a
+ * shutdown. Throws if the runtime is already initialized.
+ */
+export const initRuntime = (options: IRuntimeOptions): void =>
+    initRuntimeBinding(JSON.stringify(options));
+
+/**
+ * Shut down the native runtime, which stops all running tasks like MQTT listeners.
+ * Clients created before throw an error afterwards, new clients start a new runtime.
+ */
+export const shutdown = (): void => shutdownBinding();
+
+/**
+ * The number of native clients that weren't garbage collected yet, to check for leaks.
+ */
+export const clientCount = (): number => clientCountBinding();
@@ -5,14 +5,88 @@
 
 mod message_handler;
 
+use std::sync::{Mutex, PoisonError};
+
 use fern_logger::{logger_init, LoggerConfig, LoggerOutputConfigBuilder};
+use iota_client::builder::{build_runtime, RuntimeOptions};
 use neon::prelude::*;
-use once_cell::sync::Lazy;
-use tokio::runtime::Runtime;
+use tokio::runtime::{Handle, Runtime};
 
 pub use self::message_handler::*;
 
-pub static RUNTIME: Lazy<Runtime> = Lazy::new(|| Runtime::new().unwrap());
+struct RuntimeState {
+    runtime: Option<Runtime>,
+    // Increased on every shutdown, so clients created before can't use the next runtime
+    generation: usize,
+}
+
+static RUNTIME: Mutex<RuntimeState> = Mutex::new(RuntimeState {
+    runtime: None,
+    generation: 0,
+});
+
+/// Returns the generation of the shared runtime, which changes when it's shut down.
+pub fn runtime_generation() -> usize {
+    RUNTIME.lock().unwrap_or_else(PoisonError::into_inner).generation
+}
+
+/// Returns a handle to the shared runtime, which is created on first use, or throws if it was shut down since the
+/// given generation.
+pub fn runtime_handle<'a>(cx: &mut impl Context<'a>, generation: usize) -> NeonResult<Handle> {
+    let mut state = RUNTIME.lock().unwrap_or_else(PoisonError::into_inner);
+    if state.generation != generation {
+        drop(state);
+        return cx.throw_error("the runtime was shut down, a new client has to be created");
+    }
+    let handle = state
+        .runtime
+        .get_or_insert_with(|| build_runtime(&RuntimeOptions::default()).expect("failed to create the runtime"))
+        .handle()
+        .clone();
+
+    Ok(handle)
+}
+
+/// Creates the shared runtime with the given options, this has to happen before it's used, also after a shutdown.
+pub fn init_runtime(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let options = cx.argument::<JsString>(0)?.value(&mut cx);
+    let options: RuntimeOptions = match serde_json::from_str(&options) {
+        Ok(options) => options,
+        Err(e) => return cx.throw_error(format!("invalid runtime options: {e}")),
+    };
+
+    let mut state = RUNTIME.lock().unwrap_or_else(PoisonError::into_inner);
+    if state.runtime.is_some() {
+        drop(state);
+        return cx.throw_error("the runtime is already initialized");
+    }
+
+    match build_runtime(&options) {
+        Ok(runtime) => {
+            state.runtime = Some(runtime);
+            Ok(cx.undefined())
+        }
+        Err(e) => {
+            drop(state);
+            cx.throw_error(format!("failed to create the runtime: {e}"))
+        }
+    }
+}
+
+/// Shuts down the shared runtime, which stops all running tasks like MQTT listeners. Clients created before throw an
+/// error afterwards, new clients start a new runtime.
+pub fn shutdown(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let runtime = {
+        let mut state = RUNTIME.lock().unwrap_or_else(PoisonError::into_inner);
+        state.generation += 1;
+        state.runtime.take()
+    };
+    if let Some(runtime) = runtime {
+        // Don't wait for the tasks, they might wait for the JS thread
+        runtime.shutdown_background();
+    }
+    Ok(cx.undefined())
+}
 
 pub fn init_logger(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     let config = cx.argument::<JsString>(0)?.value(&mut cx);
@@ -27,10 +101,16 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     // Message handler methods.
     cx.export_function("sendMessage", message_handler::send_message)?;
     cx.export_function("messageHandlerNew", message_handler::message_handler_new)?;
+    cx.export_function("clientCount", message_handler::client_count)?;
 
     // MQTT
     cx.export_function("listen", message_handler::listen)?;
+    cx.export_function("listenMqttStatus", message_handler::listen_mqtt_status)?;
+
+    cx.export_function("watchDoubleSpends", message_handler::watch_double_spends)?;
 
     cx.export_function("initLogger", init_logger)?;
+    cx.export_function("initRuntime", init_runtime)?;
+    cx.export_function("shutdown", shutdown)?;
     Ok(())
 }
@@ -1,30 +1,54 @@
 // Copyright 2021-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use iota_client::{
+    block::{output::OutputId, payload::transaction::TransactionId},
     message_interface::{create_message_handler, ClientMessageHandler, Message, Response},
-    mqtt::Topic,
+    mqtt::{MqttEvent, Topic},
 };
 use neon::prelude::*;
 
 type JsCallback = Root<JsFunction<JsObject>>;
 
+// Number of message handlers that weren't dropped yet.
+static MESSAGE_HANDLER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
 pub struct MessageHandler {
     channel: Channel,
     client_message_handler: ClientMessageHandler,
+    // The generation of the shared runtime the message handler was created in
+    runtime_generation: usize,
 }
 
+// The message handler is dropped when the JS object is garbage collected and all its running tasks are done.
 impl Finalize for MessageHandler {}
 
+impl Drop for MessageHandler {
+    fn drop(&mut self) {
+        MESSAGE_HANDLER_COUNT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 impl MessageHandler {
     fn new(channel: Channel, options: String) -> Arc<Self> {
         let client_message_handler = create_message_handler(Some(options)).expect("error initializing account manager");
 
+        MESSAGE_HANDLER_COUNT.fetch_add(1, Ordering::SeqCst);
+
         Arc::new(Self {
             channel,
             client_message_handler,
+            runtime_generation: crate::runtime_generation(),
         })
     }
 
@@ -32,13 +56,13 @@ impl MessageHandler {
         match serde_json::from_str::<Message>(&serialized_message) {
             Ok(message) => {
                 let res = self.client_message_handler.send_message(message).await;
-                let mut is_err = matches!(res, Response::Error(_) | Response::Panic(_));
+                let mut is_err = matches!(res, Response::Error(_) | Response::Panic { .. });
 
-                let msg = match serde_json::to_string(&res) {
+                let msg = match self.client_message_handler.response_to_json(&res) {
                     Ok(msg) => msg,
                     Err(e) => {
                         is_err = true;
-                        serde_json::to_string(&Response::Error(e.into()))
+                        serde_json::to_string(&Response::Error(e))
                             .expect("the response is generated manually, so unwrap is safe.")
                     }
                 };
@@ -62,13 +86,41 @@ pub fn message_handler_new(mut cx: FunctionContext) -> JsResult<JsBox<Arc<Messag
     Ok(cx.boxed(message_handler))
 }
 
-pub fn send_message(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+// Returns the number of message handlers that weren't dropped yet, to check for leaks.
+pub fn client_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(MESSAGE_HANDLER_COUNT.load(Ordering::SeqCst) as f64))
+}
+
+// Returns a promise, or calls the callback if one is passed.
+pub fn send_message(mut cx: FunctionContext) -> JsResult<JsValue> {
     let message = cx.argument::<JsString>(0)?;
     let message = message.value(&mut cx);
     let message_handler = Arc::clone(&&cx.argument::<JsBox<Arc<MessageHandler>>>(1)?);
-    let callback = cx.argument::<JsFunction>(2)?.root(&mut cx);
+    let runtime = crate::runtime_handle(&mut cx, message_handler.runtime_generation)?;
+    let callback = match cx.argument_opt(2) {
+        Some(callback) if callback.is_a::<JsFunction, _>(&mut cx) => {
+            callback.downcast_or_throw::<JsFunction, _>(&mut cx)?.root(&mut cx)
+        }
+        _ => {
+            let (deferred, promise) = cx.promise();
+
+            runtime.spawn(async move {
+                let (response, is_error) = message_handler.send_message(message).await;
+                deferred.settle_with(&message_handler.channel, move |mut cx| {
+                    let response = cx.string(response);
+                    if is_error {
+                        cx.throw(response)
+                    } else {
+                        Ok(response)
+                    }
+                });
+            });
+
+            return Ok(promise.upcast());
+        }
+    };
 
-    crate::RUNTIME.spawn(async move {
+    runtime.spawn(async move {
         let (response, is_error) = message_handler.send_message(message).await;
         message_handler.channel.send(move |mut cx| {
             let cb = callback.into_inner(&mut cx);
@@ -89,7 +141,7 @@ pub fn send_message(mut cx: FunctionContext) -> JsResult<JsUndefined> {
         });
     });
 
-    Ok(cx.undefined())
+    Ok(cx.undefined().upcast())
 }
 
 // MQTT
@@ -98,15 +150,19 @@ pub fn listen(mut cx: FunctionContext) -> JsResult<JsPromise> {
     let vec: Vec<Handle<JsValue>> = js_arr_handle.to_vec(&mut cx)?;
     let mut topics = vec![];
     for topic_string in vec {
-        let topic = topic_string.downcast::<JsString, FunctionContext>(&mut cx).unwrap();
-        topics.push(Topic::try_from(topic.value(&mut cx).as_str().to_string()).expect("invalid MQTT topic"));
+        let topic = topic_string.downcast_or_throw::<JsString, FunctionContext>(&mut cx)?;
+        match Topic::try_from(topic.value(&mut cx)) {
+            Ok(topic) => topics.push(topic),
+            Err(e) => return cx.throw_error(e.to_string()),
+        }
     }
 
     let callback = Arc::new(cx.argument::<JsFunction>(1)?.root(&mut cx));
     let message_handler = Arc::clone(&&cx.argument::<JsBox<Arc<MessageHandler>>>(2)?);
+    let runtime = crate::runtime_handle(&mut cx, message_handler.runtime_generation)?;
     let (deferred, promise) = cx.promise();
 
-    crate::RUNTIME.spawn(async move {
+    runtime.spawn(async move {
         let channel0 = message_handler.channel.clone();
         let channel1 = message_handler.channel.clone();
         message_handler
@@ -122,6 +178,84 @@ pub fn listen(mut cx: FunctionContext) -> JsResult<JsPromise> {
     Ok(promise)
 }
 
+// Calls the callback with "connected", "reconnecting" or "disconnected" when the MQTT connection status changes.
+pub fn listen_mqtt_status(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = Arc::new(cx.argument::<JsFunction>(0)?.root(&mut cx));
+    let message_handler = cx.argument::<JsBox<Arc<MessageHandler>>>(1)?;
+    // Only the receiver and channel are moved into the task, so it ends when the client is dropped.
+    let mut receiver = message_handler.client_message_handler.client.mqtt_event_receiver();
+    let channel = message_handler.channel.clone();
+    let runtime = crate::runtime_handle(&mut cx, message_handler.runtime_generation)?;
+
+    runtime.spawn(async move {
+        while receiver.changed().await.is_ok() {
+            let status = match *receiver.borrow() {
+                MqttEvent::Connected => "connected",
+                MqttEvent::Reconnecting => "reconnecting",
+                MqttEvent::Disconnected => "disconnected",
+            };
+            call_event_callback(&channel, status.to_string(), callback.clone());
+        }
+    });
+
+    Ok(cx.undefined())
+}
+
+// Watches the outputs, given as JSON object from output ID to the transaction ID that is expected to spend it, and
+// calls the callback with a `doubleSpendDetected` event if another transaction spends one. The promise resolves once
+// all outputs are spent.
+pub fn watch_double_spends(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let watched_outputs = cx.argument::<JsString>(0)?.value(&mut cx);
+    let watched_outputs = match parse_watched_outputs(&watched_outputs) {
+        Ok(watched_outputs) => watched_outputs,
+        Err(e) => return cx.throw_error(format!("invalid watched outputs: {e}")),
+    };
+    let interval = Duration::from_millis(cx.argument::<JsNumber>(1)?.value(&mut cx) as u64);
+    let callback = Arc::new(cx.argument::<JsFunction>(2)?.root(&mut cx));
+    let message_handler = Arc::clone(&&cx.argument::<JsBox<Arc<MessageHandler>>>(3)?);
+    let runtime = crate::runtime_handle(&mut cx, message_handler.runtime_generation)?;
+    let (deferred, promise) = cx.promise();
+
+    runtime.spawn(async move {
+        let channel = message_handler.channel.clone();
+        let result = message_handler
+            .client_message_handler
+            .watch_double_spends(watched_outputs, interval, move |event| {
+                call_event_callback(&channel, event, callback.clone())
+            })
+            .await;
+        let error = result.err().map(|e| {
+            message_handler
+                .client_message_handler
+                .response_to_json(&Response::Error(e))
+                .expect("the response is generated manually, so unwrap is safe.")
+        });
+
+        deferred.settle_with(&message_handler.channel, move |mut cx| match error {
+            Some(error) => {
+                let error = cx.string(error);
+                cx.throw(error)
+            }
+            None => Ok(cx.undefined()),
+        });
+    });
+
+    Ok(promise)
+}
+
+fn parse_watched_outputs(watched_outputs: &str) -> Result<HashMap<OutputId, TransactionId>, String> {
+    serde_json::from_str::<HashMap<String, String>>(watched_outputs)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(output_id, transaction_id)| {
+            Ok((
+                OutputId::from_str(&output_id).map_err(|e| e.to_string())?,
+                TransactionId::from_str(&transaction_id).map_err(|e| e.to_string())?,
+            ))
+        })
+        .collect()
+}
+
 fn call_event_callback(channel: &neon::event::Channel, event_data: String, callback: Arc<JsCallback>) {
     channel.send(move |mut cx| {
         let cb = (*callback).to_inner(&mut cx);
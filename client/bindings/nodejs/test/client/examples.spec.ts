@@ -65,9 +65,9 @@ describe.skip('Main examples', () => {
 
         const addressOutputs = await client.getOutputs(outputIdsResponse.items);
 
-        expect(addressOutputs).toBeDefined();
+        expect(addressOutputs.errors).toHaveLength(0);
 
-        addressOutputs.forEach((output) => {
+        addressOutputs.items.forEach(({ item: output }) => {
             expect(output.metadata.blockId).toBeValidBlockId();
         });
     });
@@ -25,7 +25,8 @@ describe.skip('Block methods', () => {
         const blockIds = await client.getTips();
         const blocks = await client.findBlocks(blockIds);
 
-        expect(blocks.length).toBe(blockIds.length);
+        expect(blocks.items.length).toBe(blockIds.length);
+        expect(blocks.errors).toHaveLength(0);
     });
 
     it('gets block as raw bytes', async () => {
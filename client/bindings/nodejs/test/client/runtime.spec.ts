@@ -0,0 +1,61 @@
+import { setFlagsFromString } from 'v8';
+import { runInNewContext } from 'vm';
+import { Client, clientCount, initRuntime, shutdown } from '../../lib';
+import '../customMatchers';
+
+setFlagsFromString('--expose-gc');
+const gc: () => void = runInNewContext('gc');
+
+// Collects the garbage until at most `count` native clients are left, they are
+// dropped when their JS objects are collected. Returns the number of clients.
+async function collectClients(count: number): Promise<number> {
+    for (let attempt = 0; attempt < 100 && clientCount() > count; attempt++) {
+        gc();
+        await new Promise((resolve) => setImmediate(resolve));
+    }
+    return clientCount();
+}
+
+const address =
+    'rms1qpllaj0pyveqfkwxmnngz2c488hfdtmfrj3wfkgxtk4gtyrax0jaxzt70zy';
+
+describe('Native runtime', () => {
+    it('builds a new client after a shutdown', async () => {
+        const client = new Client({});
+        const hexAddress = await client.bech32ToHex(address);
+
+        shutdown();
+        await expect(client.bech32ToHex(address)).rejects.toThrow(
+            'the runtime was shut down',
+        );
+
+        // A new client starts a new runtime
+        const newClient = new Client({});
+        await expect(newClient.bech32ToHex(address)).resolves.toBe(hexAddress);
+    });
+
+    it('rejects a second initialization', async () => {
+        // Other tests might have started the runtime already
+        shutdown();
+
+        initRuntime({ workerThreads: 1, threadNamePrefix: 'runtime-test' });
+        expect(() => initRuntime({ workerThreads: 2 })).toThrow(
+            'the runtime is already initialized',
+        );
+
+        // The initialized runtime is used by new clients
+        const client = new Client({});
+        await expect(client.bech32ToHex(address)).resolves.toMatch(/^0x/);
+    });
+
+    it('counts the native clients', async () => {
+        const count = clientCount();
+
+        let client: Client | undefined = new Client({});
+        expect(clientCount()).toBe(count + 1);
+        await client.bech32ToHex(address);
+
+        client = undefined;
+        expect(await collectClients(count)).toBeLessThanOrEqual(count);
+    });
+});
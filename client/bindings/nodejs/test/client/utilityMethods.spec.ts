@@ -67,6 +67,51 @@ describe('Client utility methods', () => {
         );
     });
 
+    it('omits null fields if emitNulls is disabled', async () => {
+        const nodes = ['http://localhost:14265'];
+
+        const node = await new Client({ nodes }).getNode();
+        expect(node.auth).toBeNull();
+
+        const nodeWithoutNulls = await new Client({
+            nodes,
+            emitNulls: false,
+        }).getNode();
+        expect(nodeWithoutNulls).not.toHaveProperty('auth');
+        expect(nodeWithoutNulls.url).toBe(node.url);
+    });
+
+    it('represents addresses in the configured format', async () => {
+        const secretManager = {
+            mnemonic:
+                'endorse answer radar about source reunion marriage tag sausage weekend frost daring base attack because joke dream slender leisure group reason prepare broken river',
+        };
+        const options = { range: { start: 0, end: 1 }, bech32Hrp: 'rms' };
+
+        const [bech32] = await offlineClient.generateAddresses(
+            secretManager,
+            options,
+        );
+        const [both] = await new Client({
+            addressFormat: 'both',
+        }).generateAddresses(secretManager, options);
+
+        expect(both).toStrictEqual({
+            bech32,
+            hex: await offlineClient.bech32ToHex(bech32),
+        });
+    });
+
+    it('uses configured error messages', async () => {
+        const client = new Client({
+            errorMessages: { block: 'invalid address' },
+        });
+
+        await expect(client.bech32ToHex('invalid')).rejects.toMatch(
+            '"error":"invalid address"',
+        );
+    });
+
     it('alias id to address', async () => {
         const aliasId =
             '0xcf077d276686ba64c0404b9eb2d15556782113c5a1985f262b70f9964d3bbd7f';
@@ -0,0 +1,9 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * How addresses are represented in responses. `hex` is the packed address, starting with the address kind. `both` keeps
+ * the bech32 address and adds the hex representation in a field with the `Hex` suffix, like `addressHex`. Lists of
+ * generated addresses stay bech32 with `both`.
+ */
+export type AddressFormat = 'bech32' | 'hex' | 'both';
@@ -0,0 +1,14 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import type { IOutputResponse } from '@iota/types';
+
+/** An output of a watched address, published over MQTT. */
+export interface IAddressOutputEvent {
+    /** The bech32 address that the output belongs to */
+    address: string;
+    /** The MQTT topic the output was published on */
+    topic: string;
+    /** The output with its metadata */
+    output: IOutputResponse;
+}
@@ -0,0 +1,8 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/** Options for fetching the output IDs of an address. */
+export interface IAddressOutputIdsOptions {
+    /** Only return outputs of this type (3: basic, 4: alias, 5: foundry, 6: NFT). */
+    outputType?: number;
+}
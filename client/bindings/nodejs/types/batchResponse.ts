@@ -0,0 +1,22 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/** The error of a request, as returned by the message handler */
+export interface IClientError {
+    /** The kind of the error, like `notFound` */
+    type: string;
+    /** The error message */
+    error: string;
+    /** A machine-readable code for common errors, like `rateLimited` */
+    code?: string;
+    /** The blocks that were already sent, if sending multiple blocks failed part way (`partiallySent`) */
+    blockIds?: string[];
+}
+
+/** The successful items and the errors of the failed items of a batch request */
+export interface IBatchResponse<T> {
+    /** The successful items with their index in the request */
+    items: { index: number; item: T }[];
+    /** The errors of the failed items with their index in the request */
+    errors: { index: number; error: IClientError }[];
+}
@@ -0,0 +1,20 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import type { IBlockMetadata } from '@iota/types';
+
+/** The lifecycle state of a block */
+export type BlockState =
+    | 'pending'
+    | 'solid'
+    | 'referenced'
+    | 'confirmed'
+    | 'conflicting';
+
+/** Block metadata with the state of the block and the name of its conflict reason */
+export interface IBlockMetadataWithState extends IBlockMetadata {
+    /** The state derived from the metadata, `pending` or `solid` until the block is referenced by a milestone */
+    state: BlockState;
+    /** The camelCase name of the conflict reason, like `inputUtxoAlreadySpent`, if the block is conflicting */
+    conflictReasonName?: string;
+}
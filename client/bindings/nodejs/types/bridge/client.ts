@@ -3,6 +3,7 @@ import type { SecretManager } from '../secretManager';
 import type { IGenerateAddressesOptions } from '../generateAddressesOptions';
 import type { IBuildBlockOptions } from '../buildBlockOptions';
 import type { BlockId } from '../blockId';
+import type { PayloadType } from '../payloadType';
 import type {
     IInputSigningData,
     IPreparedTransactionData,
@@ -14,6 +15,8 @@ import type {
     QueryParameter,
 } from '../queryParameters';
 import type { IAuth } from '../network';
+import type { IConsolidationCheckpoint } from '../consolidationCheckpoint';
+import type { ITransactionInclusionProof } from '../transactionInclusionProof';
 import type { IBasicOutputBuilderOptions } from '../outputBuilderOptions/basicOutputOptions';
 import type { IAliasOutputBuilderOptions } from '../outputBuilderOptions/aliasOutputOptions';
 import type { IFoundryOutputBuilderOptions } from '../outputBuilderOptions/foundryOutputOptions';
@@ -101,6 +104,14 @@ export interface __BuildAndPostBlockMessage__ {
     };
 }
 
+export interface __PostTaggedDataMessage__ {
+    name: 'postTaggedData';
+    data: {
+        tag: string;
+        data: string;
+    };
+}
+
 export interface __GetTipsMessage__ {
     name: 'getTips';
 }
@@ -123,6 +134,21 @@ export interface __GetBlockMetadataMessage__ {
     };
 }
 
+export interface __GetBlocksReferencedByMilestoneMessage__ {
+    name: 'getBlocksReferencedByMilestone';
+    data: {
+        index: number;
+    };
+}
+
+export interface __AddressOutputIdsMessage__ {
+    name: 'addressOutputIds';
+    data: {
+        address: string;
+        outputType?: number;
+    };
+}
+
 export interface __FindInputsMessage__ {
     name: 'findInputs';
     data: {
@@ -195,6 +221,20 @@ export interface __ParseBech32AddressMessage__ {
     };
 }
 
+export interface __ParseAddressMessage__ {
+    name: 'parseAddress';
+    data: {
+        address: string;
+    };
+}
+
+export interface __ParseOutputIdMessage__ {
+    name: 'parseOutputId';
+    data: {
+        outputId: string;
+    };
+}
+
 export interface __BlockIdMessage__ {
     name: 'blockId';
     data: {
@@ -323,6 +363,21 @@ export interface __GetIncludedBlockMetadataMessage__ {
     };
 }
 
+export interface __GetTransactionInclusionProofMessage__ {
+    name: 'getTransactionInclusionProof';
+    data: {
+        transactionId: string;
+    };
+}
+
+export interface __VerifyTransactionInclusionProofMessage__ {
+    name: 'verifyTransactionInclusionProof';
+    data: {
+        proof: ITransactionInclusionProof;
+        transactionId: string;
+    };
+}
+
 export interface __Bech32ToHexMessage__ {
     name: 'bech32ToHex';
     data: {
@@ -366,6 +421,15 @@ export interface __IsAddressValidMessage__ {
     name: 'isAddressValid';
     data: {
         address: string;
+        expectedHrp?: string;
+    };
+}
+
+export interface __ValidateAddressMessage__ {
+    name: 'validateAddress';
+    data: {
+        address: string;
+        expectedHrp?: string;
     };
 }
 
@@ -422,6 +486,7 @@ export interface __FindBlocksMessage__ {
     name: 'findBlocks';
     data: {
         blockIds: string[];
+        payloadTypes?: PayloadType[];
     };
 }
 
@@ -449,6 +514,22 @@ export interface __ConsolidateFundsMessage__ {
     };
 }
 
+export interface __ConsolidationCheckpointMessage__ {
+    name: 'consolidationCheckpoint';
+    data: {
+        secretManager: SecretManager;
+        generateAddressesOptions: IGenerateAddressesOptions;
+    };
+}
+
+export interface __ConsolidateFundsFromCheckpointMessage__ {
+    name: 'consolidateFundsFromCheckpoint';
+    data: {
+        secretManager: SecretManager;
+        checkpoint: IConsolidationCheckpoint;
+    };
+}
+
 export interface __ReattachMessage__ {
     name: 'reattach';
     data: {
@@ -514,3 +595,7 @@ export interface __ClearListenersMessage__ {
         topics: string[];
     };
 }
+
+export interface __GetSubscribedTopicsMessage__ {
+    name: 'getSubscribedTopics';
+}
@@ -11,10 +11,13 @@ import type {
     __GenerateAddressesMessage__,
     __PostBlockMessage__,
     __BuildAndPostBlockMessage__,
+    __PostTaggedDataMessage__,
     __GetTipsMessage__,
     __GetNetworkInfoMessage__,
     __GetBlockMessage__,
     __GetBlockMetadataMessage__,
+    __GetBlocksReferencedByMilestoneMessage__,
+    __AddressOutputIdsMessage__,
     __FindInputsMessage__,
     __FindOutputsMessage__,
     __GetLedgerNanoStatusMessage__,
@@ -22,6 +25,8 @@ import type {
     __SignTransactionMessage__,
     __PostBlockPayloadMessage__,
     __ParseBech32AddressMessage__,
+    __ParseAddressMessage__,
+    __ParseOutputIdMessage__,
     __BlockIdMessage__,
     __GetNodeMessage__,
     __GetNetworkIdMessage__,
@@ -41,12 +46,15 @@ import type {
     __GetTreasuryMessage__,
     __GetIncludedBlockMessage__,
     __GetIncludedBlockMetadataMessage__,
+    __GetTransactionInclusionProofMessage__,
+    __VerifyTransactionInclusionProofMessage__,
     __Bech32ToHexMessage__,
     __HexToBech32Message__,
     __AliasIdToBech32Message__,
     __NftIdToBech32Message__,
     __HexPublicKeyToBech32AddressMessage__,
     __IsAddressValidMessage__,
+    __ValidateAddressMessage__,
     __AliasOutputIdsMessage__,
     __AliasOutputIdMessage__,
     __NftOutputIdsMessage__,
@@ -58,6 +66,8 @@ import type {
     __RetryMessage__,
     __RetryUntilIncludedMessage__,
     __ConsolidateFundsMessage__,
+    __ConsolidationCheckpointMessage__,
+    __ConsolidateFundsFromCheckpointMessage__,
     __ReattachMessage__,
     __ReattachUncheckedMessage__,
     __PromoteMessage__,
@@ -74,6 +84,7 @@ import type {
     __BuildNftOutputMessage__,
     __HashTransactionEssenceMessage__,
     __ClearListenersMessage__,
+    __GetSubscribedTopicsMessage__,
     __SignatureUnlockMessage__,
 } from './client';
 
@@ -90,10 +101,13 @@ export type __ClientMessages__ =
     | __GenerateAddressesMessage__
     | __PostBlockMessage__
     | __BuildAndPostBlockMessage__
+    | __PostTaggedDataMessage__
     | __GetTipsMessage__
     | __GetNetworkInfoMessage__
     | __GetBlockMessage__
     | __GetBlockMetadataMessage__
+    | __GetBlocksReferencedByMilestoneMessage__
+    | __AddressOutputIdsMessage__
     | __FindInputsMessage__
     | __FindOutputsMessage__
     | __GetLedgerNanoStatusMessage__
@@ -103,6 +117,8 @@ export type __ClientMessages__ =
     | __StoreMnemonicMessage__
     | __PostBlockPayloadMessage__
     | __ParseBech32AddressMessage__
+    | __ParseAddressMessage__
+    | __ParseOutputIdMessage__
     | __BlockIdMessage__
     | __GetNodeMessage__
     | __GetNetworkIdMessage__
@@ -126,12 +142,15 @@ export type __ClientMessages__ =
     | __GetTreasuryMessage__
     | __GetIncludedBlockMessage__
     | __GetIncludedBlockMetadataMessage__
+    | __GetTransactionInclusionProofMessage__
+    | __VerifyTransactionInclusionProofMessage__
     | __Bech32ToHexMessage__
     | __HexToBech32Message__
     | __AliasIdToBech32Message__
     | __NftIdToBech32Message__
     | __HexPublicKeyToBech32AddressMessage__
     | __IsAddressValidMessage__
+    | __ValidateAddressMessage__
     | __AliasOutputIdsMessage__
     | __AliasOutputIdMessage__
     | __NftOutputIdsMessage__
@@ -143,6 +162,8 @@ export type __ClientMessages__ =
     | __RetryMessage__
     | __RetryUntilIncludedMessage__
     | __ConsolidateFundsMessage__
+    | __ConsolidationCheckpointMessage__
+    | __ConsolidateFundsFromCheckpointMessage__
     | __ReattachMessage__
     | __ReattachUncheckedMessage__
     | __PromoteMessage__
@@ -153,4 +174,5 @@ export type __ClientMessages__ =
     | __BuildFoundryOutputMessage__
     | __BuildNftOutputMessage__
     | __HashTransactionEssenceMessage__
-    | __ClearListenersMessage__;
+    | __ClearListenersMessage__
+    | __GetSubscribedTopicsMessage__;
@@ -5,6 +5,7 @@ import type { IUTXOInput, OutputTypes } from '@iota/types';
 import type { CoinType } from '../lib';
 import type { IRange } from './range';
 import type { Burn } from './burn';
+import type { InputSelectionStrategy } from './inputSelectionStrategy';
 
 /** Options to build a new block, possibly with payloads */
 export interface IBuildBlockOptions {
@@ -13,6 +14,8 @@ export interface IBuildBlockOptions {
     initialAddressIndex?: number;
     inputs?: IUTXOInput[];
     inputRange?: IRange;
+    /** Order in which inputs are picked to cover the output amounts, `smallestFirst` by default */
+    inputSelectionStrategy?: InputSelectionStrategy;
     /** Bech32 encoded output address and amount */
     output?: IClientBlockBuilderOutputAddress;
     /** Hex encoded output address and amount */
@@ -21,7 +24,7 @@ export interface IBuildBlockOptions {
     customRemainderAddress?: string;
     tag?: string;
     data?: string;
-    /** Parent block IDs */
+    /** 1-8 parent block IDs, tips are requested from the node if not set */
     parents?: string[];
     /** Explicit burning of aliases, nfts, foundries and native tokens */
     burn?: Burn;
@@ -1,5 +1,6 @@
 // Copyright 2021-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
+import type { AddressFormat } from './addressFormat';
 import type { IMqttBrokerOptions, INetworkInfo, INode } from './network';
 
 /** Options for the client builder */
@@ -14,6 +15,8 @@ export interface IClientOptions {
     ignoreNodeHealth?: boolean;
     /** Interval in which nodes will be checked for their sync status and the NetworkInfo gets updated */
     nodeSyncInterval?: IDuration;
+    /** If the node syncing is disabled, requests are sent to the configured nodes directly then */
+    nodeSyncDisabled?: boolean;
     /** If node quorum is enabled. Will compare the responses from multiple nodes and only returns the
      * response if quorum_threshold of the nodes return the same one
      */
@@ -30,10 +33,26 @@ export interface IClientOptions {
     apiTimeout?: IDuration;
     /** Timeout when sending a block that requires remote proof of work */
     remotePowTimeout?: IDuration;
+    /** Timeouts for categories of API requests, which override the API timeout */
+    apiTimeouts?: IApiTimeouts;
     /** The amount of threads to be used for proof of work */
     powWorkerCount?: number;
-    /** Whether the PoW should be done locally or remotely. */
+    /** Whether the PoW should be done locally or remotely. If no node supports remote PoW and the fallback to local PoW is disabled, posting blocks fails with the `remotePowNotSupported` error code. */
     localPow?: boolean;
+    /** Headers which are added to every request, a JWT for a node is set in its `auth` instead */
+    headers?: { [name: string]: string };
+    /** How many outputs are requested at once when fetching multiple outputs, defaults to 100 */
+    outputFetchConcurrency?: number;
+    /** Whether a failed item aborts batch requests like `getOutputs()` or their errors are returned next to the successful items, defaults to `collect` */
+    batchErrorMode?: 'failFast' | 'collect';
+    /** Whether fields without a value are included as `null` in responses, defaults to true */
+    emitNulls?: boolean;
+    /** How bech32 addresses are represented in responses, defaults to `bech32` */
+    addressFormat?: AddressFormat;
+    /** User-friendly messages for errors, keyed by the `code` or `type` of the error */
+    errorMessages?: { [codeOrType: string]: string };
+    /** Add the tag and data of tagged data payloads decoded as UTF-8 as `tagUtf8` and `dataUtf8` to responses, if they are valid UTF-8 */
+    decodeTaggedDataUtf8?: boolean;
 }
 
 /** Time duration */
@@ -41,3 +60,15 @@ export interface IDuration {
     secs: number;
     nanos: number;
 }
+
+/** Timeouts for categories of API requests */
+export interface IApiTimeouts {
+    /** Timeout for node info and health requests */
+    info?: IDuration;
+    /** Timeout for tips requests */
+    tips?: IDuration;
+    /** Timeout for posting blocks with local proof of work */
+    postBlock?: IDuration;
+    /** Timeout for output requests */
+    output?: IDuration;
+}
@@ -0,0 +1,20 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/** Output IDs that belong to an address */
+export interface IAddressOutputs {
+    /** The index of the address */
+    addressIndex: number;
+    /** The output IDs */
+    outputIds: string[];
+}
+
+/** The state of a consolidation, containing the outputs that still need to be consolidated */
+export interface IConsolidationCheckpoint {
+    /** The address to which the funds get consolidated */
+    consolidationAddress: string;
+    /** The hex representation of the consolidation address, only with the `both` address format */
+    consolidationAddressHex?: string;
+    /** The outputs that still need to be consolidated */
+    remainingOutputs: IAddressOutputs[];
+}
@@ -0,0 +1,18 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/** A watched output that got spent by another transaction than the expected one */
+export interface IDoubleSpend {
+    /** The watched output */
+    outputId: string;
+    /** The transaction that was expected to spend the output */
+    transactionId: string;
+    /** The transaction that spent the output instead */
+    conflictingTransactionId: string;
+}
+
+/** Event of a double spend watch */
+export interface IDoubleSpendEvent {
+    type: 'doubleSpendDetected';
+    payload: IDoubleSpend;
+}
@@ -1,15 +1,28 @@
+export * from './addressFormat';
+export * from './addressOutputEvent';
+export * from './addressOutputIdsOptions';
+export * from './batchResponse';
 export * from './blockId';
+export * from './blockMetadataWithState';
 export * from './bridge';
 export * from './buildBlockOptions';
 export * from './burn';
 export * from './clientOptions';
+export * from './consolidationCheckpoint';
+export * from './doubleSpend';
 export * from './generateAddressesOptions';
+export * from './inputSelectionStrategy';
 export * from './ledgerNanoStatus';
+export * from './mqttEvent';
 export * from './network';
 export * from './nodeInfo';
 export * from './outputIdsResponse';
 export * from './outputBuilderOptions';
+export * from './payloadType';
 export * from './preparedTransactionData';
 export * from './queryParameters';
 export * from './range';
+export * from './runtimeOptions';
 export * from './secretManager';
+export * from './transactionInclusionProof';
+export * from './typedBlock';
@@ -0,0 +1,8 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/** The order in which inputs are picked to cover the output amounts. */
+export type InputSelectionStrategy =
+    | 'smallestFirst'
+    | 'largestFirst'
+    | 'minimizeInputs';
@@ -0,0 +1,19 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import type {
+    IBlock,
+    IMilestonePayload,
+    IReceiptMilestoneOption,
+} from '@iota/types';
+
+/** An event published on a MQTT topic, the payload is deserialized according to its type. */
+export type MqttEvent =
+    | { topic: string; payloadType: 'block'; payload: IBlock }
+    | { topic: string; payloadType: 'milestone'; payload: IMilestonePayload }
+    | {
+          topic: string;
+          payloadType: 'receipt';
+          payload: IReceiptMilestoneOption;
+      }
+    | { topic: string; payloadType: 'json'; payload: unknown };
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 import type { INodeInfoProtocol } from '@iota/types';
+import type { IDuration } from './clientOptions';
 
 /**
  * Network types.
@@ -19,6 +20,9 @@ export interface IAuth {
     basicAuthNamePwd?: [string, string];
 }
 
+/** Status of the MQTT connection. */
+export type MqttStatus = 'connected' | 'reconnecting' | 'disconnected';
+
 /**
  * Options for the MQTT broker.
  */
@@ -29,6 +33,10 @@ export interface IMqttBrokerOptions {
     useWs?: boolean;
     port?: number;
     maxReconnectionAttempts?: number;
+    /** Maximum delay between reconnection attempts, the delay doubles with every failed attempt. Defaults to 30 seconds. */
+    maxReconnectionBackoff?: IDuration;
+    /** Fixed client ID for the MQTT connection, a random one is generated by default */
+    clientId?: string;
 }
 
 /**
@@ -0,0 +1,10 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/** The type of the payload of a block, used to filter blocks. */
+export type PayloadType =
+    | 'none'
+    | 'taggedData'
+    | 'transaction'
+    | 'milestone'
+    | 'treasuryTransaction';
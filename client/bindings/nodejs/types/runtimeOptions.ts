@@ -0,0 +1,13 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/** Native runtime options. */
+export interface IRuntimeOptions {
+    /** Number of worker threads, defaults to the number of CPUs.*/
+    workerThreads?: number;
+    /**
+     * Prefix of the thread names, worker threads are named `{prefix}-worker-{n}` and blocking threads
+     * `{prefix}-blocking-{n}`, defaults to `iota-client`.
+     */
+    threadNamePrefix?: string;
+}
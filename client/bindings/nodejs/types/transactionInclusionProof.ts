@@ -0,0 +1,22 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import type { IBlock, IMilestonePayload } from '@iota/types';
+
+/** The hash of a sibling subtree on the path from a block to the inclusion merkle root of a milestone */
+export interface IAuditPathHash {
+    /** The hex encoded hash of the sibling subtree */
+    hash: string;
+    /** Whether the sibling subtree is on the left */
+    isLeft: boolean;
+}
+
+/** A proof that a transaction was included in the ledger by a milestone */
+export interface ITransactionInclusionProof {
+    /** The milestone that included the transaction */
+    milestone: IMilestonePayload;
+    /** The block containing the transaction */
+    block: IBlock;
+    /** The sibling hashes from the block up to the inclusion merkle root of the milestone */
+    auditPath: IAuditPathHash[];
+}
@@ -0,0 +1,10 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+import type { IBlock } from '@iota/types';
+import type { PayloadType } from './payloadType';
+
+/** A block with the type of its payload, to tell the payloads apart without inspecting them */
+export interface ITypedBlock extends IBlock {
+    payloadType: PayloadType;
+}
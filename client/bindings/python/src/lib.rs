@@ -52,13 +52,14 @@ pub fn send_message(handle: &ClientMessageHandler, message: String) -> Result<St
     let message = match serde_json::from_str::<Message>(&message) {
         Ok(message) => message,
         Err(e) => {
-            return Ok(serde_json::to_string(&Response::Error(e.into()))
-                .expect("the response is generated manually, so unwrap is safe."));
+            return Ok(handle
+                .client_message_handler
+                .response_to_json(&Response::Error(e.into()))?);
         }
     };
     let response = crate::block_on(async { handle.client_message_handler.send_message(message).await });
 
-    Ok(serde_json::to_string(&response)?)
+    Ok(handle.client_message_handler.response_to_json(&response)?)
 }
 
 /// IOTA Client implemented in Rust for Python binding.
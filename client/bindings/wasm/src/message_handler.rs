@@ -37,11 +37,11 @@ pub fn send_message_async(message: String, messageHandler: &WasmMessageHandler)
         let response: Response = send_message_inner(message_handler.as_ref(), message).await?;
 
         let ser =
-            JsValue::from(serde_json::to_string(&response).map_err(|err| {
+            JsValue::from(message_handler.response_to_json(&response).map_err(|err| {
                 JsValue::from_str(&format!("Client MessageHandler failed to serialize response: {err}"))
             })?);
         match response {
-            Response::Error(_) | Response::Panic(_) => Err(ser),
+            Response::Error(_) | Response::Panic { .. } => Err(ser),
             _ => Ok(ser),
         }
     });
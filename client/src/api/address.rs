@@ -133,6 +133,11 @@ impl<'a> GetAddressesBuilder<'a> {
 
     /// Consume the builder and get a vector of public addresses bech32 encoded
     pub async fn finish(self) -> Result<Vec<String>> {
+        // Nothing to generate, so don't request the bech32 HRP from a node or addresses from a ledger device
+        if self.range.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let bech32_hrp = match self.bech32_hrp.clone() {
             Some(bech32_hrp) => bech32_hrp,
             None => match self.client {
@@ -0,0 +1,118 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_types::block::{
+    output::{Output, Rent, RentStructure},
+    DtoError,
+};
+
+use crate::{Client, Error, Result};
+
+/// The summed amounts of the unspent outputs of an address
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTotal {
+    /// Summed amount of the outputs
+    // Using a String to prevent overflow issues in other languages
+    pub balance: String,
+    /// Number of unspent basic and NFT outputs
+    pub output_count: usize,
+    /// Summed storage deposit the outputs need, which can't be sent without also sending the outputs
+    // Using a String to prevent overflow issues in other languages
+    pub storage_deposit: String,
+    /// The ledger index the outputs were requested at
+    pub ledger_index: u32,
+}
+
+impl Client {
+    /// Returns the summed amount and number of the unspent basic and NFT outputs of an address. All fields are derived
+    /// from the outputs the indexer returned at one ledger index, the outputs are requested to sum them, but they
+    /// aren't returned.
+    pub async fn get_address_total(&self, bech32_address: &str) -> Result<AddressTotal> {
+        let (ledger_index, output_ids) = self.unspent_output_ids(bech32_address).await?;
+
+        let protocol_parameters = self.get_protocol_parameters().await?;
+        let outputs = self
+            .get_outputs(output_ids)
+            .await?
+            .iter()
+            .map(|output_response| Output::try_from_dto(&output_response.output, protocol_parameters.token_supply()))
+            .collect::<std::result::Result<Vec<Output>, DtoError>>()?;
+
+        address_total(&outputs, protocol_parameters.rent_structure(), ledger_index)
+    }
+}
+
+fn address_total(outputs: &[Output], rent_structure: &RentStructure, ledger_index: u32) -> Result<AddressTotal> {
+    let (balance, storage_deposit) = outputs
+        .iter()
+        .try_fold((0u64, 0u64), |(balance, storage_deposit), output| {
+            Some((
+                balance.checked_add(output.amount())?,
+                storage_deposit.checked_add(output.rent_cost(rent_structure))?,
+            ))
+        })
+        .ok_or_else(|| Error::InvalidAmount("the summed amount of the outputs overflows".to_string()))?;
+
+    Ok(AddressTotal {
+        balance: balance.to_string(),
+        output_count: outputs.len(),
+        storage_deposit: storage_deposit.to_string(),
+        ledger_index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::block::{
+        address::{Address, Ed25519Address},
+        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, UnlockCondition},
+        protocol::protocol_parameters,
+    };
+
+    use super::*;
+
+    #[test]
+    fn summed_outputs() {
+        let rent_structure = RentStructure::new(100, 10, 1);
+        let outputs = [1_000_000, 42_600, 2_500_000]
+            .into_iter()
+            .map(|amount| {
+                BasicOutputBuilder::new_with_amount(amount)
+                    .unwrap()
+                    .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+                        Ed25519Address::new([1; 32]),
+                    ))))
+                    .finish_output(protocol_parameters().token_supply())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            address_total(&outputs, &rent_structure, 7).unwrap(),
+            AddressTotal {
+                balance: "3542600".to_string(),
+                output_count: 3,
+                storage_deposit: "127800".to_string(),
+                ledger_index: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn overflowing_outputs() {
+        let rent_structure = RentStructure::new(100, 10, 1);
+        let output = BasicOutputBuilder::new_with_amount(u64::MAX)
+            .unwrap()
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+                Ed25519Address::new([1; 32]),
+            ))))
+            .finish_output(u64::MAX)
+            .unwrap();
+
+        assert!(matches!(
+            address_total(&[output.clone(), output], &rent_structure, 7),
+            Err(Error::InvalidAmount(_))
+        ));
+    }
+}
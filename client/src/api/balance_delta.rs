@@ -0,0 +1,162 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use iota_types::block::{
+    address::Address,
+    output::{Output, OutputId},
+    DtoError,
+};
+
+use crate::{constants::MAX_BALANCE_DELTA_MILESTONES, Client, Result};
+
+/// The net change of the balance of an address since a milestone
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBalanceDelta {
+    /// Amount of the created outputs minus amount of the consumed outputs of the address, negative if more got spent
+    /// than received
+    // Using a String to prevent overflow issues in other languages
+    pub delta: String,
+    /// The confirmed milestone index up to which the changes are included, can be used as the next checkpoint
+    pub milestone_index: u32,
+    /// Whether the changes of later milestones in the requested range weren't included, because at most
+    /// 100 milestones are included at once. They can be requested with `milestone_index` as next checkpoint.
+    pub has_more: bool,
+}
+
+impl Client {
+    /// Returns the net change of the balance of an address from the milestones confirmed after `since_milestone` up
+    /// to `until_milestone` or the latest confirmed milestone. Only outputs with an address unlock condition for the
+    /// address are taken into account. At most 100 milestones are included, the milestones are requested in pages of
+    /// [`milestone_fetch_concurrency`](crate::ClientBuilder::with_milestone_fetch_concurrency) together with the
+    /// outputs they created or consumed. The range must not include pruned milestones.
+    pub async fn get_address_balance_delta(
+        &self,
+        bech32_address: &str,
+        since_milestone: u32,
+        until_milestone: Option<u32>,
+    ) -> Result<AddressBalanceDelta> {
+        let address = Address::try_from_bech32(bech32_address)?.1;
+        let token_supply = self.get_token_supply().await?;
+        let confirmed_index = self.get_info().await?.node_info.status.confirmed_milestone.index;
+        let (start_index, end_index, has_more) = milestone_range(since_milestone, until_milestone, confirmed_index);
+
+        let mut delta = 0;
+        let page_size = self.milestone_fetch_concurrency.max(1) as u32;
+        let mut page_start = start_index;
+        while page_start <= end_index {
+            let page_end = page_start.saturating_add(page_size - 1).min(end_index);
+
+            for utxo_changes in self.get_utxo_changes_by_index_range(page_start..=page_end).await? {
+                for (output_ids, created) in [
+                    (utxo_changes.created_outputs, true),
+                    (utxo_changes.consumed_outputs, false),
+                ] {
+                    let output_ids = output_ids
+                        .iter()
+                        .map(|output_id| OutputId::from_str(output_id))
+                        .collect::<std::result::Result<Vec<OutputId>, _>>()?;
+                    let outputs = self
+                        .get_outputs(output_ids)
+                        .await?
+                        .iter()
+                        .map(|output_response| Output::try_from_dto(&output_response.output, token_supply))
+                        .collect::<std::result::Result<Vec<Output>, DtoError>>()?;
+
+                    delta += balance_change(&address, &outputs, created);
+                }
+            }
+
+            match page_end.checked_add(1) {
+                Some(next_start) => page_start = next_start,
+                None => break,
+            }
+        }
+
+        Ok(AddressBalanceDelta {
+            delta: delta.to_string(),
+            milestone_index: end_index.max(since_milestone),
+            has_more,
+        })
+    }
+}
+
+// Returns the first and last milestone index whose changes are included, and whether later milestones up to the
+// requested end are left out because of the milestone cap.
+fn milestone_range(since_milestone: u32, until_milestone: Option<u32>, confirmed_index: u32) -> (u32, u32, bool) {
+    let requested_end = until_milestone.map_or(confirmed_index, |until| until.min(confirmed_index));
+    let start_index = since_milestone.saturating_add(1);
+    let end_index = requested_end.min(since_milestone.saturating_add(MAX_BALANCE_DELTA_MILESTONES));
+
+    (start_index, end_index, end_index < requested_end)
+}
+
+// Returns the summed amount of the outputs owned by the address, negated for consumed outputs.
+fn balance_change(address: &Address, outputs: &[Output], created: bool) -> i128 {
+    let amount = outputs
+        .iter()
+        .filter(|output| {
+            output
+                .unlock_conditions()
+                .and_then(|unlock_conditions| unlock_conditions.address())
+                .is_some_and(|unlock_condition| unlock_condition.address() == address)
+        })
+        .map(|output| output.amount() as i128)
+        .sum::<i128>();
+
+    if created {
+        amount
+    } else {
+        -amount
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::block::{
+        address::Ed25519Address,
+        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, UnlockCondition},
+        protocol::protocol_parameters,
+    };
+
+    use super::*;
+
+    fn basic_output(address: Address, amount: u64) -> Output {
+        BasicOutputBuilder::new_with_amount(amount)
+            .unwrap()
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(address)))
+            .finish_output(protocol_parameters().token_supply())
+            .unwrap()
+    }
+
+    #[test]
+    fn received_after_checkpoint() {
+        let address = Address::Ed25519(Ed25519Address::new([1; 32]));
+        let other_address = Address::Ed25519(Ed25519Address::new([2; 32]));
+
+        let created = [
+            basic_output(address, 2_000_000),
+            basic_output(other_address, 5_000_000),
+            basic_output(address, 1_000_000),
+        ];
+        let consumed = [basic_output(address, 500_000), basic_output(other_address, 700_000)];
+
+        let delta = balance_change(&address, &created, true) + balance_change(&address, &consumed, false);
+        assert_eq!(delta, 2_500_000);
+
+        assert_eq!(balance_change(&address, &consumed, false), -500_000);
+        assert_eq!(balance_change(&other_address, &created, true), 5_000_000);
+    }
+
+    #[test]
+    fn capped_milestone_range() {
+        assert_eq!(milestone_range(10, None, 50), (11, 50, false));
+        assert_eq!(milestone_range(10, Some(20), 50), (11, 20, false));
+        assert_eq!(milestone_range(10, Some(80), 50), (11, 50, false));
+        assert_eq!(milestone_range(10, None, 500), (11, 110, true));
+        // Nothing confirmed after the checkpoint yet
+        assert_eq!(milestone_range(50, None, 50), (51, 50, false));
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Error, Result};
+
+/// How batch requests like [`Client::get_outputs()`](crate::Client::get_outputs) handle failing items.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BatchErrorMode {
+    /// Abort the batch and return the first error.
+    FailFast,
+    /// Request every item and return the successful items together with the errors of the failed ones.
+    #[default]
+    Collect,
+}
+
+/// The successful items and the errors of the failed items of a batch request, each together with the index of the
+/// item in the request.
+#[derive(Debug)]
+pub struct BatchResponse<T> {
+    /// The successful items.
+    pub items: Vec<(usize, T)>,
+    /// The errors of the failed items.
+    pub errors: Vec<(usize, Error)>,
+}
+
+impl<T> BatchResponse<T> {
+    /// Returns the items if none failed, otherwise [`Error::Batch`] with the errors of the failed items.
+    pub fn into_items(self) -> Result<Vec<T>> {
+        if self.errors.is_empty() {
+            Ok(self.items.into_iter().map(|(_, item)| item).collect())
+        } else {
+            Err(Error::Batch(self.errors))
+        }
+    }
+
+    /// Maps the successful items, keeping their indexes.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> BatchResponse<U> {
+        BatchResponse {
+            items: self.items.into_iter().map(|(index, item)| (index, f(item))).collect(),
+            errors: self.errors,
+        }
+    }
+}
+
+/// A [`BatchResponse`] as returned by the message interface.
+#[derive(Debug, Serialize)]
+pub struct BatchResponseDto<T> {
+    /// The successful items.
+    pub items: Vec<BatchItemDto<T>>,
+    /// The errors of the failed items.
+    pub errors: Vec<BatchErrorDto>,
+}
+
+/// A successful item of a batch request together with its index in the request.
+#[derive(Debug, Serialize)]
+pub struct BatchItemDto<T> {
+    /// The index of the item in the request.
+    pub index: usize,
+    /// The item.
+    pub item: T,
+}
+
+/// The error of a failed item of a batch request together with its index in the request.
+#[derive(Debug, Serialize)]
+pub struct BatchErrorDto {
+    /// The index of the item in the request.
+    pub index: usize,
+    /// The error of the item.
+    pub error: Error,
+}
+
+impl<T> From<BatchResponse<T>> for BatchResponseDto<T> {
+    fn from(response: BatchResponse<T>) -> Self {
+        Self {
+            items: response
+                .items
+                .into_iter()
+                .map(|(index, item)| BatchItemDto { index, item })
+                .collect(),
+            errors: response
+                .errors
+                .into_iter()
+                .map(|(index, error)| BatchErrorDto { index, error })
+                .collect(),
+        }
+    }
+}
+
+/// Collects the results of the items of a batch request according to a [`BatchErrorMode`].
+pub(crate) struct BatchResults<T> {
+    mode: BatchErrorMode,
+    response: BatchResponse<T>,
+}
+
+impl<T> BatchResults<T> {
+    pub(crate) fn new(mode: BatchErrorMode) -> Self {
+        Self {
+            mode,
+            response: BatchResponse {
+                items: Vec::new(),
+                errors: Vec::new(),
+            },
+        }
+    }
+
+    /// Adds the result of the item at `index`, in [`BatchErrorMode::FailFast`] an error is returned right away.
+    pub(crate) fn push(&mut self, index: usize, result: Result<T>) -> Result<()> {
+        match result {
+            Ok(item) => self.response.items.push((index, item)),
+            Err(error) => match self.mode {
+                BatchErrorMode::FailFast => return Err(error),
+                BatchErrorMode::Collect => self.response.errors.push((index, error)),
+            },
+        }
+        Ok(())
+    }
+
+    /// Returns the successful items together with the collected errors.
+    pub(crate) fn finish(self) -> BatchResponse<T> {
+        self.response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_batch(mode: BatchErrorMode) -> Result<BatchResults<u32>> {
+        let results = vec![Ok(0), Err(Error::UnexpectedApiResponse), Ok(2)];
+
+        let mut batch_results = BatchResults::new(mode);
+        for (index, result) in results.into_iter().enumerate() {
+            batch_results.push(index, result)?;
+        }
+        Ok(batch_results)
+    }
+
+    #[test]
+    fn batch_fail_fast() {
+        assert!(matches!(
+            run_batch(BatchErrorMode::FailFast),
+            Err(Error::UnexpectedApiResponse)
+        ));
+    }
+
+    #[test]
+    fn batch_collect() {
+        let response = run_batch(BatchErrorMode::Collect).unwrap().finish();
+
+        assert_eq!(response.items, vec![(0, 0), (2, 2)]);
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].0, 1);
+        assert!(matches!(response.errors[0].1, Error::UnexpectedApiResponse));
+    }
+
+    #[test]
+    fn batch_collect_into_items() {
+        match run_batch(BatchErrorMode::Collect).unwrap().finish().into_items() {
+            Err(Error::Batch(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].0, 1);
+                assert!(matches!(errors[0].1, Error::UnexpectedApiResponse));
+            }
+            other => panic!("unexpected batch result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn batch_response_dto() {
+        let response = BatchResponseDto::from(run_batch(BatchErrorMode::Collect).unwrap().finish());
+        let json = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(
+            json["items"],
+            serde_json::json!([{"index": 0, "item": 0}, {"index": 2, "item": 2}])
+        );
+        assert_eq!(json["errors"][0]["index"], 1);
+        assert_eq!(json["errors"][0]["error"]["type"], "unexpectedApiResponse");
+    }
+
+    #[test]
+    fn batch_default_collects() {
+        assert_eq!(BatchErrorMode::default(), BatchErrorMode::Collect);
+    }
+}
@@ -57,6 +57,11 @@ impl<'a> ClientBlockBuilder<'a> {
                 .items,
         );
 
+        // Skip outputs selected by other in-flight transactions
+        if let Some(output_reservations) = &self.client.output_reservations {
+            output_ids.retain(|output_id| !output_reservations.is_reserved(output_id));
+        }
+
         self.client.get_outputs(output_ids).await
     }
 
@@ -110,6 +115,7 @@ impl<'a> ClientBlockBuilder<'a> {
             protocol_parameters.clone(),
         )
         .required_inputs(required_inputs_for_sender_or_issuer_ids.clone())
+        .strategy(self.input_selection_strategy)
         .timestamp(current_time);
 
         if let Some(address) = self.custom_remainder_address {
@@ -123,8 +129,9 @@ impl<'a> ClientBlockBuilder<'a> {
         log::debug!("[get_inputs from addresses]");
 
         // Then select inputs with outputs from addresses.
+        let batch_size = self.client.get_address_scan_batch_size(ADDRESS_GAP_RANGE);
         let selected_transaction_data = 'input_selection: loop {
-            // Get the addresses in the BIP path/index ~ path/index+20.
+            // Get the addresses in the BIP path/index ~ path/index+batch_size.
             let addresses = self
                 .client
                 .get_addresses(
@@ -132,7 +139,7 @@ impl<'a> ClientBlockBuilder<'a> {
                         .ok_or(crate::Error::MissingParameter("secret manager"))?,
                 )
                 .with_account_index(account_index)
-                .with_range(gap_index..gap_index + ADDRESS_GAP_RANGE)
+                .with_range(gap_index..gap_index + batch_size)
                 .get_all()
                 .await?;
 
@@ -169,7 +176,7 @@ impl<'a> ClientBlockBuilder<'a> {
                 // looking up the addresses belonging to the seed. Note that we don't
                 // really count the exact 20 consecutive empty addresses, which is
                 // unnecessary. We just need to check the address range,
-                // (index * ADDRESS_GAP_RANGE, index * ADDRESS_GAP_RANGE + ADDRESS_GAP_RANGE), where index is
+                // (index * batch_size, index * batch_size + batch_size), where index is
                 // natural number, and to see if the outputs are all empty.
                 if address_outputs.is_empty() {
                     // Accumulate the empty_address_count for each run of output address searching
@@ -214,6 +221,7 @@ impl<'a> ClientBlockBuilder<'a> {
                         protocol_parameters.clone(),
                     )
                     .required_inputs(required_inputs_for_sender_or_issuer_ids.clone())
+                    .strategy(self.input_selection_strategy)
                     .timestamp(current_time);
 
                     if let Some(address) = self.custom_remainder_address {
@@ -257,7 +265,7 @@ impl<'a> ClientBlockBuilder<'a> {
                 }
             }
 
-            gap_index += ADDRESS_GAP_RANGE;
+            gap_index += batch_size;
 
             // The gap limit is 20 and use reference 40 here because there's public and internal addresses
             if empty_address_count >= (ADDRESS_GAP_RANGE * 2) as u64 {
@@ -5,6 +5,7 @@ pub(crate) mod burn;
 pub(crate) mod error;
 pub(crate) mod remainder;
 pub(crate) mod requirement;
+pub(crate) mod strategy;
 pub(crate) mod transition;
 
 use std::collections::{HashMap, HashSet};
@@ -16,6 +17,7 @@ pub use self::{
     burn::{Burn, BurnDto},
     error::Error,
     requirement::Requirement,
+    strategy::InputSelectionStrategy,
 };
 use crate::{
     api::types::RemainderData,
@@ -41,6 +43,7 @@ pub struct InputSelection {
     addresses: HashSet<Address>,
     burn: Option<Burn>,
     remainder_address: Option<Address>,
+    strategy: InputSelectionStrategy,
     protocol_parameters: ProtocolParameters,
     timestamp: u32,
     requirements: Vec<Requirement>,
@@ -186,6 +189,7 @@ impl InputSelection {
             addresses,
             burn: None,
             remainder_address: None,
+            strategy: InputSelectionStrategy::default(),
             protocol_parameters,
             timestamp: unix_timestamp_now(),
             requirements: Vec::new(),
@@ -217,6 +221,12 @@ impl InputSelection {
         self
     }
 
+    /// Sets the strategy used to pick inputs for the amount requirement of an [`InputSelection`].
+    pub fn strategy(mut self, strategy: InputSelectionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
     /// Sets the timestamp of an [`InputSelection`].
     pub fn timestamp(mut self, timestamp: u32) -> Self {
         self.timestamp = timestamp;
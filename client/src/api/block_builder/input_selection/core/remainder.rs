@@ -86,6 +86,9 @@ impl InputSelection {
         let (inputs_sum, outputs_sum, inputs_sdr, outputs_sdr) =
             amount_sums(&self.selected_inputs, &self.outputs, self.timestamp);
         let mut storage_deposit_returns = Vec::new();
+        // Sorted by address, so the order of the outputs and therefore the essence are deterministic
+        let mut inputs_sdr = inputs_sdr.into_iter().collect::<Vec<_>>();
+        inputs_sdr.sort_unstable_by_key(|(address, _)| *address);
 
         for (address, amount) in inputs_sdr {
             let output_sdr_amount = *outputs_sdr.get(&address).unwrap_or(&0);
@@ -159,7 +159,10 @@ impl AmountSelection {
     }
 
     fn into_newly_selected_inputs(self) -> Vec<(InputSigningData, Option<AliasTransition>)> {
-        self.newly_selected_inputs.into_values().collect()
+        let mut inputs = self.newly_selected_inputs.into_values().collect::<Vec<_>>();
+        // Sorted by output id, so the selection order and therefore the remainder address are deterministic
+        inputs.sort_unstable_by_key(|(input, _)| *input.output_id());
+        inputs
     }
 }
 
@@ -294,10 +297,8 @@ impl InputSelection {
             );
         }
 
-        // TODO if consolidate strategy: sum all the lowest amount until diff is covered.
-        // TODO this would be lowest amount of input strategy.
-        self.available_inputs
-            .sort_by(|left, right| left.output.amount().cmp(&right.output.amount()));
+        self.strategy
+            .sort(&mut self.available_inputs, amount_selection.missing_amount());
 
         'fulfil: {
             let basic_ed25519_inputs = self.available_inputs.iter().filter(|input| {
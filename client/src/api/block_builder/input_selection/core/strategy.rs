@@ -0,0 +1,37 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::secret::types::InputSigningData;
+
+/// The order in which available inputs are picked to fulfill the amount requirement.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InputSelectionStrategy {
+    /// Picks the inputs with the smallest amounts first, which consolidates dust outputs over time.
+    #[default]
+    SmallestFirst,
+    /// Picks the inputs with the largest amounts first.
+    LargestFirst,
+    /// Picks the smallest input that covers the missing amount on its own if there is one, otherwise the inputs with
+    /// the largest amounts first, so that as few inputs as possible are used.
+    MinimizeInputs,
+}
+
+impl InputSelectionStrategy {
+    /// Sorts the inputs in the order they should be picked to cover `missing_amount`.
+    pub(crate) fn sort(&self, inputs: &mut [InputSigningData], missing_amount: u64) {
+        match self {
+            Self::SmallestFirst => inputs.sort_by_key(|input| input.output.amount()),
+            Self::LargestFirst => inputs.sort_by_key(|input| std::cmp::Reverse(input.output.amount())),
+            Self::MinimizeInputs => {
+                inputs.sort_by_key(|input| std::cmp::Reverse(input.output.amount()));
+                // The inputs are sorted descending, so the last one covering the amount is the smallest of them.
+                if let Some(index) = inputs.iter().rposition(|input| input.output.amount() >= missing_amount) {
+                    inputs[..=index].rotate_right(1);
+                }
+            }
+        }
+    }
+}
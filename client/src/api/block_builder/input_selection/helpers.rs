@@ -55,3 +55,33 @@ pub fn minimum_storage_deposit_basic_output(
 
     Ok(basic_output.rent_cost(config))
 }
+
+/// Computes the total storage deposit of `output_count` basic outputs with only an [AddressUnlockCondition] and no
+/// [NativeTokens], which is `output_count` times the [minimum_storage_deposit_basic_output()] of such an output.
+pub fn required_storage_deposit_basic_outputs(
+    config: &RentStructure,
+    output_count: u32,
+    token_supply: u64,
+) -> Result<u64> {
+    Ok(minimum_storage_deposit_basic_output(config, &None, token_supply)? * u64::from(output_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::block::protocol::protocol_parameters;
+
+    use super::*;
+
+    #[test]
+    fn required_storage_deposit_for_output_counts() {
+        let rent_structure = RentStructure::new(100, 10, 1);
+        let token_supply = protocol_parameters().token_supply();
+
+        for (output_count, amount) in [(0, 0), (1, 42_600), (2, 85_200), (100, 4_260_000)] {
+            assert_eq!(
+                required_storage_deposit_basic_outputs(&rent_structure, output_count, token_supply).unwrap(),
+                amount
+            );
+        }
+    }
+}
@@ -21,12 +21,13 @@ use crate::{
     },
     constants::HD_WALLET_TYPE,
     secret::types::InputSigningData,
-    Result,
+    Error, Result,
 };
 
 impl<'a> ClientBlockBuilder<'a> {
     /// If custom inputs are provided we check if they are unspent, get the balance and search the Ed25519 addresses for
-    /// them with the provided input_range so we can later sign them.
+    /// them with the provided input_range so we can later sign them. Returns [`Error::InputAlreadySpent`] for the first
+    /// custom input that is already spent, before anything is signed or posted.
     /// Forwards to [try_select_inputs()] with all inputs in `mandatory_inputs`, so they will all be included in the
     /// transaction, even if not required for the provided outputs.
     pub(crate) async fn get_custom_inputs(
@@ -45,51 +46,53 @@ impl<'a> ClientBlockBuilder<'a> {
                 let output_response = self.client.get_output(input.output_id()).await?;
                 let output = Output::try_from_dto(&output_response.output, token_supply)?;
 
-                if !output_response.metadata.is_spent {
-                    let alias_transition = is_alias_transition_internal(&output, *input.output_id(), &self.outputs);
-                    let (unlock_address, _) = output.required_and_unlocked_address(
-                        current_time,
-                        input.output_id(),
-                        alias_transition.map(|g| g.0),
-                    )?;
-
-                    let bech32_hrp = self.client.get_bech32_hrp().await?;
-                    let address_index_internal = match self.secret_manager {
-                        Some(secret_manager) => {
-                            match unlock_address {
-                                Address::Ed25519(_) => Some(
-                                    search_address(
-                                        secret_manager,
-                                        &bech32_hrp,
-                                        self.coin_type,
-                                        self.account_index,
-                                        self.input_range.clone(),
-                                        &unlock_address,
-                                    )
-                                    .await?,
-                                ),
-                                // Alias and NFT addresses can't be generated from a private key.
-                                _ => None,
-                            }
-                        }
-                        // Assuming default for offline signing.
-                        None => Some((0, false)),
-                    };
-
-                    inputs_data.push(InputSigningData {
-                        output,
-                        output_metadata: OutputMetadata::try_from(&output_response.metadata)?,
-                        chain: address_index_internal.map(|(address_index, internal)| {
-                            Chain::from_u32_hardened(vec![
-                                HD_WALLET_TYPE,
-                                self.coin_type,
-                                self.account_index,
-                                internal as u32,
-                                address_index,
-                            ])
-                        }),
-                    });
+                if output_response.metadata.is_spent {
+                    return Err(Error::InputAlreadySpent(input.output_id().to_string()));
                 }
+
+                let alias_transition = is_alias_transition_internal(&output, *input.output_id(), &self.outputs);
+                let (unlock_address, _) = output.required_and_unlocked_address(
+                    current_time,
+                    input.output_id(),
+                    alias_transition.map(|g| g.0),
+                )?;
+
+                let bech32_hrp = self.client.get_bech32_hrp().await?;
+                let address_index_internal = match self.secret_manager {
+                    Some(secret_manager) => {
+                        match unlock_address {
+                            Address::Ed25519(_) => Some(
+                                search_address(
+                                    secret_manager,
+                                    &bech32_hrp,
+                                    self.coin_type,
+                                    self.account_index,
+                                    self.input_range.clone(),
+                                    &unlock_address,
+                                )
+                                .await?,
+                            ),
+                            // Alias and NFT addresses can't be generated from a private key.
+                            _ => None,
+                        }
+                    }
+                    // Assuming default for offline signing.
+                    None => Some((0, false)),
+                };
+
+                inputs_data.push(InputSigningData {
+                    output,
+                    output_metadata: OutputMetadata::try_from(&output_response.metadata)?,
+                    chain: address_index_internal.map(|(address_index, internal)| {
+                        Chain::from_u32_hardened(vec![
+                            HD_WALLET_TYPE,
+                            self.coin_type,
+                            self.account_index,
+                            internal as u32,
+                            address_index,
+                        ])
+                    }),
+                });
             }
         }
 
@@ -12,6 +12,6 @@ mod utxo_chains;
 
 pub(crate) use self::core::is_alias_transition;
 pub use self::{
-    core::{Burn, BurnDto, Error, InputSelection, Requirement, Selected},
-    helpers::minimum_storage_deposit_basic_output,
+    core::{Burn, BurnDto, Error, InputSelection, InputSelectionStrategy, Requirement, Selected},
+    helpers::{minimum_storage_deposit_basic_output, required_storage_deposit_basic_outputs},
 };
@@ -13,18 +13,21 @@ use iota_types::block::{
     output::{
         dto::OutputDto,
         unlock_condition::{AddressUnlockCondition, UnlockCondition},
-        BasicOutputBuilder, Output, OUTPUT_COUNT_RANGE,
+        BasicOutput, BasicOutputBuilder, Output, OUTPUT_COUNT_RANGE,
     },
     parent::Parents,
     payload::{Payload, TaggedDataPayload},
+    protocol::ProtocolParameters,
     Block, BlockId,
 };
 use packable::bounded::TryIntoBoundedU16Error;
 
-pub use self::transaction::verify_semantic;
+pub use self::{pow::PowVerification, transaction::verify_semantic};
 use crate::{
-    api::block_builder::input_selection::Burn, constants::SHIMMER_COIN_TYPE, secret::SecretManager, Client, Error,
-    Result,
+    api::block_builder::input_selection::{Burn, InputSelectionStrategy},
+    constants::SHIMMER_COIN_TYPE,
+    secret::SecretManager,
+    Client, Error, Result,
 };
 
 /// Builder of the block API
@@ -37,12 +40,14 @@ pub struct ClientBlockBuilder<'a> {
     initial_address_index: u32,
     inputs: Option<Vec<UtxoInput>>,
     input_range: Range<u32>,
+    input_selection_strategy: InputSelectionStrategy,
     outputs: Vec<Output>,
     custom_remainder_address: Option<Address>,
     tag: Option<Vec<u8>>,
     data: Option<Vec<u8>>,
     parents: Option<Parents>,
     burn: Option<Burn>,
+    address_reuse_check: bool,
 }
 
 /// Block output address
@@ -70,6 +75,8 @@ pub struct ClientBlockBuilderOptions {
     pub inputs: Option<Vec<UtxoInputDto>>,
     /// Input range
     pub input_range: Option<Range<u32>>,
+    /// Order in which inputs are picked to cover the output amounts
+    pub input_selection_strategy: Option<InputSelectionStrategy>,
     /// Bech32 encoded output address and amount
     pub output: Option<ClientBlockBuilderOutputAddress>,
     /// Hex encoded output address and amount
@@ -78,14 +85,38 @@ pub struct ClientBlockBuilderOptions {
     pub outputs: Option<Vec<OutputDto>>,
     /// Custom remainder address
     pub custom_remainder_address: Option<String>,
-    /// Hex encoded tag
+    /// Hex encoded tag, a byte array is also accepted
+    #[serde(default, deserialize_with = "deserialize_hex_or_bytes")]
     pub tag: Option<String>,
-    /// Hex encoded data
+    /// Hex encoded data, a byte array is also accepted
+    #[serde(default, deserialize_with = "deserialize_hex_or_bytes")]
     pub data: Option<String>,
-    /// Parents
+    /// 1-8 hex encoded parent block IDs, tips are requested from the node if not set
     pub parents: Option<Vec<BlockId>>,
     /// Explicit burning of aliases, nfts, foundries and native tokens
     pub burn: Option<Burn>,
+    /// Check if the output and remainder addresses already owned outputs
+    pub address_reuse_check: Option<bool>,
+}
+
+// Deserializes a hex string or a byte array to a hex string.
+fn deserialize_hex_or_bytes<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum HexOrBytes {
+        Hex(String),
+        Bytes(Vec<u8>),
+    }
+
+    let value: Option<HexOrBytes> = serde::Deserialize::deserialize(deserializer)?;
+
+    Ok(value.map(|value| match value {
+        HexOrBytes::Hex(hex) => hex,
+        HexOrBytes::Bytes(bytes) => prefix_hex::encode(bytes),
+    }))
 }
 
 impl<'a> ClientBlockBuilder<'a> {
@@ -99,12 +130,14 @@ impl<'a> ClientBlockBuilder<'a> {
             initial_address_index: 0,
             inputs: None,
             input_range: 0..100,
+            input_selection_strategy: InputSelectionStrategy::default(),
             outputs: Vec::new(),
             custom_remainder_address: None,
             tag: None,
             data: None,
             parents: None,
             burn: None,
+            address_reuse_check: false,
         }
     }
 
@@ -160,7 +193,15 @@ impl<'a> ClientBlockBuilder<'a> {
         self
     }
 
-    /// Set a transfer to the builder
+    /// Set the order in which inputs are picked to cover the output amounts. Inputs set with
+    /// [`ClientBlockBuilder::with_input()`] are always spent. Default: [`InputSelectionStrategy::SmallestFirst`]
+    pub fn with_input_selection_strategy(mut self, strategy: InputSelectionStrategy) -> Self {
+        self.input_selection_strategy = strategy;
+        self
+    }
+
+    /// Set a transfer to the builder. The output is always a basic output with only an address unlock condition, other
+    /// output kinds or unlock conditions need to be built and provided with [`ClientBlockBuilder::with_outputs()`].
     pub async fn with_output(mut self, address: &str, amount: u64) -> Result<ClientBlockBuilder<'a>> {
         let output = BasicOutputBuilder::new_with_amount(amount)?
             .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
@@ -176,6 +217,30 @@ impl<'a> ClientBlockBuilder<'a> {
         Ok(self)
     }
 
+    /// Set a transfer with additional unlock conditions, like a timelock or an expiration, to the builder. The output
+    /// is a basic output with an address unlock condition for `address` and the additional unlock conditions, which
+    /// have to be supported on basic outputs by the protocol version of the node, otherwise
+    /// [`Error::UnsupportedUnlockCondition`] is returned.
+    pub async fn with_output_unlock_conditions(
+        self,
+        address: &str,
+        amount: u64,
+        unlock_conditions: Vec<UnlockCondition>,
+    ) -> Result<ClientBlockBuilder<'a>> {
+        let protocol_parameters = self.client.get_protocol_parameters().await?;
+        for unlock_condition in &unlock_conditions {
+            verify_unlock_condition_support(unlock_condition, protocol_parameters.protocol_version())?;
+        }
+
+        let output = BasicOutputBuilder::new_with_amount(amount)?
+            .with_unlock_conditions(unlock_conditions)
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
+                Address::try_from_bech32(address)?.1,
+            )))
+            .finish_output(protocol_parameters.token_supply())?;
+        self.with_outputs(vec![output])
+    }
+
     /// Set outputs to the builder
     pub fn with_outputs(mut self, outputs: Vec<Output>) -> Result<Self> {
         self.outputs.extend(outputs);
@@ -187,7 +252,8 @@ impl<'a> ClientBlockBuilder<'a> {
         Ok(self)
     }
 
-    /// Set a transfer to the builder, address needs to be hex encoded
+    /// Set a transfer to the builder, address needs to be hex encoded. Like [`ClientBlockBuilder::with_output()`], the
+    /// output is always a basic output with only an address unlock condition.
     pub async fn with_output_hex(mut self, address: &str, amount: u64) -> Result<ClientBlockBuilder<'a>> {
         let output = BasicOutputBuilder::new_with_amount(amount)?
             .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
@@ -222,12 +288,22 @@ impl<'a> ClientBlockBuilder<'a> {
         self
     }
 
-    /// Set 1-8 custom parent block ids
+    /// Set 1-8 custom parent block ids, which are used instead of tips from the node, also for local PoW.
+    /// Duplicates are removed, [`iota_types::block::Error::InvalidParentCount`] is returned if none or more than 8
+    /// remain.
     pub fn with_parents(mut self, parent_ids: Vec<BlockId>) -> Result<Self> {
         self.parents.replace(Parents::new(parent_ids)?);
         Ok(self)
     }
 
+    /// Check if the addresses of the outputs or the remainder own or owned outputs other than the selected inputs and
+    /// set [`PreparedTransactionData::reuse_warning`](crate::api::PreparedTransactionData::reuse_warning) accordingly.
+    /// Disabled by default, because it requires additional requests to the node.
+    pub fn with_address_reuse_check(mut self, address_reuse_check: bool) -> Self {
+        self.address_reuse_check = address_reuse_check;
+        self
+    }
+
     /// Set multiple options from client block builder options type
     /// Useful for bindings
     pub async fn set_options(mut self, options: ClientBlockBuilderOptions) -> Result<ClientBlockBuilder<'a>> {
@@ -253,6 +329,10 @@ impl<'a> ClientBlockBuilder<'a> {
             self = self.with_input_range(input_range);
         }
 
+        if let Some(input_selection_strategy) = options.input_selection_strategy {
+            self = self.with_input_selection_strategy(input_selection_strategy);
+        }
+
         if let Some(output) = options.output {
             self = self
                 .with_output(
@@ -307,15 +387,27 @@ impl<'a> ClientBlockBuilder<'a> {
             self = self.with_burn(burn);
         }
 
+        if let Some(address_reuse_check) = options.address_reuse_check {
+            self = self.with_address_reuse_check(address_reuse_check);
+        }
+
         Ok(self)
     }
 
+    // A tagged data payload, also the one of a transaction, requires a tag if data is set
+    fn verify_tag(&self) -> Result<()> {
+        match (&self.data, &self.tag) {
+            (Some(_), None) => Err(Error::MissingParameter("tag")),
+            (Some(_), Some(tag)) if tag.is_empty() => Err(Error::TaggedData(
+                "the tag is empty, but data is set; a tag is required to attach data".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
     /// Consume the builder and get the API result
     pub async fn finish(self) -> Result<Block> {
-        // tagged_data payload requires an tagged_data tag
-        if self.data.is_some() && self.tag.is_none() {
-            return Err(Error::MissingParameter("tag"));
-        }
+        self.verify_tag()?;
         if self.inputs.is_some() && self.outputs.is_empty() {
             return Err(Error::MissingParameter("output"));
         }
@@ -323,10 +415,28 @@ impl<'a> ClientBlockBuilder<'a> {
             if self.secret_manager.is_none() && self.inputs.is_none() {
                 return Err(Error::MissingParameter("seed"));
             }
-            // Send block with transaction
-            let prepared_transaction_data = self.prepare_transaction().await?;
+            // Send block with transaction, the inputs stay reserved until the block is confirmed or sending failed
+            let output_reservations = self.client.output_reservations.clone();
+            let (prepared_transaction_data, reservation) = match &output_reservations {
+                Some(output_reservations) => {
+                    let _selection = output_reservations.lock_selection().await;
+                    let prepared_transaction_data = self.prepare_transaction().await?;
+                    let reservation = output_reservations.reserve(
+                        prepared_transaction_data
+                            .inputs_data
+                            .iter()
+                            .map(|input| *input.output_id()),
+                    );
+                    (prepared_transaction_data, Some(reservation))
+                }
+                None => (self.prepare_transaction().await?, None),
+            };
             let tx_payload = self.sign_transaction(prepared_transaction_data).await?;
-            self.finish_block(Some(tx_payload)).await
+            let block = self.finish_block(Some(tx_payload)).await?;
+            if let (Some(output_reservations), Some(reservation)) = (output_reservations, reservation) {
+                output_reservations.keep_until_confirmed(block.id(), reservation);
+            }
+            Ok(block)
         } else if self.tag.is_some() {
             // Send block with tagged_data payload
             self.finish_tagged_data().await
@@ -380,3 +490,67 @@ impl<'a> ClientBlockBuilder<'a> {
         }
     }
 }
+
+// The unlock conditions allowed on basic outputs are only known for the protocol version implemented by iota-types, so
+// other protocol versions are rejected.
+fn verify_unlock_condition_support(unlock_condition: &UnlockCondition, protocol_version: u8) -> Result<()> {
+    if protocol_version == ProtocolParameters::default().protocol_version()
+        && BasicOutput::ALLOWED_UNLOCK_CONDITIONS.contains(unlock_condition.flag())
+    {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedUnlockCondition {
+            kind: unlock_condition.kind(),
+            protocol_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::block::{
+        address::AliasAddress,
+        output::{
+            unlock_condition::{GovernorAddressUnlockCondition, TimelockUnlockCondition},
+            AliasId,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn unlock_condition_support() {
+        let timelock = UnlockCondition::Timelock(TimelockUnlockCondition::new(1_700_000_000).unwrap());
+        assert!(verify_unlock_condition_support(&timelock, 2).is_ok());
+
+        // The allowed unlock conditions of other protocol versions aren't known
+        assert!(matches!(
+            verify_unlock_condition_support(&timelock, 1),
+            Err(Error::UnsupportedUnlockCondition {
+                kind: TimelockUnlockCondition::KIND,
+                protocol_version: 1
+            })
+        ));
+
+        // Governor addresses are never allowed on basic outputs
+        let governor = UnlockCondition::GovernorAddress(GovernorAddressUnlockCondition::new(Address::Alias(
+            AliasAddress::new(AliasId::new([0; 32])),
+        )));
+        assert!(matches!(
+            verify_unlock_condition_support(&governor, 2),
+            Err(Error::UnsupportedUnlockCondition {
+                kind: GovernorAddressUnlockCondition::KIND,
+                protocol_version: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn tag_and_data_from_hex_or_bytes() {
+        let options: ClientBlockBuilderOptions =
+            serde_json::from_str(r#"{"tag":"0x6d792d617070","data":[1,2,3]}"#).unwrap();
+
+        assert_eq!(options.tag.as_deref(), Some("0x6d792d617070"));
+        assert_eq!(options.data.as_deref(), Some("0x010203"));
+    }
+}
@@ -3,15 +3,56 @@
 
 //! PoW functions.
 
+use std::time::Duration;
+
+use futures::Future;
+use instant::Instant;
 #[cfg(not(target_family = "wasm"))]
 use iota_pow::miner::{Miner, MinerBuilder, MinerCancel};
+use iota_pow::score::PowScorer;
 #[cfg(target_family = "wasm")]
 use iota_pow::wasm_miner::{SingleThreadedMiner, SingleThreadedMinerBuilder};
-use iota_types::block::{parent::Parents, payload::Payload, Block, BlockBuilder, Error as BlockError};
+use iota_types::block::{parent::Parents, payload::Payload, Block, BlockBuilder, BlockId, Error as BlockError};
 
 use crate::{Client, Error, Result};
 
+/// The Proof of Work score of a block, compared against a minimum score.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowVerification {
+    /// The Proof of Work score of the block.
+    pub score: f64,
+    /// The minimum score the block got compared against.
+    pub min_pow_score: u32,
+    /// Whether the score meets the minimum score.
+    pub valid: bool,
+}
+
+impl PowVerification {
+    /// Computes the Proof of Work score of the packed block and compares it against the minimum score.
+    pub fn new(block_bytes: &[u8], min_pow_score: u32) -> Self {
+        let score = PowScorer::new().score(block_bytes);
+
+        Self {
+            score,
+            min_pow_score,
+            valid: score >= min_pow_score as f64,
+        }
+    }
+}
+
 impl Client {
+    /// Verifies that the Proof of Work of a packed block meets the minimum score, defaulting to the min PoW score of
+    /// the network, so blocks that were mined externally can be checked before posting them.
+    pub async fn verify_pow(&self, block_bytes: &[u8], min_pow_score: Option<u32>) -> Result<PowVerification> {
+        let min_pow_score = match min_pow_score {
+            Some(min_pow_score) => min_pow_score,
+            None => self.get_min_pow_score().await?,
+        };
+
+        Ok(PowVerification::new(block_bytes, min_pow_score))
+    }
+
     /// Finishes the block with local PoW if needed.
     /// Without local PoW, it will finish the block with a 0 nonce.
     pub async fn finish_block_builder(&self, parents: Option<Parents>, payload: Option<Payload>) -> Result<Block> {
@@ -21,7 +62,7 @@ impl Client {
             // Finish block without doing PoW.
             let parents = match parents {
                 Some(parents) => parents,
-                None => Parents::new(self.get_tips().await?)?,
+                None => Parents::new(self.get_tips_with_retries().await?)?,
             };
             let mut block_builder = BlockBuilder::new(parents);
 
@@ -33,6 +74,15 @@ impl Client {
         }
     }
 
+    /// Requests tips for a new block, failed requests are retried as configured with
+    /// [`ClientBuilder::with_tip_selection_retries()`](crate::ClientBuilder::with_tip_selection_retries).
+    pub(crate) async fn get_tips_with_retries(&self) -> Result<Vec<BlockId>> {
+        retry_with_backoff(self.tip_selection_retries, self.tip_selection_retry_delay, || {
+            self.get_tips()
+        })
+        .await
+    }
+
     /// Calls the appropriate PoW function depending whether the compilation is for wasm or not.
     pub async fn finish_pow(&self, parents: Option<Parents>, payload: Option<Payload>) -> Result<Block> {
         #[cfg(not(target_family = "wasm"))]
@@ -50,17 +100,19 @@ impl Client {
     async fn finish_multi_threaded_pow(&self, parents: Option<Parents>, payload: Option<Payload>) -> Result<Block> {
         let pow_worker_count = self.pow_worker_count;
         let min_pow_score = self.get_min_pow_score().await?;
-        let tips_interval = self.get_tips_interval();
+        let tips_interval = Duration::from_secs(self.get_tips_interval());
+        let start_time = Instant::now();
 
         loop {
+            let timeout = pow_round_timeout(tips_interval, self.pow_deadline, start_time.elapsed())?;
             let cancel = MinerCancel::new();
             let cancel_2 = cancel.clone();
             let payload_ = payload.clone();
             let parents = match &parents {
                 Some(parents) => parents.clone(),
-                None => Parents::new(self.get_tips().await?)?,
+                None => Parents::new(self.get_tips_with_retries().await?)?,
             };
-            let time_thread = std::thread::spawn(move || Ok(pow_timeout(tips_interval, cancel)));
+            let time_thread = std::thread::spawn(move || Ok(pow_timeout(timeout, cancel)));
             let pow_thread = std::thread::spawn(move || {
                 let mut client_miner = MinerBuilder::new().with_cancel(cancel_2);
                 if let Some(worker_count) = pow_worker_count {
@@ -94,16 +146,18 @@ impl Client {
     #[cfg(target_family = "wasm")]
     async fn finish_single_threaded_pow(&self, parents: Option<Parents>, payload: Option<Payload>) -> Result<Block> {
         let min_pow_score: u32 = self.get_min_pow_score().await?;
-        let tips_interval: u64 = self.get_tips_interval();
+        let tips_interval = Duration::from_secs(self.get_tips_interval());
+        let start_time = Instant::now();
 
         loop {
+            let timeout = pow_round_timeout(tips_interval, self.pow_deadline, start_time.elapsed())?;
             let parents = match &parents {
                 Some(parents) => parents.clone(),
-                None => Parents::new(self.get_tips().await?)?,
+                None => Parents::new(self.get_tips_with_retries().await?)?,
             };
 
             let single_threaded_miner = SingleThreadedMinerBuilder::new()
-                .with_timeout_in_seconds(tips_interval)
+                .with_timeout_in_seconds(timeout.as_secs().max(1))
                 .finish();
 
             match do_pow(single_threaded_miner, min_pow_score, payload.clone(), parents) {
@@ -138,10 +192,188 @@ fn do_pow(
 
 // PoW timeout, if we reach this we will restart the PoW with new tips, so the final block will never be lazy.
 #[cfg(not(target_family = "wasm"))]
-fn pow_timeout(after_seconds: u64, cancel: MinerCancel) -> Option<Block> {
-    std::thread::sleep(std::time::Duration::from_secs(after_seconds));
+fn pow_timeout(after: Duration, cancel: MinerCancel) -> Option<Block> {
+    std::thread::sleep(after);
 
     cancel.trigger();
 
     None
 }
+
+// Returns how long the next PoW round may take, which is the tips interval unless the remaining time until the PoW
+// deadline is shorter. Errors if the deadline already passed.
+fn pow_round_timeout(tips_interval: Duration, pow_deadline: Option<Duration>, elapsed: Duration) -> Result<Duration> {
+    match pow_deadline {
+        Some(pow_deadline) if elapsed >= pow_deadline => Err(Error::PowDeadlineExceeded(pow_deadline)),
+        Some(pow_deadline) => Ok(tips_interval.min(pow_deadline - elapsed)),
+        None => Ok(tips_interval),
+    }
+}
+
+// Calls `f`, which requests tips, and retries it up to `retries` times if it fails with a transient error, waiting
+// `delay` before the first retry and doubling it for every further retry. If all retries fail,
+// `Error::TipsUnavailable` with the last error is returned.
+#[allow(clippy::future_not_send)]
+async fn retry_with_backoff<T, F, Fut>(retries: u32, mut delay: Duration, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Ok(res) => return Ok(res),
+            Err(e) if retries == 0 || !e.is_transient() => return Err(e),
+            Err(e) if attempt == retries => {
+                return Err(Error::TipsUnavailable {
+                    attempts: attempt + 1,
+                    error: Box::new(e),
+                });
+            }
+            Err(e) => log::debug!("[retry_with_backoff] attempt {attempt} failed: {e}"),
+        }
+
+        #[cfg(target_family = "wasm")]
+        gloo_timers::future::TimeoutFuture::new(delay.as_millis().try_into().unwrap()).await;
+
+        #[cfg(not(target_family = "wasm"))]
+        tokio::time::sleep(delay).await;
+
+        attempt += 1;
+        delay *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use iota_types::block::protocol::ProtocolParameters;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn tips_retry_after_failure() {
+        let calls = &Cell::new(0);
+        let tips = retry_with_backoff(2, Duration::from_millis(1), move || async move {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 {
+                Err(tips_unavailable())
+            } else {
+                Ok(vec![BlockId::null()])
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(tips, vec![BlockId::null()]);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn tips_retries_exhausted() {
+        let calls = &Cell::new(0);
+        let result: Result<Vec<BlockId>> = retry_with_backoff(2, Duration::from_millis(1), move || async move {
+            calls.set(calls.get() + 1);
+            Err(tips_unavailable())
+        })
+        .await;
+
+        assert!(matches!(
+            &result,
+            Err(Error::TipsUnavailable { attempts: 3, error }) if matches!(**error, Error::ResponseError { code: 503, .. })
+        ));
+        assert_eq!(result.unwrap_err().code(), Some("nodeUnavailable"));
+        assert_eq!(calls.get(), 3);
+
+        // Errors that won't go away by retrying are returned right away
+        calls.set(0);
+        let result: Result<Vec<BlockId>> = retry_with_backoff(2, Duration::from_millis(1), move || async move {
+            calls.set(calls.get() + 1);
+            Err(Error::ResponseError {
+                code: 403,
+                text: "forbidden".to_string(),
+                url: "http://localhost:14265/api/core/v2/tips".to_string(),
+            })
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::ResponseError { code: 403, .. })));
+        assert_eq!(calls.get(), 1);
+    }
+
+    fn tips_unavailable() -> Error {
+        Error::ResponseError {
+            code: 503,
+            text: "node is not synced".to_string(),
+            url: "http://localhost:14265/api/core/v2/tips".to_string(),
+        }
+    }
+
+    #[test]
+    fn pow_round_timeouts() {
+        let tips_interval = Duration::from_secs(5);
+
+        assert_eq!(
+            pow_round_timeout(tips_interval, None, Duration::from_secs(60)).unwrap(),
+            tips_interval
+        );
+        assert_eq!(
+            pow_round_timeout(tips_interval, Some(Duration::from_secs(12)), Duration::from_secs(5)).unwrap(),
+            tips_interval
+        );
+        // The last round is cut short by the deadline
+        assert_eq!(
+            pow_round_timeout(tips_interval, Some(Duration::from_secs(12)), Duration::from_secs(10)).unwrap(),
+            Duration::from_secs(2)
+        );
+        assert!(matches!(
+            pow_round_timeout(tips_interval, Some(Duration::from_secs(12)), Duration::from_secs(12)),
+            Err(Error::PowDeadlineExceeded(_))
+        ));
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[tokio::test]
+    async fn pow_deadline_exceeded() {
+        let mut client_builder = Client::builder()
+            .with_local_pow(true)
+            .with_pow_deadline(Duration::from_millis(200));
+        // A target that can't be reached in time
+        client_builder.network_info.protocol_parameters = ProtocolParameters::new(
+            2,
+            "testnet".to_string(),
+            "rms".to_string(),
+            u32::MAX,
+            15,
+            Default::default(),
+            1_813_620_509_061_365,
+        )
+        .unwrap();
+        let client = client_builder.finish().unwrap();
+
+        let block = client
+            .finish_pow(Some(Parents::new(vec![BlockId::null()]).unwrap()), None)
+            .await;
+
+        assert!(matches!(block, Err(Error::PowDeadlineExceeded(_))));
+    }
+
+    #[test]
+    fn pow_verification() {
+        // Example from https://github.com/iotaledger/tips/blob/main/tips/TIP-0012/tip-0012.md#example with a score of
+        // 937.29
+        let block_bytes = [
+            0x48, 0x65, 0x6c, 0x6c, 0x6f, 0x2c, 0x20, 0x57, 0x6f, 0x72, 0x6c, 0x64, 0x21, 0x5e, 0xe6, 0xaa, 0xaa, 0xaa,
+            0xaa, 0xaa, 0xaa,
+        ];
+
+        let passing = PowVerification::new(&block_bytes, 900);
+        assert!(passing.valid);
+        assert!((passing.score - 937.2857142857143).abs() < f64::EPSILON);
+
+        let failing = PowVerification::new(&block_bytes, 1000);
+        assert!(!failing.valid);
+        assert_eq!(failing.score, passing.score);
+    }
+}
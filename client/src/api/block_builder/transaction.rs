@@ -3,6 +3,8 @@
 
 //! Transaction preparation and signing
 
+use std::collections::BTreeSet;
+
 use iota_types::block::{
     input::{Input, UtxoInput},
     output::{InputsCommitment, Output, OutputId},
@@ -33,6 +35,7 @@ impl<'a> ClientBlockBuilder<'a> {
     /// Prepare a transaction
     pub async fn prepare_transaction(&self) -> Result<PreparedTransactionData> {
         log::debug!("[prepare_transaction]");
+        self.verify_tag()?;
         let protocol_parameters = self.client.get_protocol_parameters().await?;
         let token_supply = self.client.get_token_supply().await?;
 
@@ -78,10 +81,46 @@ impl<'a> ClientBlockBuilder<'a> {
 
         let essence = TransactionEssence::Regular(regular_essence);
 
+        let reuse_warning = if self.address_reuse_check {
+            // The inputs are spent by this transaction, so only other outputs of the receiving addresses count
+            let input_ids = selected_transaction_data
+                .inputs
+                .iter()
+                .map(|input| *input.output_metadata.output_id())
+                .collect::<Vec<_>>();
+            let mut addresses = BTreeSet::new();
+            addresses.extend(
+                self.outputs
+                    .iter()
+                    .filter_map(|output| output.unlock_conditions()?.address())
+                    .map(|unlock_condition| *unlock_condition.address()),
+            );
+            if let Some(remainder) = &selected_transaction_data.remainder {
+                addresses.insert(remainder.address);
+            }
+
+            let bech32_hrp = self.client.get_bech32_hrp().await?;
+            let mut reuse_warning = false;
+            for address in addresses {
+                if self
+                    .client
+                    .address_used_apart_from(&address.to_bech32(&bech32_hrp), &input_ids)
+                    .await?
+                {
+                    reuse_warning = true;
+                    break;
+                }
+            }
+            reuse_warning
+        } else {
+            false
+        };
+
         Ok(PreparedTransactionData {
             essence,
             inputs_data: selected_transaction_data.inputs,
             remainder: selected_transaction_data.remainder,
+            reuse_warning,
         })
     }
 
@@ -0,0 +1,268 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_types::{
+    api::core::{dto::LedgerInclusionStateDto, response::BlockMetadataResponse},
+    block::{semantic::ConflictReason, BlockId},
+};
+
+#[cfg(all(feature = "mqtt", not(target_family = "wasm")))]
+use crate::node_api::mqtt::{MqttPayload, Topic};
+use crate::{Client, Result};
+
+/// The lifecycle state of a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockState {
+    /// The block is known to the node, but not solid yet.
+    Pending,
+    /// The block and its past cone are known to the node.
+    Solid,
+    /// The block is referenced by a milestone, but its ledger inclusion state isn't known yet.
+    Referenced,
+    /// The block is referenced by a milestone and its transaction, if any, got included.
+    Confirmed,
+    /// The block is referenced by a milestone, but its transaction is conflicting.
+    Conflicting,
+}
+
+impl BlockState {
+    /// Returns whether the state can't change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Confirmed | Self::Conflicting)
+    }
+}
+
+impl From<&BlockMetadataResponse> for BlockState {
+    fn from(metadata: &BlockMetadataResponse) -> Self {
+        match (&metadata.ledger_inclusion_state, metadata.referenced_by_milestone_index) {
+            (Some(LedgerInclusionStateDto::Conflicting), _) => Self::Conflicting,
+            (Some(LedgerInclusionStateDto::Included | LedgerInclusionStateDto::NoTransaction), _) => Self::Confirmed,
+            (None, Some(_)) => Self::Referenced,
+            (None, None) if metadata.is_solid => Self::Solid,
+            (None, None) => Self::Pending,
+        }
+    }
+}
+
+/// Block metadata as returned by the node, together with the state of the block and the name of the conflict reason
+/// the node only returns as numeric code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockMetadataWithState {
+    /// The metadata as returned by the node.
+    #[serde(flatten)]
+    pub metadata: BlockMetadataResponse,
+    /// The state derived from the metadata, `pending` or `solid` until the block is referenced by a milestone.
+    pub state: BlockState,
+    /// The camelCase name of the conflict reason, like `inputUtxoAlreadySpent`, if the block is conflicting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict_reason_name: Option<String>,
+}
+
+impl From<BlockMetadataResponse> for BlockMetadataWithState {
+    fn from(metadata: BlockMetadataResponse) -> Self {
+        let state = BlockState::from(&metadata);
+        let conflict_reason_name = metadata
+            .conflict_reason
+            .filter(|_| state == BlockState::Conflicting)
+            .and_then(|code| ConflictReason::try_from(code).ok())
+            .map(|reason| conflict_reason_name(reason).to_string());
+
+        Self {
+            metadata,
+            state,
+            conflict_reason_name,
+        }
+    }
+}
+
+impl Client {
+    /// Calls the callback with every state transition of a block until it's confirmed or conflicting and returns the
+    /// final state. With the `mqtt` feature the block metadata topic is used and the node is only polled in the
+    /// provided interval if no update was received, otherwise or if subscribing fails the node is polled in the
+    /// interval.
+    pub async fn watch_block<C>(&self, block_id: &BlockId, interval: Duration, mut callback: C) -> Result<BlockState>
+    where
+        C: FnMut(BlockState) + Send,
+    {
+        let mut last_state = None;
+
+        #[cfg(all(feature = "mqtt", not(target_family = "wasm")))]
+        {
+            let topic = Topic::try_new(format!("block-metadata/{block_id}"))?;
+            let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+            let subscribed = self
+                .subscribe_handler(vec![topic.clone()], move |event| {
+                    if let MqttPayload::Json(value) = &event.payload {
+                        let _ = sender.send(value.clone());
+                    }
+                })
+                .await;
+
+            match subscribed {
+                Ok(handler) => {
+                    // Only request the metadata after subscribing, so no update can be missed
+                    let mut metadata = self.get_block_metadata(block_id).await;
+
+                    let state = loop {
+                        match metadata {
+                            Ok(metadata) => {
+                                if let Some(state) = deliver_state(&mut last_state, &metadata, &mut callback) {
+                                    break Ok(state);
+                                }
+                            }
+                            Err(e) => break Err(e),
+                        }
+
+                        metadata = match tokio::time::timeout(interval, receiver.recv()).await {
+                            Ok(Some(value)) => serde_json::from_value(value).map_err(Into::into),
+                            // No update received in time, the connection might have been lost
+                            _ => self.get_block_metadata(block_id).await,
+                        };
+                    };
+
+                    // Other subscriptions to the topic are kept
+                    self.unsubscribe_handler(vec![topic], &handler).await?;
+                    return state;
+                }
+                Err(e) => log::warn!("Subscribing to the block metadata failed, polling the node instead: {e}"),
+            }
+        }
+
+        loop {
+            let metadata = self.get_block_metadata(block_id).await?;
+            if let Some(state) = deliver_state(&mut last_state, &metadata, &mut callback) {
+                return Ok(state);
+            }
+
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(interval.as_millis().try_into().unwrap()).await;
+
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+// Returns the camelCase name of the conflict reason.
+const fn conflict_reason_name(reason: ConflictReason) -> &'static str {
+    match reason {
+        ConflictReason::None => "none",
+        ConflictReason::InputUtxoAlreadySpent => "inputUtxoAlreadySpent",
+        ConflictReason::InputUtxoAlreadySpentInThisMilestone => "inputUtxoAlreadySpentInThisMilestone",
+        ConflictReason::InputUtxoNotFound => "inputUtxoNotFound",
+        ConflictReason::CreatedConsumedAmountMismatch => "createdConsumedAmountMismatch",
+        ConflictReason::InvalidSignature => "invalidSignature",
+        ConflictReason::TimelockNotExpired => "timelockNotExpired",
+        ConflictReason::InvalidNativeTokens => "invalidNativeTokens",
+        ConflictReason::StorageDepositReturnUnfulfilled => "storageDepositReturnUnfulfilled",
+        ConflictReason::InvalidUnlock => "invalidUnlock",
+        ConflictReason::InputsCommitmentsMismatch => "inputsCommitmentsMismatch",
+        ConflictReason::UnverifiedSender => "unverifiedSender",
+        ConflictReason::InvalidChainStateTransition => "invalidChainStateTransition",
+        ConflictReason::SemanticValidationFailed => "semanticValidationFailed",
+    }
+}
+
+// Calls the callback if the state of the block changed and returns the state once it's terminal.
+fn deliver_state<C: FnMut(BlockState)>(
+    last_state: &mut Option<BlockState>,
+    metadata: &BlockMetadataResponse,
+    callback: &mut C,
+) -> Option<BlockState> {
+    let state = BlockState::from(metadata);
+
+    if last_state.replace(state) != Some(state) {
+        callback(state);
+    }
+
+    state.is_terminal().then_some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(value: serde_json::Value) -> BlockMetadataResponse {
+        let mut metadata = serde_json::json!({
+            "blockId": "0xb00b5f6e6e6e3e3e4b1b6c2d3d9e2c5b2a5f55c94a4e5f22c1e4d1d1e2d1f1a0",
+            "parents": ["0x3f4a2c5b2a5f55c94a4e5f22c1e4d1d1e2d1f1a0b00b5f6e6e6e3e3e4b1b6c2d"],
+            "isSolid": false,
+        });
+        metadata
+            .as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(metadata).unwrap()
+    }
+
+    #[test]
+    fn confirming_block_transitions() {
+        let updates = [
+            metadata(serde_json::json!({})),
+            metadata(serde_json::json!({})),
+            metadata(serde_json::json!({ "isSolid": true })),
+            metadata(serde_json::json!({ "isSolid": true, "referencedByMilestoneIndex": 5 })),
+            metadata(serde_json::json!({
+                "isSolid": true,
+                "referencedByMilestoneIndex": 5,
+                "ledgerInclusionState": "included"
+            })),
+        ];
+
+        let mut last_state = None;
+        let mut states = Vec::new();
+        let final_state = updates
+            .iter()
+            .find_map(|metadata| deliver_state(&mut last_state, metadata, &mut |state| states.push(state)));
+
+        assert_eq!(final_state, Some(BlockState::Confirmed));
+        assert_eq!(
+            states,
+            vec![
+                BlockState::Pending,
+                BlockState::Solid,
+                BlockState::Referenced,
+                BlockState::Confirmed
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicting_block_is_terminal() {
+        let metadata = metadata(serde_json::json!({
+            "isSolid": true,
+            "referencedByMilestoneIndex": 5,
+            "ledgerInclusionState": "conflicting",
+            "conflictReason": 1
+        }));
+
+        assert_eq!(BlockState::from(&metadata), BlockState::Conflicting);
+        assert!(BlockState::Conflicting.is_terminal());
+        assert!(!BlockState::Referenced.is_terminal());
+    }
+
+    #[test]
+    fn metadata_with_state() {
+        let conflicting = BlockMetadataWithState::from(metadata(serde_json::json!({
+            "isSolid": true,
+            "referencedByMilestoneIndex": 5,
+            "ledgerInclusionState": "conflicting",
+            "conflictReason": 1
+        })));
+        let json = serde_json::to_value(&conflicting).unwrap();
+
+        assert_eq!(json["state"], "conflicting");
+        assert_eq!(json["conflictReason"], 1);
+        assert_eq!(json["conflictReasonName"], "inputUtxoAlreadySpent");
+
+        let pending = serde_json::to_value(BlockMetadataWithState::from(metadata(serde_json::json!({})))).unwrap();
+
+        assert_eq!(pending["state"], "pending");
+        assert!(pending.get("conflictReasonName").is_none());
+    }
+}
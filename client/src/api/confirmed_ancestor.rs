@@ -0,0 +1,175 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use futures::Future;
+use iota_types::block::BlockId;
+
+use crate::{constants::MAX_CONFIRMED_ANCESTOR_REQUESTS, Client, Error, Result};
+
+impl Client {
+    /// Walks the parents of a block breadth-first until a block referenced by a milestone is found and returns the
+    /// path from the block to it, useful to find out where a branch of the tangle that doesn't get confirmed is stuck.
+    /// Parents that the node doesn't know anymore were pruned and are taken as referenced. Returns `None` if no
+    /// referenced block is found within `max_depth` parent levels or within 1000 requests.
+    pub async fn find_confirmed_ancestor(&self, block_id: &BlockId, max_depth: u32) -> Result<Option<Vec<BlockId>>> {
+        find_referenced_ancestor(
+            *block_id,
+            max_depth,
+            MAX_CONFIRMED_ANCESTOR_REQUESTS,
+            |block_id| async move {
+                let metadata = self.get_block_metadata(&block_id).await?;
+                let parents = metadata
+                    .parents
+                    .iter()
+                    .map(|parent| BlockId::from_str(parent))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+
+                Ok((metadata.referenced_by_milestone_index.is_some(), parents))
+            },
+        )
+        .await
+    }
+}
+
+// Breadth-first search over the parents returned by `fetch` together with whether a block is referenced. Every block
+// is only fetched once and the search stops after `max_depth` parent levels or `max_requests` fetched blocks. Parents
+// that can't be found anymore were pruned, which only happens to referenced blocks.
+#[allow(clippy::future_not_send)]
+async fn find_referenced_ancestor<F, Fut>(
+    block_id: BlockId,
+    max_depth: u32,
+    max_requests: usize,
+    mut fetch: F,
+) -> Result<Option<Vec<BlockId>>>
+where
+    F: FnMut(BlockId) -> Fut,
+    Fut: Future<Output = Result<(bool, Vec<BlockId>)>>,
+{
+    // Child through which a block was first reached, to reconstruct the path
+    let mut children = HashMap::new();
+    let mut visited = HashSet::from([block_id]);
+    let mut level = vec![block_id];
+    let mut requests = 0;
+
+    for depth in 0..=max_depth {
+        let mut next_level = Vec::new();
+
+        for block_id in level {
+            if requests == max_requests {
+                return Ok(None);
+            }
+            requests += 1;
+
+            let (referenced, parents) = match fetch(block_id).await {
+                Ok(block) => block,
+                Err(Error::NotFound(_) | Error::Pruned(_)) if depth > 0 => (true, Vec::new()),
+                Err(e) => return Err(e),
+            };
+
+            if referenced {
+                let mut path = vec![block_id];
+                while let Some(child) = children.get(path.last().unwrap()) {
+                    path.push(*child);
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+
+            if depth < max_depth {
+                for parent in parents {
+                    if visited.insert(parent) {
+                        children.insert(parent, block_id);
+                        next_level.push(parent);
+                    }
+                }
+            }
+        }
+
+        level = next_level;
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A tangle where block 1 references 2 and 3, 2 references 4, 3 references 4 and 5, 5 references 6 and only 6 is
+    // referenced by a milestone.
+    async fn fetch(block_id: BlockId) -> Result<(bool, Vec<BlockId>)> {
+        let id = |byte| BlockId::new([byte; 32]);
+        let bytes: &[u8] = block_id.as_ref();
+        let parents = match bytes[0] {
+            1 => vec![id(2), id(3)],
+            2 => vec![id(4)],
+            3 => vec![id(4), id(5)],
+            4 => vec![id(7)],
+            5 => vec![id(6)],
+            _ => vec![],
+        };
+
+        Ok((block_id == id(6), parents))
+    }
+
+    #[tokio::test]
+    async fn confirmed_ancestor_at_depth_three() {
+        let path = find_referenced_ancestor(BlockId::new([1; 32]), 5, 100, fetch)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            path,
+            Some(vec![
+                BlockId::new([1; 32]),
+                BlockId::new([3; 32]),
+                BlockId::new([5; 32]),
+                BlockId::new([6; 32])
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn no_confirmed_ancestor_within_depth() {
+        let path = find_referenced_ancestor(BlockId::new([1; 32]), 2, 100, fetch)
+            .await
+            .unwrap();
+
+        assert_eq!(path, None);
+    }
+
+    #[tokio::test]
+    async fn no_confirmed_ancestor_within_requests() {
+        // Blocks 1, 2, 3 and 4 are fetched before block 5
+        let path = find_referenced_ancestor(BlockId::new([1; 32]), 5, 4, fetch)
+            .await
+            .unwrap();
+
+        assert_eq!(path, None);
+    }
+
+    #[tokio::test]
+    async fn pruned_parent_is_confirmed() {
+        let pruned = |block_id: BlockId| async move {
+            if block_id == BlockId::new([1; 32]) {
+                Ok((false, vec![BlockId::new([2; 32])]))
+            } else {
+                Err(Error::NotFound("block not found".to_string()))
+            }
+        };
+
+        let path = find_referenced_ancestor(BlockId::new([1; 32]), 5, 100, pruned)
+            .await
+            .unwrap();
+        assert_eq!(path, Some(vec![BlockId::new([1; 32]), BlockId::new([2; 32])]));
+
+        // An unknown block itself is still an error
+        let path = find_referenced_ancestor(BlockId::new([3; 32]), 5, 100, pruned).await;
+        assert!(matches!(path, Err(Error::NotFound(_))));
+    }
+}
@@ -3,44 +3,114 @@
 
 use std::str::FromStr;
 
-use iota_types::block::{
-    address::Address,
-    input::{UtxoInput, INPUT_COUNT_MAX},
-    output::{
-        unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeTokensBuilder, Output, OutputId,
-        UnlockCondition,
+use futures::Future;
+use iota_types::{
+    api::core::response::OutputWithMetadataResponse,
+    block::{
+        address::Address,
+        input::{UtxoInput, INPUT_COUNT_MAX},
+        output::{
+            unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeTokensBuilder, Output, OutputId,
+            UnlockCondition,
+        },
+        payload::transaction::TransactionId,
+        BlockId,
     },
-    payload::transaction::TransactionId,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
     api::GetAddressesBuilderOptions, node_api::indexer::query_parameters::QueryParameter, secret::SecretManager,
-    Client, Result,
+    Client, Error, Result,
 };
 
 impl Client {
     /// Function to consolidate all funds and native tokens from a range of addresses to the address with the lowest
-    /// index in that range. Returns the address to which the funds got consolidated, if any were available
+    /// index in that range. Returns the address to which the funds got consolidated, if any were available. Errors with
+    /// [`Error::MissingParameter`] if the range is empty. If a step fails after consolidation blocks were sent,
+    /// [`Error::PartiallySent`] is returned with their block ids.
     pub async fn consolidate_funds(
         &self,
         secret_manager: &SecretManager,
         address_builder_options: GetAddressesBuilderOptions,
     ) -> Result<String> {
-        let token_supply = self.get_token_supply().await?;
-        let mut last_transfer_index = address_builder_options.range.as_ref().unwrap_or(&(0..1)).start;
         // use the start index as offset
-        let offset = last_transfer_index;
+        let offset = address_builder_options.range.as_ref().unwrap_or(&(0..1)).start;
 
         let addresses = self
             .get_addresses(secret_manager)
             .set_options(address_builder_options)?
             .finish()
             .await?;
+        let consolidation_address = addresses
+            .first()
+            .cloned()
+            .ok_or(Error::MissingParameter("address range"))?;
+
+        self.consolidate_addresses(secret_manager, offset, &addresses).await?;
 
-        let consolidation_address = addresses[0].clone();
+        Ok(consolidation_address)
+    }
+
+    /// Consolidates all funds and native tokens like [`Client::consolidate_funds()`], but returns the ids of the
+    /// consolidation blocks, which is empty if there was nothing to consolidate. Outputs are consolidated in blocks of
+    /// at most [`INPUT_COUNT_MAX`] inputs until every address of the range is consolidated. If a step fails after
+    /// blocks were sent, [`Error::PartiallySent`] is returned with their block ids.
+    pub async fn consolidate_funds_with_block_ids(
+        &self,
+        secret_manager: &SecretManager,
+        address_builder_options: GetAddressesBuilderOptions,
+    ) -> Result<Vec<BlockId>> {
+        // use the start index as offset
+        let offset = address_builder_options.range.as_ref().unwrap_or(&(0..1)).start;
+
+        let addresses = self
+            .get_addresses(secret_manager)
+            .set_options(address_builder_options)?
+            .finish()
+            .await?;
+
+        if addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.consolidate_addresses(secret_manager, offset, &addresses).await
+    }
+
+    // Consolidates the funds of the addresses, whose first index is the offset, to the first address and returns the
+    // ids of the sent blocks. Errors if there are no addresses, if a later step fails the error contains the ids of the
+    // blocks that were already sent.
+    async fn consolidate_addresses(
+        &self,
+        secret_manager: &SecretManager,
+        offset: u32,
+        addresses: &[String],
+    ) -> Result<Vec<BlockId>> {
+        let mut block_ids = Vec::new();
+
+        match self
+            .send_consolidation_blocks(secret_manager, offset, addresses, &mut block_ids)
+            .await
+        {
+            Ok(()) => Ok(block_ids),
+            Err(e) => Err(e.with_sent_blocks(&block_ids)),
+        }
+    }
+
+    // Sends the consolidation blocks of the addresses and adds their ids to `sent_block_ids` as soon as they're sent.
+    async fn send_consolidation_blocks(
+        &self,
+        secret_manager: &SecretManager,
+        offset: u32,
+        addresses: &[String],
+        sent_block_ids: &mut Vec<BlockId>,
+    ) -> Result<()> {
+        let consolidation_address = addresses.first().ok_or(Error::MissingParameter("address range"))?;
+        let token_supply = self.get_token_supply().await?;
+        let mut last_transfer_index = offset;
 
         'consolidation: loop {
-            let mut block_ids = Vec::new();
+            let round_start = sent_block_ids.len();
             // Iterate over addresses reversed so the funds end up on the first address in the range
             for (index, address) in addresses.iter().enumerate().rev() {
                 let index = index as u32;
@@ -73,49 +143,288 @@ impl Client {
                 let outputs_chunks = basic_outputs_responses.chunks(INPUT_COUNT_MAX.into());
 
                 for chunk in outputs_chunks {
-                    let mut block_builder = self.block().with_secret_manager(secret_manager);
-                    let mut total_amount = 0;
-                    let mut total_native_tokens = NativeTokensBuilder::new();
+                    let block_id = self
+                        .consolidate_outputs(secret_manager, index, consolidation_address, chunk, token_supply)
+                        .await?;
+                    sent_block_ids.push(block_id);
+                }
+            }
+
+            if sent_block_ids.len() == round_start {
+                break 'consolidation;
+            }
+            // Wait for txs to get confirmed so we don't create conflicting txs
+            for block_id in &sent_block_ids[round_start..] {
+                let _ = self.retry_until_included(block_id, None, None).await?;
+            }
+        }
 
-                    for output_response in chunk {
-                        block_builder = block_builder.with_input(UtxoInput::from(OutputId::new(
-                            TransactionId::from_str(&output_response.metadata.transaction_id)?,
-                            output_response.metadata.output_index,
-                        )?))?;
+        Ok(())
+    }
 
-                        let output = Output::try_from_dto(&output_response.output, token_supply)?;
+    /// Collects the outputs of a range of addresses that [`Client::consolidate_funds_from_checkpoint()`] will
+    /// consolidate to the address with the lowest index in that range. The checkpoint can be persisted, so an
+    /// interrupted consolidation can be resumed later. Errors with [`Error::MissingParameter`] if the range is empty.
+    pub async fn consolidation_checkpoint(
+        &self,
+        secret_manager: &SecretManager,
+        address_builder_options: GetAddressesBuilderOptions,
+    ) -> Result<ConsolidationCheckpoint> {
+        // use the start index as offset
+        let offset = address_builder_options.range.as_ref().unwrap_or(&(0..1)).start;
 
-                        if let Some(native_tokens) = output.native_tokens() {
-                            total_native_tokens.add_native_tokens(native_tokens.clone())?;
-                        }
-                        total_amount += output.amount();
-                    }
+        let addresses = self
+            .get_addresses(secret_manager)
+            .set_options(address_builder_options)?
+            .finish()
+            .await?;
+        let consolidation_address = addresses
+            .first()
+            .cloned()
+            .ok_or(Error::MissingParameter("address range"))?;
+
+        let mut remaining_outputs = Vec::new();
+        // Iterate over addresses reversed so the funds end up on the first address in the range
+        for (index, address) in addresses.iter().enumerate().rev() {
+            let output_ids = self
+                .basic_output_ids(vec![
+                    QueryParameter::Address(address.to_string()),
+                    QueryParameter::HasExpiration(false),
+                    QueryParameter::HasTimelock(false),
+                    QueryParameter::HasStorageDepositReturn(false),
+                ])
+                .await?
+                .items;
+
+            // A single output on the consolidation address is already consolidated
+            if output_ids.is_empty() || (index == 0 && output_ids.len() < 2) {
+                continue;
+            }
+            remaining_outputs.push(AddressOutputs {
+                address_index: index as u32 + offset,
+                output_ids,
+            });
+        }
+
+        Ok(ConsolidationCheckpoint {
+            consolidation_address,
+            remaining_outputs,
+        })
+    }
 
-                    let consolidation_output = BasicOutputBuilder::new_with_amount(total_amount)?
-                        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
-                            Address::try_from_bech32(&consolidation_address)?.1,
-                        )))
-                        .with_native_tokens(total_native_tokens.finish()?)
-                        .finish_output(token_supply)?;
-
-                    let block = block_builder
-                        .with_input_range(index..index + 1)
-                        .with_outputs(vec![consolidation_output])?
-                        .with_initial_address_index(0)
-                        .finish()
+    /// Consolidates the remaining outputs of the checkpoint. The checkpoint is updated after every consolidation
+    /// transaction got included, so if this fails it only contains the outputs that weren't consolidated yet and can be
+    /// used to resume the consolidation. Outputs that already got spent in the meantime are skipped. If a sent block
+    /// doesn't get included, [`Error::PartiallySent`] is returned with its block id.
+    pub async fn consolidate_funds_from_checkpoint(
+        &self,
+        secret_manager: &SecretManager,
+        checkpoint: &mut ConsolidationCheckpoint,
+    ) -> Result<()> {
+        let token_supply = self.get_token_supply().await?;
+        let consolidation_address = checkpoint.consolidation_address.clone();
+
+        consolidate_remaining_outputs(checkpoint, |address_index, output_ids| {
+            let consolidation_address = &consolidation_address;
+            async move {
+                let outputs_responses = self
+                    .get_outputs(output_ids)
+                    .await?
+                    .into_iter()
+                    .filter(|output_response| !output_response.metadata.is_spent)
+                    .collect::<Vec<_>>();
+
+                if !outputs_responses.is_empty() {
+                    let block_id = self
+                        .consolidate_outputs(
+                            secret_manager,
+                            address_index,
+                            consolidation_address,
+                            &outputs_responses,
+                            token_supply,
+                        )
                         .await?;
-                    block_ids.push(block.id());
+                    // Wait for the tx to get confirmed so the outputs are only removed from the checkpoint once they're
+                    // consolidated
+                    self.retry_until_included(&block_id, None, None)
+                        .await
+                        .map_err(|e| e.with_sent_blocks(&[block_id]))?;
                 }
+                Ok(())
             }
+        })
+        .await
+    }
 
-            if block_ids.is_empty() {
-                break 'consolidation;
-            }
-            // Wait for txs to get confirmed so we don't create conflicting txs
-            for block_id in block_ids {
-                let _ = self.retry_until_included(&block_id, None, None).await?;
+    // Consolidates the outputs, which have to belong to the address with the provided index, to the consolidation
+    // address.
+    async fn consolidate_outputs(
+        &self,
+        secret_manager: &SecretManager,
+        index: u32,
+        consolidation_address: &str,
+        outputs_responses: &[OutputWithMetadataResponse],
+        token_supply: u64,
+    ) -> Result<BlockId> {
+        let mut block_builder = self.block().with_secret_manager(secret_manager);
+        let mut total_amount = 0;
+        let mut total_native_tokens = NativeTokensBuilder::new();
+
+        for output_response in outputs_responses {
+            block_builder = block_builder.with_input(UtxoInput::from(OutputId::new(
+                TransactionId::from_str(&output_response.metadata.transaction_id)?,
+                output_response.metadata.output_index,
+            )?))?;
+
+            let output = Output::try_from_dto(&output_response.output, token_supply)?;
+
+            if let Some(native_tokens) = output.native_tokens() {
+                total_native_tokens.add_native_tokens(native_tokens.clone())?;
             }
+            total_amount += output.amount();
         }
-        Ok(consolidation_address)
+
+        let consolidation_output = BasicOutputBuilder::new_with_amount(total_amount)?
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(
+                Address::try_from_bech32(consolidation_address)?.1,
+            )))
+            .with_native_tokens(total_native_tokens.finish()?)
+            .finish_output(token_supply)?;
+
+        let block = block_builder
+            .with_input_range(index..index + 1)
+            .with_outputs(vec![consolidation_output])?
+            .with_initial_address_index(0)
+            .finish()
+            .await?;
+
+        Ok(block.id())
+    }
+}
+
+/// The state of a consolidation, containing the outputs that still need to be consolidated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationCheckpoint {
+    /// The address to which the funds get consolidated.
+    pub consolidation_address: String,
+    /// The outputs that still need to be consolidated.
+    pub remaining_outputs: Vec<AddressOutputs>,
+}
+
+/// Output ids that belong to an address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressOutputs {
+    /// The index of the address.
+    pub address_index: u32,
+    /// The output ids.
+    pub output_ids: Vec<OutputId>,
+}
+
+// Consolidates the remaining outputs of the checkpoint in chunks of at most `INPUT_COUNT_MAX` outputs, a chunk is only
+// removed from the checkpoint after it got consolidated.
+#[allow(clippy::future_not_send)]
+async fn consolidate_remaining_outputs<F, Fut>(
+    checkpoint: &mut ConsolidationCheckpoint,
+    mut consolidate: F,
+) -> Result<()>
+where
+    F: FnMut(u32, Vec<OutputId>) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    while let Some(address_outputs) = checkpoint.remaining_outputs.first_mut() {
+        let chunk_len = address_outputs.output_ids.len().min(INPUT_COUNT_MAX.into());
+        let chunk = address_outputs.output_ids[..chunk_len].to_vec();
+
+        consolidate(address_outputs.address_index, chunk).await?;
+
+        address_outputs.output_ids.drain(..chunk_len);
+        if address_outputs.output_ids.is_empty() {
+            checkpoint.remaining_outputs.remove(0);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SHIMMER_TESTNET_BECH32_HRP;
+
+    fn output_id(index: u16) -> OutputId {
+        OutputId::new(TransactionId::new([1; 32]), index).unwrap()
+    }
+
+    #[tokio::test]
+    async fn resume_from_checkpoint() {
+        let mut checkpoint = ConsolidationCheckpoint {
+            consolidation_address: "rms1qzt7s5ssn3tudx3nquhn9qsuxfcmvvjnkxmzu9ulhkfjc06vnxyegn5c3h5".to_string(),
+            remaining_outputs: vec![
+                AddressOutputs {
+                    address_index: 2,
+                    output_ids: vec![output_id(0), output_id(1)],
+                },
+                AddressOutputs {
+                    address_index: 1,
+                    output_ids: vec![output_id(2)],
+                },
+                AddressOutputs {
+                    address_index: 0,
+                    output_ids: vec![output_id(3), output_id(4)],
+                },
+            ],
+        };
+        let mut consolidated = Vec::new();
+
+        // The consolidation gets interrupted at the second address
+        let result = consolidate_remaining_outputs(&mut checkpoint, |address_index, output_ids| {
+            let result = if address_index == 1 {
+                Err(Error::Node("connection closed".to_string()))
+            } else {
+                consolidated.push((address_index, output_ids));
+                Ok(())
+            };
+            async move { result }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(consolidated, vec![(2, vec![output_id(0), output_id(1)])]);
+        assert_eq!(checkpoint.remaining_outputs.len(), 2);
+
+        // Resuming only consolidates the remaining outputs
+        let mut resumed = Vec::new();
+        consolidate_remaining_outputs(&mut checkpoint, |address_index, output_ids| {
+            resumed.push((address_index, output_ids));
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            resumed,
+            vec![(1, vec![output_id(2)]), (0, vec![output_id(3), output_id(4)])]
+        );
+        assert!(checkpoint.remaining_outputs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn consolidate_empty_range() {
+        let client = Client::builder().with_ignore_node_health().finish().unwrap();
+        let secret_manager = SecretManager::try_from_mnemonic(&Client::generate_mnemonic().unwrap()).unwrap();
+        let options = || GetAddressesBuilderOptions {
+            range: Some(0..0),
+            bech32_hrp: Some(SHIMMER_TESTNET_BECH32_HRP.to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            client.consolidate_funds(&secret_manager, options()).await,
+            Err(Error::MissingParameter("address range"))
+        ));
+        assert!(matches!(
+            client.consolidation_checkpoint(&secret_manager, options()).await,
+            Err(Error::MissingParameter("address range"))
+        ));
     }
 }
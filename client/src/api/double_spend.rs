@@ -0,0 +1,219 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use iota_types::block::{
+    output::{dto::OutputMetadataDto, OutputId},
+    payload::transaction::TransactionId,
+};
+
+#[cfg(all(feature = "mqtt", not(target_family = "wasm")))]
+use crate::node_api::mqtt::{MqttPayload, Topic};
+use crate::{Client, Result};
+
+/// A watched output got spent by another transaction than the expected one.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DoubleSpend {
+    /// The watched output.
+    pub output_id: OutputId,
+    /// The transaction that was expected to spend the output.
+    pub transaction_id: TransactionId,
+    /// The transaction that spent the output instead.
+    pub conflicting_transaction_id: TransactionId,
+}
+
+impl Client {
+    /// Watches outputs that are expected to be spent by the given transactions. If an output gets spent by another
+    /// transaction, the callback is called with the [`DoubleSpend`]. Returns once all watched outputs are spent. With
+    /// the `mqtt` feature the output topics are used and the node is only polled in the provided interval if no update
+    /// was received, otherwise or if subscribing fails the node is polled in the interval. Transient errors, like from
+    /// an overloaded node, are skipped until the next poll. Errors with [`Error::NotFound`](crate::Error::NotFound) if
+    /// the node doesn't know a watched output, which would otherwise be watched forever.
+    pub async fn watch_double_spends<C>(
+        &self,
+        mut watched_outputs: HashMap<OutputId, TransactionId>,
+        interval: Duration,
+        mut callback: C,
+    ) -> Result<()>
+    where
+        C: FnMut(DoubleSpend) + Send,
+    {
+        #[cfg(all(feature = "mqtt", not(target_family = "wasm")))]
+        {
+            let topics = watched_outputs
+                .keys()
+                .map(|output_id| Topic::try_new(format!("outputs/{output_id}")))
+                .collect::<std::result::Result<Vec<_>, _>>();
+            let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+            let subscribed = match topics {
+                Ok(topics) => self
+                    .subscribe_handler(topics.clone(), move |event| {
+                        if let MqttPayload::Json(value) = &event.payload {
+                            if let Some(metadata) = value.get("metadata") {
+                                let _ = sender.send(metadata.clone());
+                            }
+                        }
+                    })
+                    .await
+                    .map(|handler| (topics, handler)),
+                Err(e) => Err(e),
+            };
+
+            match subscribed {
+                Ok((topics, handler)) => {
+                    let result: Result<()> = async {
+                        // Only request the metadata after subscribing, so no update can be missed
+                        self.poll_double_spends(&mut watched_outputs, &mut callback).await?;
+
+                        while !watched_outputs.is_empty() {
+                            match tokio::time::timeout(interval, receiver.recv()).await {
+                                Ok(Some(metadata)) => handle_output_metadata(
+                                    &mut watched_outputs,
+                                    &serde_json::from_value(metadata)?,
+                                    &mut callback,
+                                )?,
+                                // No update received in time, the connection might have been lost
+                                _ => self.poll_double_spends(&mut watched_outputs, &mut callback).await?,
+                            }
+                        }
+                        Ok(())
+                    }
+                    .await;
+
+                    self.unsubscribe_handler(topics, &handler).await?;
+                    return result;
+                }
+                Err(e) => log::warn!("Subscribing to the watched outputs failed, polling the node instead: {e}"),
+            }
+        }
+
+        loop {
+            self.poll_double_spends(&mut watched_outputs, &mut callback).await?;
+
+            if watched_outputs.is_empty() {
+                return Ok(());
+            }
+
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(interval.as_millis().try_into().unwrap()).await;
+
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    // Requests the metadata of the watched outputs and handles it. Outputs whose request failed with a transient error
+    // are requested again in the next poll, other errors are returned.
+    async fn poll_double_spends<C: FnMut(DoubleSpend)>(
+        &self,
+        watched_outputs: &mut HashMap<OutputId, TransactionId>,
+        callback: &mut C,
+    ) -> Result<()> {
+        let output_ids = watched_outputs.keys().copied().collect::<Vec<_>>();
+        let results =
+            futures::future::join_all(output_ids.iter().map(|output_id| self.get_output_metadata(output_id))).await;
+
+        for (output_id, result) in output_ids.iter().zip(results) {
+            match result {
+                Ok(metadata) => handle_output_metadata(watched_outputs, &metadata, callback)?,
+                Err(e) if e.is_transient() => {
+                    log::debug!("Requesting the metadata of output {output_id} failed, retrying in the next poll: {e}");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Calls the callback if a watched output got double spent and stops watching it once it's spent.
+fn handle_output_metadata<C: FnMut(DoubleSpend)>(
+    watched_outputs: &mut HashMap<OutputId, TransactionId>,
+    metadata: &OutputMetadataDto,
+    callback: &mut C,
+) -> Result<()> {
+    let output_id = metadata.output_id()?;
+    if let Some(transaction_id) = watched_outputs.get(&output_id) {
+        if let Some(double_spend) = detect_double_spend(output_id, *transaction_id, metadata)? {
+            callback(double_spend);
+        }
+        if metadata.is_spent {
+            watched_outputs.remove(&output_id);
+        }
+    }
+
+    Ok(())
+}
+
+// Returns a double spend if the output got spent by another transaction than the expected one.
+fn detect_double_spend(
+    output_id: OutputId,
+    transaction_id: TransactionId,
+    metadata: &OutputMetadataDto,
+) -> Result<Option<DoubleSpend>> {
+    match &metadata.transaction_id_spent {
+        Some(transaction_id_spent) => {
+            let conflicting_transaction_id = TransactionId::from_str(transaction_id_spent)?;
+
+            Ok((conflicting_transaction_id != transaction_id).then_some(DoubleSpend {
+                output_id,
+                transaction_id,
+                conflicting_transaction_id,
+            }))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(transaction_id_spent: Option<TransactionId>) -> OutputMetadataDto {
+        OutputMetadataDto {
+            block_id: "0xb00b5f6e6e6e3e3e4b1b6c2d3d9e2c5b2a5f55c94a4e5f22c1e4d1d1e2d1f1a0".to_string(),
+            transaction_id: TransactionId::new([1; 32]).to_string(),
+            output_index: 0,
+            is_spent: transaction_id_spent.is_some(),
+            milestone_index_spent: transaction_id_spent.map(|_| 6),
+            milestone_timestamp_spent: transaction_id_spent.map(|_| 1_680_000_060),
+            transaction_id_spent: transaction_id_spent.map(|transaction_id| transaction_id.to_string()),
+            milestone_index_booked: 5,
+            milestone_timestamp_booked: 1_680_000_000,
+            ledger_index: 6,
+        }
+    }
+
+    #[test]
+    fn conflicting_spend_is_detected() {
+        let output_id = OutputId::new(TransactionId::new([1; 32]), 0).unwrap();
+        let own_transaction_id = TransactionId::new([2; 32]);
+        let conflicting_transaction_id = TransactionId::new([3; 32]);
+
+        assert_eq!(
+            detect_double_spend(output_id, own_transaction_id, &metadata(None)).unwrap(),
+            None
+        );
+        assert_eq!(
+            detect_double_spend(output_id, own_transaction_id, &metadata(Some(own_transaction_id))).unwrap(),
+            None
+        );
+        assert_eq!(
+            detect_double_spend(
+                output_id,
+                own_transaction_id,
+                &metadata(Some(conflicting_transaction_id))
+            )
+            .unwrap(),
+            Some(DoubleSpend {
+                output_id,
+                transaction_id: own_transaction_id,
+                conflicting_transaction_id,
+            })
+        );
+    }
+}
@@ -1,15 +1,22 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashSet, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashSet},
+    ops::RangeInclusive,
+    str::FromStr,
+    sync::PoisonError,
+};
 
 use iota_types::{
     api::core::{dto::LedgerInclusionStateDto, response::OutputWithMetadataResponse},
     block::{
+        address::Address,
         input::{Input, UtxoInput, INPUT_COUNT_MAX},
-        output::{Output, OutputId},
+        output::{dto::OutputDto, AliasOutput, BasicOutput, FoundryOutput, NftOutput, Output, OutputId},
         parent::Parents,
         payload::{
+            milestone::MilestonePayload,
             transaction::{TransactionEssence, TransactionId},
             Payload,
         },
@@ -18,7 +25,10 @@ use iota_types::{
 };
 
 use crate::{
-    api::{input_selection::Error as InputSelectionError, ClientBlockBuilder, GetAddressesBuilder},
+    api::{
+        input_selection::Error as InputSelectionError, BatchResponse, BatchResults, ClientBlockBuilder,
+        GetAddressesBuilder, MilestoneOutputs, MilestoneTimestamp, PayloadType, RetryAction, RetryStrategy,
+    },
     constants::{
         DEFAULT_RETRY_UNTIL_INCLUDED_INTERVAL, DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT, FIVE_MINUTES_IN_SECONDS,
     },
@@ -65,40 +75,315 @@ impl Client {
 
     /// Return a list of addresses from a secret manager regardless of their validity.
     pub fn get_addresses<'a>(&'a self, secret_manager: &'a SecretManager) -> GetAddressesBuilder<'a> {
-        GetAddressesBuilder::new(secret_manager).with_client(self)
+        GetAddressesBuilder::new(secret_manager)
+            .with_client(self)
+            .with_range(0..self.get_address_scan_batch_size(super::ADDRESS_GAP_RANGE))
     }
 
-    /// Find all blocks by provided block IDs.
-    pub async fn find_blocks(&self, block_ids: &[BlockId]) -> Result<Vec<Block>> {
-        let mut blocks = Vec::new();
+    /// Returns the next public address of an account that never had any outputs. Spent outputs are only known if the
+    /// node has the explorer plugin, otherwise only unspent outputs of all kinds are checked. Every returned address
+    /// index is remembered for the lifetime of the client, so consecutive calls never return the same address twice,
+    /// even if it didn't receive any funds in the meantime. Useful to get a fresh deposit address per invoice.
+    pub async fn get_next_unused_address(
+        &self,
+        secret_manager: &SecretManager,
+        coin_type: u32,
+        account_index: u32,
+    ) -> Result<String> {
+        let bech32_hrp = self.get_bech32_hrp().await?;
+        // The first address identifies the account, so different secret managers don't share the same index
+        let account_address = self
+            .get_addresses(secret_manager)
+            .with_coin_type(coin_type)
+            .with_account_index(account_index)
+            .with_range(0..1)
+            .get_raw()
+            .await?[0];
+
+        let mut start_index = self
+            .unused_address_indexes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&account_address)
+            .map_or(0, |index| index + 1);
+
+        let batch_size = self.get_address_scan_batch_size(super::ADDRESS_GAP_RANGE);
+
+        'search: loop {
+            let addresses = self
+                .get_addresses(secret_manager)
+                .with_coin_type(coin_type)
+                .with_account_index(account_index)
+                .with_range(start_index..start_index + batch_size)
+                .with_bech32_hrp(&bech32_hrp)
+                .finish()
+                .await?;
+
+            let used = futures::future::try_join_all(addresses.iter().map(|address| self.address_has_history(address)))
+                .await?;
+
+            for (offset, (address, used)) in addresses.into_iter().zip(used).enumerate() {
+                let index = start_index + offset as u32;
+
+                if used {
+                    continue;
+                }
 
-        // Use a `HashSet` to prevent duplicate block_ids.
-        let mut block_ids_to_query = HashSet::<BlockId>::new();
+                let highest_index = {
+                    let mut unused_address_indexes = self
+                        .unused_address_indexes
+                        .write()
+                        .unwrap_or_else(PoisonError::into_inner);
+                    match unused_address_indexes.get(&account_address) {
+                        Some(highest_index) if *highest_index >= index => Some(*highest_index),
+                        _ => {
+                            unused_address_indexes.insert(account_address, index);
+                            None
+                        }
+                    }
+                };
+
+                match highest_index {
+                    // Another call returned this or a later address in the meantime, continue after it
+                    Some(highest_index) => {
+                        start_index = highest_index + 1;
+                        continue 'search;
+                    }
+                    None => return Ok(address),
+                }
+            }
 
-        // Collect the `BlockId` in the HashSet.
-        for block_id in block_ids {
-            block_ids_to_query.insert(*block_id);
+            start_index += batch_size;
+        }
+    }
+
+    /// Returns true if the address owns any basic or NFT output, useful to warn about address reuse.
+    pub async fn address_has_outputs(&self, bech32_address: &str) -> Result<bool> {
+        if !self
+            .basic_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+            .await?
+            .items
+            .is_empty()
+        {
+            return Ok(true);
         }
 
+        Ok(!self
+            .nft_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+            .await?
+            .items
+            .is_empty())
+    }
+
+    // Returns true if the address owns any unspent output or, if the node has the explorer plugin, ever owned one.
+    async fn address_has_history(&self, bech32_address: &str) -> Result<bool> {
+        self.address_used_apart_from(bech32_address, &[]).await
+    }
+
+    /// Returns true if the address owns any output other than the excluded ones or, if the node has the explorer
+    /// plugin, ever owned one, useful to warn about address reuse when the excluded outputs are about to be spent.
+    pub async fn address_used_apart_from(
+        &self,
+        bech32_address: &str,
+        excluded_output_ids: &[OutputId],
+    ) -> Result<bool> {
+        if self
+            .address_output_ids(bech32_address, None)
+            .await?
+            .iter()
+            .any(|output_id| !excluded_output_ids.contains(output_id))
+        {
+            return Ok(true);
+        }
+
+        // The indexer only knows unspent outputs. Every output has at most two ledger updates, its creation and its
+        // spending, so one more update than those of the excluded outputs is enough to find another one.
+        let path = &format!("api/explorer/v2/ledger/updates/by-address/{bech32_address}");
+        let query = format!("pageSize={}", 2 * excluded_output_ids.len() + 1);
+        match self
+            .node_manager
+            .get_request::<LedgerUpdatesResponse>(path, Some(&query), self.get_timeout(), false, true)
+            .await
+        {
+            Ok(ledger_updates) => Ok(ledger_updates
+                .items
+                .iter()
+                .any(|ledger_update| !excluded_output_ids.contains(&ledger_update.output_id))),
+            // The node doesn't have the explorer plugin
+            Err(Error::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the IDs of all unspent outputs owned by the address, following the pagination cursor of the indexer. If
+    /// `output_kind` is provided, only outputs of this kind are returned. Alias outputs are found by their state
+    /// controller or governor and foundry outputs by their controlling alias address.
+    pub async fn address_output_ids(&self, bech32_address: &str, output_kind: Option<u8>) -> Result<Vec<OutputId>> {
+        let address = || bech32_address.to_string();
+        let output_kinds = match output_kind {
+            Some(output_kind) => vec![output_kind],
+            // Only alias addresses can control foundries.
+            None => match Address::try_from_bech32(bech32_address)?.1 {
+                Address::Alias(_) => vec![
+                    BasicOutput::KIND,
+                    AliasOutput::KIND,
+                    FoundryOutput::KIND,
+                    NftOutput::KIND,
+                ],
+                _ => vec![BasicOutput::KIND, AliasOutput::KIND, NftOutput::KIND],
+            },
+        };
+
+        let mut output_ids = Vec::new();
+
+        for output_kind in output_kinds {
+            match output_kind {
+                BasicOutput::KIND => output_ids.extend(
+                    self.basic_output_ids(vec![QueryParameter::Address(address())])
+                        .await?
+                        .items,
+                ),
+                AliasOutput::KIND => {
+                    output_ids.extend(
+                        self.alias_output_ids(vec![QueryParameter::StateController(address())])
+                            .await?
+                            .items,
+                    );
+                    output_ids.extend(
+                        self.alias_output_ids(vec![QueryParameter::Governor(address())])
+                            .await?
+                            .items,
+                    );
+                }
+                FoundryOutput::KIND => output_ids.extend(
+                    self.foundry_output_ids(vec![QueryParameter::AliasAddress(address())])
+                        .await?
+                        .items,
+                ),
+                NftOutput::KIND => output_ids.extend(
+                    self.nft_output_ids(vec![QueryParameter::Address(address())])
+                        .await?
+                        .items,
+                ),
+                _ => return Err(iota_types::block::Error::UnsupportedOutputKind(output_kind).into()),
+            }
+        }
+
+        // An alias output can have the address as state controller and governor.
+        let mut seen = HashSet::new();
+        output_ids.retain(|output_id| seen.insert(*output_id));
+
+        Ok(output_ids)
+    }
+
+    /// Find all blocks by provided block IDs. If any request fails, the error or, with
+    /// [`BatchErrorMode::Collect`](crate::api::BatchErrorMode::Collect), `Error::Batch` with the errors of all failed
+    /// requests is returned. Use [`Client::find_blocks_batch()`] to get the successful blocks together with the
+    /// errors.
+    pub async fn find_blocks(&self, block_ids: &[BlockId]) -> Result<Vec<Block>> {
+        self.find_blocks_batch(block_ids).await?.into_items()
+    }
+
+    /// Find all blocks by provided block IDs like [`Client::find_blocks()`]. Failed requests are handled according
+    /// to the [`BatchErrorMode`](crate::api::BatchErrorMode) of the client, the indexes of the returned blocks and
+    /// errors refer to the provided block IDs.
+    pub async fn find_blocks_batch(&self, block_ids: &[BlockId]) -> Result<BatchResponse<Block>> {
+        let mut blocks = BatchResults::new(self.batch_error_mode);
+
+        // Use a `HashSet` to prevent duplicate block_ids, the first occurrence keeps its index.
+        let mut seen = HashSet::<BlockId>::new();
+
         // Use `get_block()` API to get the `Block`.
-        for block_id in block_ids_to_query {
-            let block = self.get_block(&block_id).await?;
-            blocks.push(block);
+        for (index, block_id) in block_ids.iter().enumerate() {
+            if seen.insert(*block_id) {
+                blocks.push(index, self.get_block(block_id).await)?;
+            }
         }
+        Ok(blocks.finish())
+    }
+
+    /// Find all blocks by provided block IDs, like [`Client::find_blocks()`], but only return blocks with one of the
+    /// payload types. The node can't filter by payload type, so this is done client-side. If no payload types are
+    /// provided, all blocks are returned.
+    pub async fn find_blocks_with_payload_types(
+        &self,
+        block_ids: &[BlockId],
+        payload_types: &[PayloadType],
+    ) -> Result<Vec<Block>> {
+        self.find_blocks_with_payload_types_batch(block_ids, payload_types)
+            .await?
+            .into_items()
+    }
+
+    /// Find all blocks by provided block IDs with one of the payload types, like
+    /// [`Client::find_blocks_with_payload_types()`], but return the successful blocks together with the errors of the
+    /// failed requests, like [`Client::find_blocks_batch()`].
+    pub async fn find_blocks_with_payload_types_batch(
+        &self,
+        block_ids: &[BlockId],
+        payload_types: &[PayloadType],
+    ) -> Result<BatchResponse<Block>> {
+        let mut blocks = self.find_blocks_batch(block_ids).await?;
+
+        if !payload_types.is_empty() {
+            blocks
+                .items
+                .retain(|(_, block)| payload_types.contains(&PayloadType::of(block.payload())));
+        }
+
         Ok(blocks)
     }
 
+    /// Returns the milestone that referenced the block with the provided block id, which contains the timestamp of
+    /// the confirmation. Errors with [`Error::NotConfirmed`] if the block isn't referenced by a milestone yet.
+    pub async fn get_confirming_milestone(&self, block_id: &BlockId) -> Result<MilestonePayload> {
+        let block_metadata = self.get_block_metadata(block_id).await?;
+
+        match block_metadata.referenced_by_milestone_index {
+            Some(milestone_index) => self.get_milestone_by_index(milestone_index).await,
+            None => Err(Error::NotConfirmed(block_id.to_string())),
+        }
+    }
+
+    /// Returns the block that included the transaction which created the output. Errors with [`Error::NotFound`] if
+    /// the node doesn't know the output and with [`Error::Pruned`] if the output was booked at or below the pruning
+    /// index of the node.
+    pub async fn get_creating_block(&self, output_id: &OutputId) -> Result<Block> {
+        match self.get_included_block(output_id.transaction_id()).await {
+            Err(Error::NotFound(url)) => {
+                // Check if the output is unknown or only the block isn't available anymore
+                let milestone_index_booked = self.get_output_metadata(output_id).await?.milestone_index_booked;
+                let pruning_index = self.get_info().await?.node_info.status.pruning_index;
+
+                if milestone_index_booked <= pruning_index {
+                    Err(Error::Pruned(format!("block creating output {output_id}")))
+                } else {
+                    Err(Error::NotFound(url))
+                }
+            }
+            result => result,
+        }
+    }
+
     /// Retries (promotes or reattaches) a block for provided block id. Block should only be
     /// retried only if they are valid and haven't been confirmed for a while.
     pub async fn retry(&self, block_id: &BlockId) -> Result<(BlockId, Block)> {
+        self.retry_with_strategy(block_id, RetryStrategy::Automatic).await
+    }
+
+    /// Retries a block for provided block id like [`Client::retry()`], but only takes the actions allowed by the
+    /// [`RetryStrategy`].
+    pub async fn retry_with_strategy(&self, block_id: &BlockId, strategy: RetryStrategy) -> Result<(BlockId, Block)> {
         // Get the metadata to check if it needs to promote or reattach
         let block_metadata = self.get_block_metadata(block_id).await?;
-        if block_metadata.should_promote.unwrap_or(false) {
-            self.promote_unchecked(block_id).await
-        } else if block_metadata.should_reattach.unwrap_or(false) {
-            self.reattach_unchecked(block_id).await
-        } else {
-            Err(Error::NoNeedPromoteOrReattach(block_id.to_string()))
+        match strategy.action(
+            &block_id.to_string(),
+            block_metadata.should_promote.unwrap_or(false),
+            block_metadata.should_reattach.unwrap_or(false),
+        )? {
+            Some(RetryAction::Promote) => self.promote_unchecked(block_id).await,
+            Some(RetryAction::Reattach) => self.reattach_unchecked(block_id).await,
+            None => Err(Error::NoNeedPromoteOrReattach(block_id.to_string())),
         }
     }
 
@@ -110,6 +395,19 @@ impl Client {
         block_id: &BlockId,
         interval: Option<u64>,
         max_attempts: Option<u64>,
+    ) -> Result<Vec<(BlockId, Block)>> {
+        self.retry_until_included_with_strategy(block_id, interval, max_attempts, RetryStrategy::Automatic)
+            .await
+    }
+
+    /// Retries a block for provided block id until it's included like [`Client::retry_until_included()`], but only
+    /// takes the actions allowed by the [`RetryStrategy`].
+    pub async fn retry_until_included_with_strategy(
+        &self,
+        block_id: &BlockId,
+        interval: Option<u64>,
+        max_attempts: Option<u64>,
+        strategy: RetryStrategy,
     ) -> Result<Vec<(BlockId, Block)>> {
         log::debug!("[retry_until_included]");
         // Attachments of the Block to check inclusion state
@@ -139,6 +437,7 @@ impl Client {
                 if let Some(inclusion_state) = block_metadata.ledger_inclusion_state {
                     match inclusion_state {
                         LedgerInclusionStateDto::Included | LedgerInclusionStateDto::NoTransaction => {
+                            self.release_pending_block(block_id);
                             // if original block, request it so we can return it on first position
                             if block_id == block_id_ {
                                 let mut included_and_reattached_blocks =
@@ -158,14 +457,22 @@ impl Client {
                 }
                 // Only reattach or promote latest attachment of the block
                 if index == block_ids_len - 1 {
-                    if block_metadata.should_promote.unwrap_or(false) {
-                        // Safe to unwrap since we iterate over it
-                        self.promote_unchecked(block_ids.last().unwrap()).await?;
-                    } else if block_metadata.should_reattach.unwrap_or(false) {
-                        // Safe to unwrap since we iterate over it
-                        let reattached = self.reattach_unchecked(block_ids.last().unwrap()).await?;
-                        block_ids.push(reattached.0);
-                        blocks_with_id.push(reattached);
+                    match strategy.action(
+                        &block_id_.to_string(),
+                        block_metadata.should_promote.unwrap_or(false),
+                        block_metadata.should_reattach.unwrap_or(false),
+                    )? {
+                        Some(RetryAction::Promote) => {
+                            // Safe to unwrap since we iterate over it
+                            self.promote_unchecked(block_ids.last().unwrap()).await?;
+                        }
+                        Some(RetryAction::Reattach) => {
+                            // Safe to unwrap since we iterate over it
+                            let reattached = self.reattach_unchecked(block_ids.last().unwrap()).await?;
+                            block_ids.push(reattached.0);
+                            blocks_with_id.push(reattached);
+                        }
+                        None => {}
                     }
                 }
             }
@@ -175,6 +482,7 @@ impl Client {
                 let block = self.get_block(block_id).await?;
                 if let Some(Payload::Transaction(transaction_payload)) = block.payload() {
                     let included_block = self.get_included_block(&transaction_payload.id()).await?;
+                    self.release_pending_block(block_id);
                     let mut included_and_reattached_blocks = vec![(included_block.id(), included_block)];
                     included_and_reattached_blocks.extend(blocks_with_id);
                     return Ok(included_and_reattached_blocks);
@@ -245,11 +553,25 @@ impl Client {
     }
 
     /// Find all outputs based on the requests criteria. This method will try to query multiple nodes if
-    /// the request amount exceeds individual node limit.
+    /// the request amount exceeds individual node limit. Outputs that are found by their output ID and via one of the
+    /// addresses are only returned once, use [`Client::find_outputs_with_duplicates()`] to keep them.
     pub async fn find_outputs(
         &self,
         output_ids: &[OutputId],
         addresses: &[String],
+    ) -> Result<Vec<OutputWithMetadataResponse>> {
+        let mut output_responses = self.find_outputs_with_duplicates(output_ids, addresses).await?;
+        dedup_output_responses(&mut output_responses);
+
+        Ok(output_responses)
+    }
+
+    /// Find all outputs based on the requests criteria, like [`Client::find_outputs()`], but without removing outputs
+    /// which are returned for multiple criteria.
+    pub async fn find_outputs_with_duplicates(
+        &self,
+        output_ids: &[OutputId],
+        addresses: &[String],
     ) -> Result<Vec<OutputWithMetadataResponse>> {
         let mut output_responses = self.get_outputs(output_ids.to_vec()).await?;
 
@@ -269,7 +591,38 @@ impl Client {
             output_responses.extend(self.get_outputs(output_ids_response.items).await?);
         }
 
-        Ok(output_responses.clone())
+        Ok(output_responses)
+    }
+
+    /// Find all outputs based on the requests criteria, like [`Client::find_outputs()`], but only return outputs with
+    /// an amount within `[min_amount, max_amount]`. The indexer can't filter by amount, so this is done client-side.
+    pub async fn find_outputs_in_amount_range(
+        &self,
+        output_ids: &[OutputId],
+        addresses: &[String],
+        min_amount: Option<u64>,
+        max_amount: Option<u64>,
+    ) -> Result<Vec<OutputWithMetadataResponse>> {
+        let output_responses = self.find_outputs(output_ids, addresses).await?;
+
+        filter_output_responses_by_amount(
+            output_responses,
+            &(min_amount.unwrap_or(0)..=max_amount.unwrap_or(u64::MAX)),
+        )
+    }
+
+    /// Find all outputs based on the requests criteria, like [`Client::find_outputs()`], grouped by the milestone that
+    /// booked them in chronological order. Only the groups of the latest `max_milestones` milestones are returned. The
+    /// milestone index and timestamp are taken from the output metadata, so no milestones are requested.
+    pub async fn find_outputs_grouped_by_milestone(
+        &self,
+        output_ids: &[OutputId],
+        addresses: &[String],
+        max_milestones: usize,
+    ) -> Result<Vec<MilestoneOutputs>> {
+        let output_responses = self.find_outputs(output_ids, addresses).await?;
+
+        Ok(group_output_responses_by_milestone(output_responses, max_milestones))
     }
 
     /// Reattaches blocks for provided block id. Blocks can be reattached only if they are valid and haven't been
@@ -290,7 +643,12 @@ impl Client {
         let reattach_block = self.finish_block_builder(None, block.payload().cloned()).await?;
 
         // Post the modified
-        let block_id = self.post_block_raw(&reattach_block).await?;
+        let reattached_block_id = self.post_block_raw(&reattach_block).await?;
+        // The reserved inputs of the original block get released by any of its attachments
+        if let Some(output_reservations) = &self.output_reservations {
+            output_reservations.add_attachment(block_id, reattached_block_id);
+        }
+        let block_id = reattached_block_id;
         // Get block if we use remote Pow, because the node will change parents and nonce
         let block = if self.get_local_pow() {
             reattach_block
@@ -340,9 +698,7 @@ impl Client {
 
         if let Some(latest_ms_timestamp) = network_info.latest_milestone_timestamp {
             // Check the local time is in the range of +-5 minutes of the node to prevent locking funds by accident
-            if !(latest_ms_timestamp - FIVE_MINUTES_IN_SECONDS..latest_ms_timestamp + FIVE_MINUTES_IN_SECONDS)
-                .contains(&current_time)
-            {
+            if !is_time_synced(current_time, latest_ms_timestamp) {
                 return Err(Error::TimeNotSynced {
                     current_time,
                     milestone_timestamp: latest_ms_timestamp,
@@ -352,4 +708,239 @@ impl Client {
 
         Ok(current_time)
     }
+
+    /// Returns the index and timestamp of the latest confirmed milestone of the node, together with the local time
+    /// and if it differs more than 5 minutes from the milestone timestamp.
+    pub async fn get_latest_milestone_timestamp(&self) -> Result<MilestoneTimestamp> {
+        let confirmed_milestone = self.get_info().await?.node_info.status.confirmed_milestone;
+
+        let milestone_timestamp = match confirmed_milestone.timestamp {
+            Some(timestamp) => timestamp,
+            None => self
+                .get_milestone_by_index(confirmed_milestone.index)
+                .await?
+                .essence()
+                .timestamp(),
+        };
+        let local_time = unix_timestamp_now();
+
+        Ok(MilestoneTimestamp {
+            milestone_index: confirmed_milestone.index,
+            milestone_timestamp,
+            local_time,
+            clock_skew: !is_time_synced(local_time, milestone_timestamp),
+        })
+    }
+}
+
+/// The ledger updates of an address as returned by the explorer plugin, only their output IDs are needed.
+#[derive(Debug, Serialize, Deserialize)]
+struct LedgerUpdatesResponse {
+    items: Vec<LedgerUpdate>,
+}
+
+/// The creation or spending of an output as returned by the explorer plugin.
+#[derive(Debug, Serialize, Deserialize)]
+struct LedgerUpdate {
+    #[serde(rename = "outputId")]
+    output_id: OutputId,
+}
+
+// Checks if the local time is in the range of +-5 minutes of the milestone timestamp.
+fn is_time_synced(local_time: u32, milestone_timestamp: u32) -> bool {
+    (milestone_timestamp - FIVE_MINUTES_IN_SECONDS..milestone_timestamp + FIVE_MINUTES_IN_SECONDS).contains(&local_time)
+}
+
+// Removes output responses with an already seen output ID, keeping the first occurrence.
+fn dedup_output_responses(output_responses: &mut Vec<OutputWithMetadataResponse>) {
+    let mut seen_output_ids = HashSet::new();
+    output_responses.retain(|output_response| {
+        seen_output_ids.insert((
+            output_response.metadata.transaction_id.clone(),
+            output_response.metadata.output_index,
+        ))
+    });
+}
+
+// Only keeps output responses with an amount within the given range.
+pub(crate) fn filter_output_responses_by_amount(
+    output_responses: Vec<OutputWithMetadataResponse>,
+    amount_range: &RangeInclusive<u64>,
+) -> Result<Vec<OutputWithMetadataResponse>> {
+    let mut filtered_output_responses = Vec::new();
+
+    for output_response in output_responses {
+        let amount = match &output_response.output {
+            OutputDto::Treasury(output) => &output.amount,
+            OutputDto::Basic(output) => &output.amount,
+            OutputDto::Alias(output) => &output.amount,
+            OutputDto::Foundry(output) => &output.amount,
+            OutputDto::Nft(output) => &output.amount,
+        };
+        let amount = amount
+            .parse::<u64>()
+            .map_err(|_| Error::InvalidAmount(amount.clone()))?;
+
+        if amount_range.contains(&amount) {
+            filtered_output_responses.push(output_response);
+        }
+    }
+
+    Ok(filtered_output_responses)
+}
+
+// Groups the output responses by the milestone that booked them in chronological order, keeping only the groups of
+// the latest `max_milestones` milestones.
+pub(crate) fn group_output_responses_by_milestone(
+    output_responses: Vec<OutputWithMetadataResponse>,
+    max_milestones: usize,
+) -> Vec<MilestoneOutputs> {
+    let mut milestones = BTreeMap::new();
+
+    for output_response in output_responses {
+        milestones
+            .entry(output_response.metadata.milestone_index_booked)
+            .or_insert_with(|| MilestoneOutputs {
+                milestone_index: output_response.metadata.milestone_index_booked,
+                milestone_timestamp: output_response.metadata.milestone_timestamp_booked,
+                outputs: Vec::new(),
+            })
+            .outputs
+            .push(output_response);
+    }
+
+    let skipped_milestones = milestones.len().saturating_sub(max_milestones);
+
+    milestones.into_values().skip(skipped_milestones).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output_response(transaction_id: &str, output_index: u16) -> OutputWithMetadataResponse {
+        output_response_with_amount(transaction_id, output_index, 1_000_000)
+    }
+
+    fn output_response_with_amount(transaction_id: &str, output_index: u16, amount: u64) -> OutputWithMetadataResponse {
+        booked_output_response(transaction_id, output_index, amount, 1)
+    }
+
+    fn booked_output_response(
+        transaction_id: &str,
+        output_index: u16,
+        amount: u64,
+        milestone_index: u32,
+    ) -> OutputWithMetadataResponse {
+        serde_json::from_value(serde_json::json!({
+            "metadata": {
+                "blockId": "0xb00b5f6e6e6e3e3e4b1b6c2d3d9e2c5b2a5f55c94a4e5f22c1e4d1d1e2d1f1a0",
+                "transactionId": transaction_id,
+                "outputIndex": output_index,
+                "isSpent": false,
+                "milestoneIndexBooked": milestone_index,
+                "milestoneTimestampBooked": 1_680_000_000 + milestone_index * 5,
+                "ledgerIndex": 1
+            },
+            "output": {
+                "type": 2,
+                "amount": amount.to_string()
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn dedup_overlapping_outputs() {
+        let transaction_id_0 = "0x0000000000000000000000000000000000000000000000000000000000000000";
+        let transaction_id_1 = "0x1111111111111111111111111111111111111111111111111111111111111111";
+
+        // The first output is requested by its ID and also found via an address
+        let mut output_responses = vec![
+            output_response(transaction_id_0, 0),
+            output_response(transaction_id_1, 0),
+            output_response(transaction_id_0, 0),
+            output_response(transaction_id_0, 1),
+        ];
+        dedup_output_responses(&mut output_responses);
+
+        let output_ids = output_responses
+            .iter()
+            .map(|output_response| {
+                (
+                    output_response.metadata.transaction_id.as_str(),
+                    output_response.metadata.output_index,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            output_ids,
+            vec![(transaction_id_0, 0), (transaction_id_1, 0), (transaction_id_0, 1)]
+        );
+    }
+
+    #[test]
+    fn filter_outputs_by_amount() {
+        let transaction_id = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+        let output_responses = vec![
+            output_response_with_amount(transaction_id, 0, 500),
+            output_response_with_amount(transaction_id, 1, 1_000),
+            output_response_with_amount(transaction_id, 2, 5_000),
+            output_response_with_amount(transaction_id, 3, 10_000),
+            output_response_with_amount(transaction_id, 4, 1_000_000),
+        ];
+        let filtered = filter_output_responses_by_amount(output_responses, &(1_000..=10_000)).unwrap();
+
+        let output_indexes = filtered
+            .iter()
+            .map(|output_response| output_response.metadata.output_index)
+            .collect::<Vec<_>>();
+        assert_eq!(output_indexes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn group_outputs_by_milestone() {
+        let transaction_id = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+        let output_responses = vec![
+            booked_output_response(transaction_id, 0, 1_000, 12),
+            booked_output_response(transaction_id, 1, 2_000, 10),
+            booked_output_response(transaction_id, 2, 3_000, 12),
+            booked_output_response(transaction_id, 3, 4_000, 11),
+            booked_output_response(transaction_id, 4, 5_000, 10),
+        ];
+
+        let grouped = group_output_responses_by_milestone(output_responses.clone(), 10);
+        let groups = grouped
+            .iter()
+            .map(|milestone_outputs| {
+                (
+                    milestone_outputs.milestone_index,
+                    milestone_outputs.milestone_timestamp,
+                    milestone_outputs
+                        .outputs
+                        .iter()
+                        .map(|output_response| output_response.metadata.output_index)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                (10, 1_680_000_050, vec![1, 4]),
+                (11, 1_680_000_055, vec![3]),
+                (12, 1_680_000_060, vec![0, 2])
+            ]
+        );
+
+        // Only the latest milestones are kept
+        let grouped = group_output_responses_by_milestone(output_responses, 2);
+        let milestone_indexes = grouped
+            .iter()
+            .map(|milestone_outputs| milestone_outputs.milestone_index)
+            .collect::<Vec<_>>();
+        assert_eq!(milestone_indexes, vec![11, 12]);
+    }
 }
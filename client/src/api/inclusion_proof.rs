@@ -0,0 +1,356 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+use iota_types::{
+    api::core::dto::LedgerInclusionStateDto,
+    block::{
+        payload::{dto::MilestonePayloadDto, milestone::MilestonePayload, transaction::TransactionId, Payload},
+        protocol::ProtocolParameters,
+        Block, BlockDto, BlockId, DtoError,
+    },
+};
+
+use crate::{Client, Error, Result};
+
+// Domain separation of the inclusion merkle tree of milestones, like in RFC 6962.
+const LEAF_HASH_PREFIX: u8 = 0;
+const NODE_HASH_PREFIX: u8 = 1;
+
+/// The hash of a sibling subtree on the path from a block to the inclusion merkle root of a milestone.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditPathHash {
+    /// The hash of the sibling subtree.
+    pub hash: [u8; 32],
+    /// Whether the sibling subtree is on the left.
+    pub is_left: bool,
+}
+
+/// A proof that a transaction was included in the ledger by a milestone, which can be checked without trusting the
+/// node with [`TransactionInclusionProof::verify()`]. The signatures of the milestone still need to be checked against
+/// the known milestone public keys.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionInclusionProof {
+    /// The milestone that included the transaction.
+    pub milestone: MilestonePayload,
+    /// The block containing the transaction.
+    pub block: Block,
+    /// The sibling hashes from the block up to the inclusion merkle root of the milestone.
+    pub audit_path: Vec<AuditPathHash>,
+}
+
+impl TransactionInclusionProof {
+    /// Returns whether the block contains the transaction and the audit path leads from the block to the inclusion
+    /// merkle root of the milestone.
+    pub fn verify(&self, transaction_id: &TransactionId) -> bool {
+        let contains_transaction =
+            matches!(self.block.payload(), Some(Payload::Transaction(payload)) if payload.id() == *transaction_id);
+
+        contains_transaction
+            && audit_path_root(&self.block.id(), &self.audit_path) == **self.milestone.essence().inclusion_merkle_root()
+    }
+}
+
+/// An [`AuditPathHash`] as returned by the message interface.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AuditPathHashDto {
+    /// The hex encoded hash of the sibling subtree.
+    pub hash: String,
+    /// Whether the sibling subtree is on the left.
+    #[serde(rename = "isLeft")]
+    pub is_left: bool,
+}
+
+impl From<&AuditPathHash> for AuditPathHashDto {
+    fn from(value: &AuditPathHash) -> Self {
+        Self {
+            hash: prefix_hex::encode(value.hash),
+            is_left: value.is_left,
+        }
+    }
+}
+
+/// A [`TransactionInclusionProof`] as returned by the message interface.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionInclusionProofDto {
+    /// The milestone that included the transaction.
+    pub milestone: MilestonePayloadDto,
+    /// The block containing the transaction.
+    pub block: BlockDto,
+    /// The sibling hashes from the block up to the inclusion merkle root of the milestone.
+    #[serde(rename = "auditPath")]
+    pub audit_path: Vec<AuditPathHashDto>,
+}
+
+impl From<&TransactionInclusionProof> for TransactionInclusionProofDto {
+    fn from(value: &TransactionInclusionProof) -> Self {
+        Self {
+            milestone: MilestonePayloadDto::from(&value.milestone),
+            block: BlockDto::from(&value.block),
+            audit_path: value.audit_path.iter().map(AuditPathHashDto::from).collect(),
+        }
+    }
+}
+
+impl TransactionInclusionProof {
+    /// Conversion from [`TransactionInclusionProofDto`] to [`TransactionInclusionProof`].
+    pub fn try_from_dto(
+        value: &TransactionInclusionProofDto,
+        protocol_parameters: &ProtocolParameters,
+    ) -> Result<Self, DtoError> {
+        Ok(Self {
+            milestone: MilestonePayload::try_from_dto(&value.milestone, protocol_parameters)?,
+            block: Block::try_from_dto(&value.block, protocol_parameters)?,
+            audit_path: value
+                .audit_path
+                .iter()
+                .map(|audit_path_hash| {
+                    Ok(AuditPathHash {
+                        hash: prefix_hex::decode(&audit_path_hash.hash)
+                            .map_err(|_| DtoError::InvalidField("auditPath"))?,
+                        is_left: audit_path_hash.is_left,
+                    })
+                })
+                .collect::<Result<_, DtoError>>()?,
+        })
+    }
+}
+
+impl Client {
+    /// Returns a proof that the transaction was included in the ledger. The audit path is computed from the blocks
+    /// included by the milestone in white flag order, so this requests the metadata of every block it referenced.
+    pub async fn get_transaction_inclusion_proof(
+        &self,
+        transaction_id: &TransactionId,
+    ) -> Result<TransactionInclusionProof> {
+        let block = self.get_included_block(transaction_id).await?;
+        let block_id = block.id();
+        let milestone_index = self
+            .get_block_metadata(&block_id)
+            .await?
+            .referenced_by_milestone_index
+            .ok_or_else(|| Error::NotConfirmed(block_id.to_string()))?;
+
+        let mut included_blocks = self
+            .get_blocks_referenced_by_milestone(milestone_index)
+            .await?
+            .into_iter()
+            .filter(|metadata| metadata.ledger_inclusion_state == Some(LedgerInclusionStateDto::Included))
+            .collect::<Vec<_>>();
+        included_blocks.sort_by_key(|metadata| metadata.white_flag_index);
+
+        let block_ids = included_blocks
+            .iter()
+            .map(|metadata| BlockId::from_str(&metadata.block_id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let index = block_ids
+            .iter()
+            .position(|id| *id == block_id)
+            .ok_or(Error::UnexpectedApiResponse)?;
+
+        Ok(TransactionInclusionProof {
+            milestone: self.get_milestone_by_index(milestone_index).await?,
+            block,
+            audit_path: audit_path(&block_ids, index),
+        })
+    }
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Blake2b256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut hash = [0; 32];
+    hash.copy_from_slice(&hasher.finalize());
+    hash
+}
+
+fn leaf_hash(block_id: &BlockId) -> [u8; 32] {
+    hash(&[&[LEAF_HASH_PREFIX], block_id.as_ref()])
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hash(&[&[NODE_HASH_PREFIX], left, right])
+}
+
+// The largest power of two below `len`, where the tree of `len` > 1 leaves is split into its subtrees.
+fn split_index(len: usize) -> usize {
+    1 << (usize::BITS - 1 - (len - 1).leading_zeros())
+}
+
+fn merkle_root(block_ids: &[BlockId]) -> [u8; 32] {
+    match block_ids {
+        [] => hash(&[]),
+        [block_id] => leaf_hash(block_id),
+        _ => {
+            let (left, right) = block_ids.split_at(split_index(block_ids.len()));
+            node_hash(&merkle_root(left), &merkle_root(right))
+        }
+    }
+}
+
+// The sibling hashes from the leaf at `index` up to the root.
+fn audit_path(block_ids: &[BlockId], index: usize) -> Vec<AuditPathHash> {
+    if block_ids.len() < 2 {
+        return Vec::new();
+    }
+
+    let split = split_index(block_ids.len());
+    let (left, right) = block_ids.split_at(split);
+    let (mut path, hash, is_left) = if index < split {
+        (audit_path(left, index), merkle_root(right), false)
+    } else {
+        (audit_path(right, index - split), merkle_root(left), true)
+    };
+    path.push(AuditPathHash { hash, is_left });
+
+    path
+}
+
+fn audit_path_root(block_id: &BlockId, audit_path: &[AuditPathHash]) -> [u8; 32] {
+    audit_path.iter().fold(leaf_hash(block_id), |hash, sibling| {
+        if sibling.is_left {
+            node_hash(&sibling.hash, &hash)
+        } else {
+            node_hash(&hash, &sibling.hash)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::block::{
+        input::Input,
+        output::Output,
+        payload::{
+            milestone::{MerkleRoot, MilestoneEssence, MilestoneOptions},
+            transaction::{RegularTransactionEssence, TransactionEssence, TransactionPayload},
+        },
+        protocol::protocol_parameters,
+        rand::{
+            block::rand_block_ids,
+            bytes::rand_bytes_array,
+            input::rand_utxo_input,
+            milestone::{rand_merkle_root, rand_milestone_id},
+            output::{rand_basic_output, rand_inputs_commitment},
+            parents::rand_parents,
+        },
+        signature::{Ed25519Signature, Signature},
+        unlock::{SignatureUnlock, Unlock, Unlocks},
+    };
+
+    use super::*;
+
+    fn transaction_block() -> Block {
+        let protocol_parameters = protocol_parameters();
+        let essence = RegularTransactionEssence::builder(protocol_parameters.network_id(), rand_inputs_commitment())
+            .with_inputs(vec![Input::Utxo(rand_utxo_input())])
+            .add_output(Output::Basic(rand_basic_output(protocol_parameters.token_supply())))
+            .finish(&protocol_parameters)
+            .unwrap();
+        let unlocks = Unlocks::new(vec![Unlock::Signature(SignatureUnlock::from(Signature::Ed25519(
+            Ed25519Signature::new(rand_bytes_array(), rand_bytes_array()),
+        )))])
+        .unwrap();
+        let payload = TransactionPayload::new(TransactionEssence::Regular(essence), unlocks).unwrap();
+
+        Block::build(rand_parents())
+            .with_payload(Payload::from(payload))
+            .finish()
+            .unwrap()
+    }
+
+    fn proof(block: Block, block_ids: &[BlockId], index: usize) -> TransactionInclusionProof {
+        let essence = MilestoneEssence::new(
+            10.into(),
+            0,
+            2,
+            rand_milestone_id(),
+            rand_parents(),
+            MerkleRoot::from(merkle_root(block_ids)),
+            rand_merkle_root(),
+            Vec::new(),
+            MilestoneOptions::new(vec![]).unwrap(),
+        )
+        .unwrap();
+        let signatures = vec![Signature::from(Ed25519Signature::new(
+            rand_bytes_array(),
+            rand_bytes_array(),
+        ))];
+
+        TransactionInclusionProof {
+            milestone: MilestonePayload::new(essence, signatures).unwrap(),
+            block,
+            audit_path: audit_path(block_ids, index),
+        }
+    }
+
+    #[test]
+    fn audit_paths_lead_to_root() {
+        for len in 1..=9 {
+            let block_ids = rand_block_ids(len);
+            let root = merkle_root(&block_ids);
+
+            for (index, block_id) in block_ids.iter().enumerate() {
+                assert_eq!(audit_path_root(block_id, &audit_path(&block_ids, index)), root);
+            }
+        }
+    }
+
+    #[test]
+    fn valid_proof() {
+        let block = transaction_block();
+        let mut block_ids = rand_block_ids(6);
+        block_ids.insert(3, block.id());
+        let transaction_id = match block.payload() {
+            Some(Payload::Transaction(payload)) => payload.id(),
+            _ => unreachable!(),
+        };
+
+        assert!(proof(block, &block_ids, 3).verify(&transaction_id));
+    }
+
+    #[test]
+    fn tampered_proof() {
+        let block = transaction_block();
+        let mut block_ids = rand_block_ids(6);
+        block_ids.insert(3, block.id());
+        let transaction_id = match block.payload() {
+            Some(Payload::Transaction(payload)) => payload.id(),
+            _ => unreachable!(),
+        };
+
+        let mut tampered_hash = proof(block.clone(), &block_ids, 3);
+        tampered_hash.audit_path[1].hash[0] ^= 1;
+        assert!(!tampered_hash.verify(&transaction_id));
+
+        let mut tampered_side = proof(block.clone(), &block_ids, 3);
+        tampered_side.audit_path[0].is_left = !tampered_side.audit_path[0].is_left;
+        assert!(!tampered_side.verify(&transaction_id));
+
+        let other_block = proof(transaction_block(), &block_ids, 3);
+        assert!(!other_block.verify(&transaction_id));
+
+        assert!(!proof(block, &block_ids, 3).verify(&TransactionId::new([0; 32])));
+    }
+
+    #[test]
+    fn proof_dto_roundtrip() {
+        let block_ids = rand_block_ids(6);
+        let proof = proof(transaction_block(), &block_ids, 3);
+
+        let json = serde_json::to_value(TransactionInclusionProofDto::from(&proof)).unwrap();
+        assert_eq!(json["auditPath"].as_array().unwrap().len(), proof.audit_path.len());
+        assert_eq!(json["auditPath"][0]["isLeft"], proof.audit_path[0].is_left);
+
+        let dto = serde_json::from_value::<TransactionInclusionProofDto>(json).unwrap();
+        assert_eq!(
+            TransactionInclusionProof::try_from_dto(&dto, &protocol_parameters()).unwrap(),
+            proof
+        );
+    }
+}
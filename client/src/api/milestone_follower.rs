@@ -0,0 +1,220 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use futures::Future;
+use iota_types::api::core::response::UtxoChangesResponse;
+
+#[cfg(all(feature = "mqtt", not(target_family = "wasm")))]
+use crate::node_api::mqtt::{MqttEvent, MqttPayload, Topic};
+use crate::{Client, Result};
+
+impl Client {
+    /// Returns a [`MilestoneFollower`] that delivers the UTXO changes of confirmed milestones, starting at
+    /// `from_index`.
+    pub fn milestone_follower(&self, from_index: u32) -> MilestoneFollower<'_> {
+        MilestoneFollower {
+            client: self,
+            checkpoint: from_index,
+        }
+    }
+}
+
+/// Follows confirmed milestones in order and delivers their UTXO changes.
+///
+/// The checkpoint is the index of the next milestone to deliver, so after a restart following can be resumed from it
+/// without skipping or duplicating milestones.
+pub struct MilestoneFollower<'a> {
+    client: &'a Client,
+    checkpoint: u32,
+}
+
+impl<'a> MilestoneFollower<'a> {
+    /// Returns the index of the next milestone that will be delivered.
+    pub fn checkpoint(&self) -> u32 {
+        self.checkpoint
+    }
+
+    /// Delivers the UTXO changes of all milestones from the checkpoint up to the latest confirmed milestone and
+    /// returns the new checkpoint. If a request fails, the checkpoint stays at the milestone that couldn't be fetched.
+    pub async fn sync<C>(&mut self, callback: C) -> Result<u32>
+    where
+        C: FnMut(UtxoChangesResponse) + Send,
+    {
+        let client = self.client;
+        let confirmed_index = client.get_info().await?.node_info.status.confirmed_milestone.index;
+
+        deliver_milestones(
+            &mut self.checkpoint,
+            confirmed_index,
+            |index| client.get_utxo_changes_by_index(index),
+            callback,
+        )
+        .await?;
+
+        Ok(self.checkpoint)
+    }
+
+    /// Delivers the UTXO changes of newly confirmed milestones until an error occurs. With the `mqtt` feature the
+    /// confirmed milestone topic is used and the node is only polled in the provided interval while the MQTT
+    /// connection is down, otherwise or if subscribing fails the node is polled in the interval.
+    pub async fn follow<C>(&mut self, interval: Duration, mut callback: C) -> Result<()>
+    where
+        C: FnMut(UtxoChangesResponse) + Send,
+    {
+        #[cfg(all(feature = "mqtt", not(target_family = "wasm")))]
+        {
+            let topic = Topic::try_new("milestone-info/confirmed")?;
+            let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+            let subscribed = self
+                .client
+                .subscribe_handler(vec![topic.clone()], move |event| {
+                    if let MqttPayload::Json(value) = &event.payload {
+                        if let Some(index) = value.get("index").and_then(serde_json::Value::as_u64) {
+                            let _ = sender.send(index as u32);
+                        }
+                    }
+                })
+                .await;
+
+            match subscribed {
+                Ok(handler) => {
+                    let result = self.follow_mqtt(interval, &mut callback, &mut receiver).await;
+                    self.client.unsubscribe_handler(vec![topic], &handler).await?;
+                    return result;
+                }
+                Err(e) => log::warn!("Subscribing to confirmed milestones failed, polling the node instead: {e}"),
+            }
+        }
+
+        loop {
+            self.sync(&mut callback).await?;
+
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(interval.as_millis().try_into().unwrap()).await;
+
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    // Delivers the milestones announced on the confirmed milestone topic, the node is only polled while the MQTT
+    // connection is down, so no milestone confirmed in the meantime is missed.
+    #[cfg(all(feature = "mqtt", not(target_family = "wasm")))]
+    async fn follow_mqtt<C>(
+        &mut self,
+        interval: Duration,
+        callback: &mut C,
+        receiver: &mut tokio::sync::mpsc::UnboundedReceiver<u32>,
+    ) -> Result<()>
+    where
+        C: FnMut(UtxoChangesResponse) + Send,
+    {
+        let client = self.client;
+        let mut mqtt_events = client.mqtt_event_receiver();
+
+        // Milestones confirmed before subscribing aren't announced
+        self.sync(&mut *callback).await?;
+
+        loop {
+            let connected = *mqtt_events.borrow_and_update() == MqttEvent::Connected;
+
+            if connected {
+                tokio::select! {
+                    Some(confirmed_index) = receiver.recv() => {
+                        deliver_milestones(
+                            &mut self.checkpoint,
+                            confirmed_index,
+                            |index| client.get_utxo_changes_by_index(index),
+                            &mut *callback,
+                        )
+                        .await?;
+                    }
+                    // Poll if the connection is lost
+                    _ = mqtt_events.changed() => {}
+                }
+            } else {
+                tokio::time::sleep(interval).await;
+                self.sync(&mut *callback).await?;
+            }
+        }
+    }
+}
+
+// Fetches and delivers the items from `checkpoint` up to and including `confirmed_index` in order, the checkpoint is
+// only advanced after an item got delivered.
+#[allow(clippy::future_not_send)]
+async fn deliver_milestones<T, F, Fut, C>(
+    checkpoint: &mut u32,
+    confirmed_index: u32,
+    mut fetch: F,
+    mut callback: C,
+) -> Result<()>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T>>,
+    C: FnMut(T),
+{
+    while *checkpoint <= confirmed_index {
+        let item = fetch(*checkpoint).await?;
+        callback(item);
+        *checkpoint += 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[tokio::test]
+    async fn resume_after_restart() {
+        let mut delivered = Vec::new();
+
+        // The connection drops while fetching milestone 4
+        let mut checkpoint = 1;
+        let result = deliver_milestones(
+            &mut checkpoint,
+            5,
+            |index| async move {
+                if index == 4 {
+                    Err(Error::Node("connection closed".to_string()))
+                } else {
+                    Ok(index)
+                }
+            },
+            |index| delivered.push(index),
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(checkpoint, 4);
+
+        // Restart from the stored checkpoint, meanwhile more milestones got confirmed
+        let mut resumed_checkpoint = checkpoint;
+        deliver_milestones(
+            &mut resumed_checkpoint,
+            7,
+            |index| async move { Ok(index) },
+            |index| delivered.push(index),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resumed_checkpoint, 8);
+
+        // Nothing new confirmed
+        deliver_milestones(
+            &mut resumed_checkpoint,
+            7,
+            |index| async move { Ok(index) },
+            |index| delivered.push(index),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(delivered, (1..=7).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::RangeInclusive;
+
+use futures::{Future, StreamExt, TryStreamExt};
+use iota_types::{api::core::response::UtxoChangesResponse, block::payload::milestone::MilestonePayload};
+
+use crate::{constants::MAX_MILESTONE_RANGE, Client, Error, Result};
+
+impl Client {
+    /// Requests the milestones of a range of indexes, at most
+    /// [`milestone_fetch_concurrency`](crate::ClientBuilder::with_milestone_fetch_concurrency) at once. The milestones
+    /// are returned in the order of their index. A range can contain at most 1000 milestones.
+    pub async fn get_milestones(&self, indexes: RangeInclusive<u32>) -> Result<Vec<MilestonePayload>> {
+        fetch_milestone_range(indexes, self.milestone_fetch_concurrency, |index| {
+            self.get_milestone_by_index(index)
+        })
+        .await
+    }
+
+    /// Requests the UTXO changes of a range of milestone indexes, at most
+    /// [`milestone_fetch_concurrency`](crate::ClientBuilder::with_milestone_fetch_concurrency) at once. The changes
+    /// are returned in the order of the milestone index. A range can contain at most 1000 milestones.
+    pub async fn get_utxo_changes_by_index_range(
+        &self,
+        indexes: RangeInclusive<u32>,
+    ) -> Result<Vec<UtxoChangesResponse>> {
+        fetch_milestone_range(indexes, self.milestone_fetch_concurrency, |index| {
+            self.get_utxo_changes_by_index(index)
+        })
+        .await
+    }
+}
+
+// Fetches the items of the indexes in order, with at most `concurrency` requests in flight. Ranges with more than
+// `MAX_MILESTONE_RANGE` indexes are rejected, as all items are kept in memory.
+#[allow(clippy::future_not_send)]
+async fn fetch_milestone_range<T, F, Fut>(
+    indexes: RangeInclusive<u32>,
+    concurrency: usize,
+    mut fetch: F,
+) -> Result<Vec<T>>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let (start, end) = (*indexes.start(), *indexes.end());
+    if start <= end && end - start >= MAX_MILESTONE_RANGE {
+        return Err(Error::MilestoneRangeTooLarge {
+            start,
+            end,
+            max: MAX_MILESTONE_RANGE,
+        });
+    }
+
+    futures::stream::iter(indexes)
+        .map(fetch)
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn bounded_in_flight_requests() {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let items = fetch_milestone_range(1..=20, 3, |index| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(u64::from(20 - index))).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(index)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(items, (1..=20).collect::<Vec<_>>());
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn range_too_large() {
+        let fetch = |index| async move { Ok(index) };
+
+        assert_eq!(fetch_milestone_range(0..=999, 5, fetch).await.unwrap().len(), 1000);
+        assert!(matches!(
+            fetch_milestone_range(0..=u32::MAX, 5, fetch).await,
+            Err(Error::MilestoneRangeTooLarge { max: 1000, .. })
+        ));
+    }
+}
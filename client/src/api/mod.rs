@@ -4,11 +4,45 @@
 //! High level APIs
 
 mod address;
+mod address_total;
+mod balance_delta;
+mod batch;
 mod block_builder;
+mod block_watcher;
+mod confirmed_ancestor;
 mod consolidation;
+mod double_spend;
 mod high_level;
+mod inclusion_proof;
+mod milestone_follower;
+mod milestone_range;
+mod output_reservation;
+mod referenced_blocks;
+mod retry;
+mod split_transfer;
+mod supply;
 mod types;
+mod utxo_snapshot;
 
-pub use self::{address::*, block_builder::*, types::*};
+#[cfg(feature = "message_interface")]
+pub(crate) use self::high_level::{filter_output_responses_by_amount, group_output_responses_by_milestone};
+pub use self::{
+    address::*,
+    address_total::AddressTotal,
+    balance_delta::AddressBalanceDelta,
+    batch::{BatchErrorDto, BatchErrorMode, BatchItemDto, BatchResponse, BatchResponseDto},
+    block_builder::*,
+    block_watcher::{BlockMetadataWithState, BlockState},
+    consolidation::{AddressOutputs, ConsolidationCheckpoint},
+    double_spend::DoubleSpend,
+    inclusion_proof::{AuditPathHash, AuditPathHashDto, TransactionInclusionProof, TransactionInclusionProofDto},
+    milestone_follower::MilestoneFollower,
+    output_reservation::{PendingBlock, PendingState},
+    retry::RetryStrategy,
+    supply::Supply,
+    types::*,
+    utxo_snapshot::*,
+};
+pub(crate) use self::{batch::BatchResults, output_reservation::OutputReservations, retry::RetryAction};
 
 const ADDRESS_GAP_RANGE: u32 = 20;
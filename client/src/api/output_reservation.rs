@@ -0,0 +1,345 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use futures::lock::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
+use iota_types::block::{output::OutputId, BlockId};
+
+use crate::{Client, Error, Result};
+
+/// The transactions of a client that are sent, but not confirmed yet, to restore the reservations of their inputs
+/// after a restart.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingState {
+    /// The blocks with a transaction that await confirmation.
+    pub pending_blocks: Vec<PendingBlock>,
+}
+
+/// A sent block with a transaction that awaits confirmation.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingBlock {
+    /// The id of the block.
+    pub block_id: BlockId,
+    /// The inputs of the transaction, reserved until the block is confirmed.
+    pub reserved_outputs: Vec<OutputId>,
+}
+
+impl Client {
+    /// Returns the blocks sent by this client that await confirmation together with their reserved inputs, so they
+    /// can be persisted and imported with [`Client::import_pending_state()`] after a restart. Empty if output
+    /// reservation isn't enabled.
+    pub fn export_pending_state(&self) -> PendingState {
+        PendingState {
+            pending_blocks: self
+                .output_reservations
+                .as_ref()
+                .map(OutputReservations::pending_blocks)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Restores the reservations of an exported [`PendingState`], so the inputs of the pending blocks don't get
+    /// selected again. The reservations get released by [`Client::check_pending_blocks()`] or
+    /// [`Client::retry_until_included()`] once the blocks are included or conflicting.
+    pub fn import_pending_state(&self, state: PendingState) -> Result<()> {
+        self.output_reservations
+            .as_ref()
+            .ok_or(Error::OutputReservationDisabled)?
+            .restore(state.pending_blocks);
+
+        Ok(())
+    }
+
+    /// Checks the pending blocks of this client and their reattachments once and releases the reservations of the ones
+    /// that are included or conflicting. Blocks that the node doesn't know, like pruned ones, or that are orphaned and
+    /// should be reattached are released too, if no attachment is still pending. Returns the blocks that are still
+    /// pending.
+    pub async fn check_pending_blocks(&self) -> Result<Vec<BlockId>> {
+        let output_reservations = match &self.output_reservations {
+            Some(output_reservations) => output_reservations,
+            None => return Ok(Vec::new()),
+        };
+        let mut pending = Vec::new();
+
+        for PendingBlock { block_id, .. } in output_reservations.pending_blocks() {
+            let mut still_pending = false;
+            let mut inclusion_state = false;
+
+            for attachment in output_reservations.attachments(&block_id) {
+                match self.get_block_metadata(&attachment).await {
+                    Ok(metadata) if metadata.ledger_inclusion_state.is_some() => {
+                        inclusion_state = true;
+                        break;
+                    }
+                    // Orphaned, it won't be included anymore
+                    Ok(metadata) if metadata.should_reattach.unwrap_or(false) => {}
+                    Ok(_) => still_pending = true,
+                    // Pruned or never known by the node
+                    Err(Error::NotFound(_)) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+
+            if inclusion_state || !still_pending {
+                output_reservations.release_block(&block_id);
+            } else {
+                pending.push(block_id);
+            }
+        }
+
+        Ok(pending)
+    }
+
+    // Releases the reserved inputs of a sent block once it or one of its reattachments is included or conflicting.
+    pub(crate) fn release_pending_block(&self, block_id: &BlockId) {
+        if let Some(output_reservations) = &self.output_reservations {
+            output_reservations.release_block(block_id);
+        }
+    }
+}
+
+/// The outputs selected as inputs by transactions that are in flight, so concurrent transactions of the same client
+/// don't select them too.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct OutputReservations {
+    reserved: Arc<Mutex<HashSet<OutputId>>>,
+    // Inputs of sent blocks, reserved until the blocks are confirmed
+    pending: Arc<Mutex<HashMap<BlockId, Vec<OutputId>>>>,
+    // Reattachments of sent blocks, mapped to the id of the sent block
+    attachments: Arc<Mutex<HashMap<BlockId, BlockId>>>,
+    // Held while inputs are selected and reserved, so a concurrent transaction sees the reservations
+    selection: Arc<AsyncMutex<()>>,
+}
+
+impl OutputReservations {
+    /// Waits until no other transaction is selecting inputs, the returned guard has to be held until the selected
+    /// inputs are reserved.
+    pub(crate) async fn lock_selection(&self) -> AsyncMutexGuard<'_, ()> {
+        self.selection.lock().await
+    }
+
+    pub(crate) fn is_reserved(&self, output_id: &OutputId) -> bool {
+        self.reserved
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(output_id)
+    }
+
+    /// Reserves the outputs until the returned reservation is dropped.
+    pub(crate) fn reserve(&self, output_ids: impl IntoIterator<Item = OutputId>) -> Reservation {
+        let mut reserved = self.reserved.lock().unwrap_or_else(PoisonError::into_inner);
+        let output_ids = output_ids
+            .into_iter()
+            .filter(|output_id| reserved.insert(*output_id))
+            .collect();
+
+        Reservation {
+            reservations: self.clone(),
+            output_ids,
+        }
+    }
+
+    /// Keeps the outputs of the reservation reserved until the sent block is released.
+    pub(crate) fn keep_until_confirmed(&self, block_id: BlockId, mut reservation: Reservation) {
+        let output_ids = std::mem::take(&mut reservation.output_ids);
+
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(block_id)
+            .or_default()
+            .extend(output_ids);
+    }
+
+    /// Releases the outputs reserved for a sent block, which can also be given by the id of one of its reattachments.
+    pub(crate) fn release_block(&self, block_id: &BlockId) {
+        let original = self.original_block_id(block_id);
+        self.attachments
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|_, sent| *sent != original);
+        let output_ids = self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&original);
+
+        if let Some(output_ids) = output_ids {
+            let mut reserved = self.reserved.lock().unwrap_or_else(PoisonError::into_inner);
+            for output_id in &output_ids {
+                reserved.remove(output_id);
+            }
+        }
+    }
+
+    /// Records a reattachment of a block, so the reserved outputs of the sent block are released by it too.
+    pub(crate) fn add_attachment(&self, block_id: &BlockId, reattached_block_id: BlockId) {
+        let original = self.original_block_id(block_id);
+
+        if self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&original)
+        {
+            self.attachments
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(reattached_block_id, original);
+        }
+    }
+
+    /// Returns the sent block and its reattachments.
+    pub(crate) fn attachments(&self, block_id: &BlockId) -> Vec<BlockId> {
+        let mut attachments = vec![*block_id];
+        attachments.extend(
+            self.attachments
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .iter()
+                .filter(|(_, sent)| *sent == block_id)
+                .map(|(reattached, _)| *reattached),
+        );
+        attachments
+    }
+
+    // Returns the id of the sent block if the block is a reattachment.
+    fn original_block_id(&self, block_id: &BlockId) -> BlockId {
+        self.attachments
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(block_id)
+            .copied()
+            .unwrap_or(*block_id)
+    }
+
+    pub(crate) fn pending_blocks(&self) -> Vec<PendingBlock> {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(block_id, output_ids)| PendingBlock {
+                block_id: *block_id,
+                reserved_outputs: output_ids.clone(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn restore(&self, pending_blocks: Vec<PendingBlock>) {
+        for PendingBlock {
+            block_id,
+            reserved_outputs,
+        } in pending_blocks
+        {
+            let reservation = self.reserve(reserved_outputs);
+            self.keep_until_confirmed(block_id, reservation);
+        }
+    }
+}
+
+/// Outputs reserved by a transaction, released on drop, so also if the transaction fails or its future gets dropped.
+#[derive(Debug)]
+pub(crate) struct Reservation {
+    reservations: OutputReservations,
+    output_ids: Vec<OutputId>,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        let mut reserved = self
+            .reservations
+            .reserved
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        for output_id in &self.output_ids {
+            reserved.remove(output_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::block::payload::transaction::TransactionId;
+
+    use super::*;
+
+    fn output_id(index: u16) -> OutputId {
+        OutputId::new(TransactionId::new([1; 32]), index).unwrap()
+    }
+
+    #[test]
+    fn reservations_are_released_on_drop() {
+        let reservations = OutputReservations::default();
+
+        let first = reservations.reserve([output_id(0), output_id(1)]);
+        // Already reserved outputs stay owned by the first reservation
+        let second = reservations.reserve([output_id(1), output_id(2)]);
+        assert!((0..3).all(|index| reservations.is_reserved(&output_id(index))));
+
+        drop(second);
+        assert!(reservations.is_reserved(&output_id(1)));
+        assert!(!reservations.is_reserved(&output_id(2)));
+
+        drop(first);
+        assert!(!reservations.is_reserved(&output_id(0)));
+        assert!(!reservations.is_reserved(&output_id(1)));
+    }
+
+    #[test]
+    fn pending_reservations_survive_export_and_import() {
+        let block_id = BlockId::new([2; 32]);
+        let reservations = OutputReservations::default();
+        reservations.keep_until_confirmed(block_id, reservations.reserve([output_id(0), output_id(1)]));
+        // A transaction that isn't sent yet isn't pending
+        let _in_flight = reservations.reserve([output_id(2)]);
+
+        let pending_blocks = reservations.pending_blocks();
+        assert_eq!(
+            pending_blocks,
+            vec![PendingBlock {
+                block_id,
+                reserved_outputs: vec![output_id(0), output_id(1)],
+            }]
+        );
+
+        // Restored after a restart, a new transaction doesn't select the inputs again
+        let restored = OutputReservations::default();
+        restored.restore(pending_blocks);
+        assert!(restored.is_reserved(&output_id(0)));
+        assert!(restored.is_reserved(&output_id(1)));
+        assert!(!restored.is_reserved(&output_id(2)));
+
+        restored.release_block(&block_id);
+        assert!(!restored.is_reserved(&output_id(0)));
+        assert!(!restored.is_reserved(&output_id(1)));
+    }
+
+    #[test]
+    fn reattachments_release_the_sent_block() {
+        let block_id = BlockId::new([2; 32]);
+        let reattached_block_id = BlockId::new([3; 32]);
+        let reservations = OutputReservations::default();
+        reservations.keep_until_confirmed(block_id, reservations.reserve([output_id(0)]));
+        reservations.add_attachment(&block_id, reattached_block_id);
+        // Blocks that aren't pending don't get attachments
+        reservations.add_attachment(&BlockId::new([4; 32]), BlockId::new([5; 32]));
+
+        assert_eq!(reservations.attachments(&block_id), vec![block_id, reattached_block_id]);
+        assert_eq!(
+            reservations.attachments(&BlockId::new([4; 32])),
+            vec![BlockId::new([4; 32])]
+        );
+
+        reservations.release_block(&reattached_block_id);
+        assert!(!reservations.is_reserved(&output_id(0)));
+        assert!(reservations.pending_blocks().is_empty());
+        assert_eq!(reservations.attachments(&block_id), vec![block_id]);
+    }
+}
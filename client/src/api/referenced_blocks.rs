@@ -0,0 +1,107 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::HashSet, str::FromStr};
+
+use futures::Future;
+use iota_types::{api::core::response::BlockMetadataResponse, block::BlockId};
+
+use crate::{Client, Result};
+
+impl Client {
+    /// Returns the metadata of the blocks referenced by the milestone with the index. The parents of the milestone are
+    /// walked breadth-first until blocks referenced by earlier milestones are reached, so only the blocks of one
+    /// milestone are kept in memory, call it for every index to go through a range of milestones.
+    pub async fn get_blocks_referenced_by_milestone(&self, index: u32) -> Result<Vec<BlockMetadataResponse>> {
+        let milestone = self.get_milestone_by_index(index).await?;
+
+        collect_referenced_blocks(index, milestone.essence().parents().to_vec(), |block_id| async move {
+            self.get_block_metadata(&block_id).await
+        })
+        .await
+    }
+}
+
+// Breadth-first search from the parents of a milestone over the blocks that were referenced by it. The parents of
+// blocks that were referenced by another milestone aren't visited, every block is only fetched once.
+#[allow(clippy::future_not_send)]
+async fn collect_referenced_blocks<F, Fut>(
+    milestone_index: u32,
+    parents: Vec<BlockId>,
+    mut fetch: F,
+) -> Result<Vec<BlockMetadataResponse>>
+where
+    F: FnMut(BlockId) -> Fut,
+    Fut: Future<Output = Result<BlockMetadataResponse>>,
+{
+    let mut visited = parents.iter().copied().collect::<HashSet<_>>();
+    let mut level = parents;
+    let mut referenced_blocks = Vec::new();
+
+    while !level.is_empty() {
+        let mut next_level = Vec::new();
+
+        for block_id in level {
+            let metadata = fetch(block_id).await?;
+
+            if metadata.referenced_by_milestone_index != Some(milestone_index) {
+                continue;
+            }
+
+            for parent in &metadata.parents {
+                let parent = BlockId::from_str(parent)?;
+                if visited.insert(parent) {
+                    next_level.push(parent);
+                }
+            }
+            referenced_blocks.push(metadata);
+        }
+
+        level = next_level;
+    }
+
+    Ok(referenced_blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A tangle where the milestone references blocks 1 and 2, 1 references 3, 2 references 3 and 4 and only 4 was
+    // referenced by the previous milestone.
+    async fn fetch(block_id: BlockId) -> Result<BlockMetadataResponse> {
+        let id = |byte| BlockId::new([byte; 32]).to_string();
+        let bytes: &[u8] = block_id.as_ref();
+        let (parents, referenced_by_milestone_index) = match bytes[0] {
+            1 => (vec![id(3)], 10),
+            2 => (vec![id(3), id(4)], 10),
+            3 => (vec![id(5)], 10),
+            _ => (vec![id(6)], 9),
+        };
+
+        Ok(BlockMetadataResponse {
+            block_id: block_id.to_string(),
+            parents,
+            is_solid: true,
+            referenced_by_milestone_index: Some(referenced_by_milestone_index),
+            milestone_index: None,
+            ledger_inclusion_state: None,
+            conflict_reason: None,
+            white_flag_index: None,
+            should_promote: None,
+            should_reattach: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn blocks_referenced_by_milestone() {
+        let blocks = collect_referenced_blocks(10, vec![BlockId::new([1; 32]), BlockId::new([2; 32])], fetch)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            blocks.iter().map(|block| block.block_id.clone()).collect::<Vec<_>>(),
+            [1, 2, 3].map(|byte| BlockId::new([byte; 32]).to_string())
+        );
+    }
+}
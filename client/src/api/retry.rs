@@ -0,0 +1,86 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Error, Result};
+
+/// Which actions [`Client::retry()`](crate::Client::retry) and
+/// [`Client::retry_until_included()`](crate::Client::retry_until_included) are allowed to take.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RetryStrategy {
+    /// Promote or reattach, depending on what the node recommends.
+    #[default]
+    Automatic,
+    /// Only promote, a block is never reattached so no conflicting transaction blocks get posted.
+    PromoteOnly,
+    /// Only reattach, a block is never promoted.
+    ReattachOnly,
+}
+
+/// The action to take for a block that isn't included yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RetryAction {
+    Promote,
+    Reattach,
+}
+
+impl RetryStrategy {
+    /// Returns the action allowed by the strategy for the recommendations of the node, `None` if there is nothing to
+    /// do. Errors if only promotion is allowed, but the block can only be reattached.
+    pub(crate) fn action(
+        self,
+        block_id: &str,
+        should_promote: bool,
+        should_reattach: bool,
+    ) -> Result<Option<RetryAction>> {
+        match self {
+            Self::Automatic if should_promote => Ok(Some(RetryAction::Promote)),
+            Self::Automatic if should_reattach => Ok(Some(RetryAction::Reattach)),
+            Self::PromoteOnly if should_promote => Ok(Some(RetryAction::Promote)),
+            Self::PromoteOnly if should_reattach => Err(Error::PromotionNotPossible(block_id.to_string())),
+            Self::ReattachOnly if should_reattach => Ok(Some(RetryAction::Reattach)),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_strategy_actions() {
+        let block_id = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+        assert_eq!(
+            RetryStrategy::Automatic.action(block_id, true, false).unwrap(),
+            Some(RetryAction::Promote)
+        );
+        assert_eq!(
+            RetryStrategy::Automatic.action(block_id, false, true).unwrap(),
+            Some(RetryAction::Reattach)
+        );
+        assert_eq!(RetryStrategy::Automatic.action(block_id, false, false).unwrap(), None);
+
+        assert_eq!(
+            RetryStrategy::ReattachOnly.action(block_id, false, true).unwrap(),
+            Some(RetryAction::Reattach)
+        );
+        assert_eq!(RetryStrategy::ReattachOnly.action(block_id, true, false).unwrap(), None);
+    }
+
+    #[test]
+    fn promote_only_never_reattaches() {
+        let block_id = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+        assert_eq!(
+            RetryStrategy::PromoteOnly.action(block_id, true, false).unwrap(),
+            Some(RetryAction::Promote)
+        );
+        assert_eq!(RetryStrategy::PromoteOnly.action(block_id, false, false).unwrap(), None);
+        assert!(matches!(
+            RetryStrategy::PromoteOnly.action(block_id, false, true),
+            Err(Error::PromotionNotPossible(_))
+        ));
+    }
+}
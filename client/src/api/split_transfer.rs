@@ -0,0 +1,140 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_types::block::{output::Output, BlockId};
+
+use crate::{secret::SecretManager, Client, Error, Result};
+
+impl Client {
+    /// Sends the outputs in as many blocks as needed so that the summed amount of the outputs in a single block doesn't
+    /// exceed `max_amount_per_block`, to limit the exposure per block. The outputs keep their order and the block ids
+    /// are returned in the order the blocks got sent. Every block has to be included before the next one is built, so
+    /// its remainder output can already be used as input and no conflicting transactions are created. If sending fails
+    /// after some blocks were sent, [`Error::PartiallySent`] is returned with their block ids.
+    pub async fn send_outputs_with_amount_cap(
+        &self,
+        secret_manager: &SecretManager,
+        outputs: Vec<Output>,
+        max_amount_per_block: u64,
+    ) -> Result<Vec<BlockId>> {
+        let output_groups = split_outputs_by_amount(outputs, max_amount_per_block)?;
+        let last_group_index = output_groups.len().saturating_sub(1);
+        let mut block_ids = Vec::new();
+
+        for (index, outputs) in output_groups.into_iter().enumerate() {
+            let block = self
+                .block()
+                .with_secret_manager(secret_manager)
+                .with_outputs(outputs)
+                .map_err(|e| e.with_sent_blocks(&block_ids))?
+                .finish()
+                .await
+                .map_err(|e| e.with_sent_blocks(&block_ids))?;
+            let block_id = block.id();
+            block_ids.push(block_id);
+
+            // Wait for the transaction to get confirmed so the next one can use the remainder
+            if index != last_group_index {
+                self.retry_until_included(&block_id, None, None)
+                    .await
+                    .map_err(|e| e.with_sent_blocks(&block_ids))?;
+            }
+        }
+
+        Ok(block_ids)
+    }
+}
+
+// Splits the outputs in order into groups whose summed amount doesn't exceed `max_amount`.
+fn split_outputs_by_amount(outputs: Vec<Output>, max_amount: u64) -> Result<Vec<Vec<Output>>> {
+    let mut groups: Vec<Vec<Output>> = Vec::new();
+    let mut group_amount = 0;
+
+    for output in outputs {
+        let amount = output.amount();
+        if amount > max_amount {
+            return Err(Error::OutputAmountAboveCap {
+                amount,
+                cap: max_amount,
+            });
+        }
+
+        match groups.last_mut() {
+            Some(group) if group_amount + amount <= max_amount => {
+                group_amount += amount;
+                group.push(output);
+            }
+            _ => {
+                group_amount = amount;
+                groups.push(vec![output]);
+            }
+        }
+    }
+
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use iota_types::block::{
+        address::{Address, Ed25519Address},
+        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, UnlockCondition},
+        protocol::protocol_parameters,
+    };
+
+    use super::*;
+
+    fn basic_output(amount: u64) -> Output {
+        BasicOutputBuilder::new_with_amount(amount)
+            .unwrap()
+            .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+                Ed25519Address::new([0; 32]),
+            ))))
+            .finish_output(protocol_parameters().token_supply())
+            .unwrap()
+    }
+
+    fn group_amounts(groups: &[Vec<Output>]) -> Vec<Vec<u64>> {
+        groups
+            .iter()
+            .map(|group| group.iter().map(Output::amount).collect())
+            .collect()
+    }
+
+    #[test]
+    fn outputs_above_cap_are_split() {
+        let outputs = [400_000, 300_000, 500_000, 1_000_000, 200_000]
+            .into_iter()
+            .map(basic_output)
+            .collect();
+
+        let groups = split_outputs_by_amount(outputs, 1_000_000).unwrap();
+
+        assert_eq!(
+            group_amounts(&groups),
+            vec![vec![400_000, 300_000], vec![500_000], vec![1_000_000], vec![200_000]]
+        );
+    }
+
+    #[test]
+    fn outputs_below_cap_stay_together() {
+        let outputs = [400_000, 300_000].into_iter().map(basic_output).collect();
+
+        let groups = split_outputs_by_amount(outputs, 1_000_000).unwrap();
+
+        assert_eq!(group_amounts(&groups), vec![vec![400_000, 300_000]]);
+    }
+
+    #[test]
+    fn single_output_above_cap() {
+        let outputs = vec![basic_output(400_000), basic_output(2_000_000)];
+
+        assert!(matches!(
+            split_outputs_by_amount(outputs, 1_000_000),
+            Err(Error::OutputAmountAboveCap {
+                amount: 2_000_000,
+                cap: 1_000_000
+            })
+        ));
+    }
+}
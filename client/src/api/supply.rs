@@ -0,0 +1,55 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Client, Error, Result};
+
+/// The supply of the base token of the network
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Supply {
+    /// Total supply from the protocol parameters
+    // Using Strings to prevent overflow issues in other languages
+    pub total_supply: String,
+    /// Amount held by the treasury
+    pub treasury: String,
+    /// Total supply minus the treasury
+    pub circulating: String,
+}
+
+impl Supply {
+    fn new(total_supply: u64, treasury: u64) -> Self {
+        Self {
+            total_supply: total_supply.to_string(),
+            treasury: treasury.to_string(),
+            circulating: total_supply.saturating_sub(treasury).to_string(),
+        }
+    }
+}
+
+impl Client {
+    /// Returns the total supply of the base token, the amount held by the treasury and the circulating supply.
+    pub async fn get_supply(&self) -> Result<Supply> {
+        let total_supply = self.get_token_supply().await?;
+        let treasury = self.get_treasury().await?.amount;
+        let treasury = treasury.parse::<u64>().map_err(|_| Error::InvalidAmount(treasury))?;
+
+        Ok(Supply::new(total_supply, treasury))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circulating_supply() {
+        assert_eq!(
+            Supply::new(1_813_620_509_061_365, 277_761),
+            Supply {
+                total_supply: "1813620509061365".to_string(),
+                treasury: "277761".to_string(),
+                circulating: "1813620508783604".to_string(),
+            }
+        );
+    }
+}
@@ -1,18 +1,21 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_types::block::{
-    address::{dto::AddressDto, Address},
-    output::{dto::OutputDto, Output},
-    payload::{
-        transaction::{
-            dto::{TransactionEssenceDto, TransactionPayloadDto},
-            TransactionEssence,
+use iota_types::{
+    api::core::response::OutputWithMetadataResponse,
+    block::{
+        address::{dto::AddressDto, Address},
+        output::{dto::OutputDto, Output},
+        payload::{
+            transaction::{
+                dto::{TransactionEssenceDto, TransactionPayloadDto},
+                TransactionEssence,
+            },
+            Payload, TransactionPayload,
         },
-        TransactionPayload,
+        protocol::ProtocolParameters,
+        Block, BlockDto, DtoError,
     },
-    protocol::ProtocolParameters,
-    DtoError,
 };
 
 use crate::{
@@ -30,6 +33,10 @@ pub struct PreparedTransactionData {
     pub inputs_data: Vec<InputSigningData>,
     /// Optional remainder output information
     pub remainder: Option<RemainderData>,
+    /// If an output or remainder address owns or owned outputs other than the inputs, only checked if enabled with
+    /// [`ClientBlockBuilder::with_address_reuse_check()`](crate::api::ClientBlockBuilder::with_address_reuse_check)
+    #[serde(rename = "reuseWarning", default)]
+    pub reuse_warning: bool,
 }
 
 /// PreparedTransactionData Dto
@@ -42,6 +49,9 @@ pub struct PreparedTransactionDataDto {
     pub inputs_data: Vec<InputSigningDataDto>,
     /// Optional remainder output information
     pub remainder: Option<RemainderDataDto>,
+    /// If the remainder address owns or owned outputs other than the inputs
+    #[serde(rename = "reuseWarning", default)]
+    pub reuse_warning: bool,
 }
 
 impl From<&PreparedTransactionData> for PreparedTransactionDataDto {
@@ -50,6 +60,7 @@ impl From<&PreparedTransactionData> for PreparedTransactionDataDto {
             essence: TransactionEssenceDto::from(&value.essence),
             inputs_data: value.inputs_data.iter().map(InputSigningDataDto::from).collect(),
             remainder: value.remainder.as_ref().map(RemainderDataDto::from),
+            reuse_warning: value.reuse_warning,
         }
     }
 }
@@ -76,6 +87,7 @@ impl PreparedTransactionData {
                 ),
                 None => None,
             },
+            reuse_warning: value.reuse_warning,
         })
     }
 
@@ -97,6 +109,7 @@ impl PreparedTransactionData {
                 ),
                 None => None,
             },
+            reuse_warning: value.reuse_warning,
         })
     }
 }
@@ -232,3 +245,114 @@ pub struct Bech32Addresses {
     /// Internal/change addresses <https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki#change>
     pub internal: Vec<String>,
 }
+
+impl Bech32Addresses {
+    /// Returns the addresses interleaved by index, with the public address of an index before its internal one, like
+    /// `[public 0, internal 0, public 1, internal 1, ...]`.
+    pub fn into_interleaved(self) -> Vec<AddressWithInternal> {
+        self.public
+            .into_iter()
+            .zip(self.internal)
+            .flat_map(|(public, internal)| {
+                [
+                    AddressWithInternal {
+                        address: public,
+                        internal: false,
+                    },
+                    AddressWithInternal {
+                        address: internal,
+                        internal: true,
+                    },
+                ]
+            })
+            .collect()
+    }
+}
+
+/// A bech32 encoded address and whether it's an internal/change address
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressWithInternal {
+    /// The bech32 encoded address
+    pub address: String,
+    /// Whether it's an internal/change address
+    pub internal: bool,
+}
+
+/// The timestamp of the latest confirmed milestone, compared with the local time
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneTimestamp {
+    /// The index of the latest confirmed milestone
+    pub milestone_index: u32,
+    /// The timestamp of the latest confirmed milestone
+    pub milestone_timestamp: u32,
+    /// The local time at the time of the request
+    pub local_time: u32,
+    /// If the local time differs more than 5 minutes from the milestone timestamp
+    pub clock_skew: bool,
+}
+
+/// Outputs booked by the same milestone
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneOutputs {
+    /// The index of the milestone that booked the outputs
+    pub milestone_index: u32,
+    /// The timestamp of the milestone that booked the outputs
+    pub milestone_timestamp: u32,
+    /// The outputs with their metadata
+    pub outputs: Vec<OutputWithMetadataResponse>,
+}
+
+/// The type of the payload of a block, used to filter blocks
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PayloadType {
+    /// Blocks without a payload
+    None,
+    /// Blocks with a tagged data payload
+    TaggedData,
+    /// Blocks with a transaction payload
+    Transaction,
+    /// Blocks with a milestone payload
+    Milestone,
+    /// Blocks with a treasury transaction payload
+    TreasuryTransaction,
+}
+
+impl PayloadType {
+    /// Returns the payload type of a block payload.
+    pub fn of(payload: Option<&Payload>) -> Self {
+        match payload {
+            None => Self::None,
+            Some(Payload::TaggedData(_)) => Self::TaggedData,
+            Some(Payload::Transaction(_)) => Self::Transaction,
+            Some(Payload::Milestone(_)) => Self::Milestone,
+            Some(Payload::TreasuryTransaction(_)) => Self::TreasuryTransaction,
+        }
+    }
+}
+
+/// A block DTO with the type of its payload.
+///
+/// The payload type is a discriminator, so the untagged payload doesn't have to be inspected to know its shape. It's
+/// deserialized as [`BlockDto`] as well, as unknown fields are ignored.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedBlockDto {
+    /// The type of the payload
+    pub payload_type: PayloadType,
+    /// The block
+    #[serde(flatten)]
+    pub block: BlockDto,
+}
+
+impl From<&Block> for TypedBlockDto {
+    fn from(block: &Block) -> Self {
+        Self {
+            payload_type: PayloadType::of(block.payload()),
+            block: BlockDto::from(block),
+        }
+    }
+}
@@ -0,0 +1,449 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{future::Future, ops::Range, time::Duration};
+
+use instant::Instant;
+use iota_types::block::output::{Output, OutputId};
+
+use crate::{node_api::indexer::query_parameters::QueryParameter, secret::SecretManager, Client, Error, Result};
+
+/// Point-in-time snapshot of the unspent outputs of an account
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoSnapshot {
+    /// The highest ledger index reported by the indexer while the snapshot was taken
+    pub ledger_index: u32,
+    /// Addresses with unspent outputs
+    pub addresses: Vec<UtxoSnapshotAddress>,
+    /// Total amount of all unspent outputs
+    // Using a String to prevent overflow issues in other languages
+    pub total_amount: String,
+    /// False if the scan was cut short by a deadline, so the snapshot only contains the outputs found until then
+    pub complete: bool,
+}
+
+/// Unspent outputs of a single address in a [`UtxoSnapshot`]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoSnapshotAddress {
+    /// Bech32 encoded address
+    pub address: String,
+    /// Address index
+    pub index: u32,
+    /// Internal (change) or public address
+    pub internal: bool,
+    /// Unspent outputs of the address
+    pub outputs: Vec<UtxoSnapshotOutput>,
+    /// Total amount of the unspent outputs of the address
+    pub amount: String,
+}
+
+/// Unspent output in a [`UtxoSnapshot`]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoSnapshotOutput {
+    /// Output ID
+    pub output_id: OutputId,
+    /// Amount
+    pub amount: String,
+}
+
+/// Whether an account can afford a set of outputs, without taking fees or storage deposits of remainders into account
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Affordability {
+    /// If the available amount covers the required amount
+    pub affordable: bool,
+    /// Total amount of the unspent outputs of the account
+    // Using a String to prevent overflow issues in other languages
+    pub available: String,
+    /// Total amount of the outputs
+    pub required: String,
+}
+
+/// The first address of an account that owns unspent outputs
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FundedAddress {
+    /// Bech32 encoded address
+    pub address: String,
+    /// Address index
+    pub index: u32,
+    /// Internal (change) or public address
+    pub internal: bool,
+    /// Total amount of the unspent outputs of the address
+    // Using a String to prevent overflow issues in other languages
+    pub amount: String,
+}
+
+impl Affordability {
+    // The outputs are only affordable if their summed amount doesn't overflow.
+    fn new(available: u64, outputs: &[Output]) -> Self {
+        let required = outputs
+            .iter()
+            .try_fold(0u64, |sum, output| sum.checked_add(output.amount()));
+
+        Self {
+            affordable: required.is_some_and(|required| available >= required),
+            available: available.to_string(),
+            required: outputs
+                .iter()
+                .map(|output| u128::from(output.amount()))
+                .sum::<u128>()
+                .to_string(),
+        }
+    }
+}
+
+impl Client {
+    /// Takes a snapshot of all unspent basic and NFT outputs owned by the public and internal addresses of an
+    /// account. Addresses are scanned until `gap_limit` consecutive addresses without outputs were found.
+    pub async fn export_utxo_snapshot(
+        &self,
+        secret_manager: &SecretManager,
+        coin_type: u32,
+        account_index: u32,
+        gap_limit: u32,
+    ) -> Result<UtxoSnapshot> {
+        self.scan_utxo_snapshot(secret_manager, coin_type, account_index, gap_limit, None)
+            .await
+    }
+
+    /// Like [`Client::export_utxo_snapshot()`], but stops scanning once the deadline is reached, requests that are
+    /// still in flight then are cancelled. The outputs of the addresses scanned until then are returned with
+    /// [`UtxoSnapshot::complete`] set to false, so a provisional balance can be shown and refined with a later scan.
+    pub async fn export_utxo_snapshot_with_deadline(
+        &self,
+        secret_manager: &SecretManager,
+        coin_type: u32,
+        account_index: u32,
+        gap_limit: u32,
+        deadline: Duration,
+    ) -> Result<UtxoSnapshot> {
+        self.scan_utxo_snapshot(secret_manager, coin_type, account_index, gap_limit, Some(deadline))
+            .await
+    }
+
+    /// Compares the total amount of the unspent outputs of an account, as in [`Client::export_utxo_snapshot()`], with
+    /// the summed amount of the outputs. Outputs whose summed amount overflows can't be afforded. No input selection is
+    /// done and nothing is sent.
+    pub async fn can_afford(
+        &self,
+        secret_manager: &SecretManager,
+        coin_type: u32,
+        account_index: u32,
+        gap_limit: u32,
+        outputs: &[Output],
+    ) -> Result<Affordability> {
+        let snapshot = self
+            .export_utxo_snapshot(secret_manager, coin_type, account_index, gap_limit)
+            .await?;
+        let available = snapshot
+            .total_amount
+            .parse::<u64>()
+            .map_err(|_| Error::InvalidAmount(snapshot.total_amount))?;
+
+        Ok(Affordability::new(available, outputs))
+    }
+
+    /// Returns the first address of an account that owns unspent basic or NFT outputs, scanning the public addresses
+    /// before the internal ones and stopping at the first hit. Returns `None` if no funds were found before
+    /// `gap_limit` consecutive addresses without outputs.
+    pub async fn get_first_funded_address(
+        &self,
+        secret_manager: &SecretManager,
+        coin_type: u32,
+        account_index: u32,
+        gap_limit: u32,
+    ) -> Result<Option<FundedAddress>> {
+        let bech32_hrp = self.get_bech32_hrp().await?;
+        let token_supply = self.get_token_supply().await?;
+        let batch_size = self.get_address_scan_batch_size(gap_limit);
+
+        for internal in [false, true] {
+            let mut address_scan = AddressScan::new(gap_limit, batch_size);
+
+            while let Some(range) = address_scan.next_batch() {
+                let bech32_addresses = self
+                    .get_addresses(secret_manager)
+                    .with_coin_type(coin_type)
+                    .with_account_index(account_index)
+                    .with_range(range.clone())
+                    .with_internal_addresses(internal)
+                    .with_bech32_hrp(&bech32_hrp)
+                    .finish()
+                    .await?;
+
+                for (index, address) in range.zip(bech32_addresses) {
+                    let (_, output_ids) = self.unspent_output_ids(&address).await?;
+
+                    if output_ids.is_empty() {
+                        if !address_scan.record_empty() {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    let mut amount = 0;
+                    for output_response in self.get_outputs(output_ids).await? {
+                        amount += Output::try_from_dto(&output_response.output, token_supply)?.amount();
+                    }
+
+                    return Ok(Some(FundedAddress {
+                        address,
+                        index,
+                        internal,
+                        amount: amount.to_string(),
+                    }));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Returns the highest ledger index reported by the indexer and the IDs of the unspent basic and NFT outputs of the
+    // address.
+    pub(crate) async fn unspent_output_ids(&self, bech32_address: &str) -> Result<(u32, Vec<OutputId>)> {
+        let mut ledger_index = 0;
+        let mut output_ids = Vec::new();
+
+        for output_ids_response in [
+            self.basic_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+                .await?,
+            self.nft_output_ids(vec![QueryParameter::Address(bech32_address.to_string())])
+                .await?,
+        ] {
+            ledger_index = ledger_index.max(output_ids_response.ledger_index);
+            output_ids.extend(output_ids_response.items);
+        }
+
+        Ok((ledger_index, output_ids))
+    }
+
+    async fn scan_utxo_snapshot(
+        &self,
+        secret_manager: &SecretManager,
+        coin_type: u32,
+        account_index: u32,
+        gap_limit: u32,
+        deadline: Option<Duration>,
+    ) -> Result<UtxoSnapshot> {
+        let deadline = deadline.map(|deadline| Instant::now() + deadline);
+        let bech32_hrp = self.get_bech32_hrp().await?;
+        let token_supply = self.get_token_supply().await?;
+        let gap_limit = gap_limit.max(1);
+        let batch_size = self.get_address_scan_batch_size(gap_limit);
+
+        let mut ledger_index = 0;
+        let mut addresses = Vec::new();
+        let mut total_amount = 0;
+        let mut complete = true;
+
+        'scan: for internal in [false, true] {
+            let mut address_scan = AddressScan::new(gap_limit, batch_size);
+
+            while let Some(range) = address_scan.next_batch() {
+                let bech32_addresses = self
+                    .get_addresses(secret_manager)
+                    .with_coin_type(coin_type)
+                    .with_account_index(account_index)
+                    .with_range(range.clone())
+                    .with_internal_addresses(internal)
+                    .with_bech32_hrp(&bech32_hrp)
+                    .finish()
+                    .await?;
+
+                for (index, address) in range.zip(bech32_addresses) {
+                    // The requests of an address are cut short as well, so a slow node can't overrun the deadline.
+                    let address_outputs = until_deadline(deadline, async {
+                        let (address_ledger_index, output_ids) = self.unspent_output_ids(&address).await?;
+                        let output_responses = if output_ids.is_empty() {
+                            Vec::new()
+                        } else {
+                            self.get_outputs(output_ids).await?
+                        };
+                        Ok((address_ledger_index, output_responses))
+                    })
+                    .await?;
+                    let Some((address_ledger_index, output_responses)) = address_outputs else {
+                        complete = false;
+                        break 'scan;
+                    };
+                    ledger_index = ledger_index.max(address_ledger_index);
+
+                    if output_responses.is_empty() {
+                        if !address_scan.record_empty() {
+                            break;
+                        }
+                        continue;
+                    }
+                    address_scan.record_used();
+
+                    let mut outputs = Vec::new();
+                    let mut address_amount = 0;
+                    for output_response in output_responses {
+                        let amount = Output::try_from_dto(&output_response.output, token_supply)?.amount();
+                        address_amount += amount;
+                        outputs.push(UtxoSnapshotOutput {
+                            output_id: output_response.metadata.output_id()?,
+                            amount: amount.to_string(),
+                        });
+                    }
+                    total_amount += address_amount;
+
+                    addresses.push(UtxoSnapshotAddress {
+                        address,
+                        index,
+                        internal,
+                        outputs,
+                        amount: address_amount.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(UtxoSnapshot {
+            ledger_index,
+            addresses,
+            total_amount: total_amount.to_string(),
+            complete,
+        })
+    }
+}
+
+// Runs the request with the time left until the deadline, returns `None` if the deadline is reached first.
+async fn until_deadline<T>(deadline: Option<Instant>, request: impl Future<Output = Result<T>>) -> Result<Option<T>> {
+    let Some(deadline) = deadline else {
+        return request.await.map(Some);
+    };
+    let now = Instant::now();
+    if now >= deadline {
+        return Ok(None);
+    }
+    let remaining = deadline - now;
+
+    #[cfg(not(target_family = "wasm"))]
+    let result = tokio::time::timeout(remaining, request).await.ok();
+    #[cfg(target_family = "wasm")]
+    let result = {
+        let timeout = gloo_timers::future::TimeoutFuture::new(remaining.as_millis().try_into().unwrap_or(u32::MAX));
+        futures::pin_mut!(request);
+        match futures::future::select(request, timeout).await {
+            futures::future::Either::Left((result, _)) => Some(result),
+            futures::future::Either::Right(_) => None,
+        }
+    };
+
+    result.transpose()
+}
+
+// Hands out the address ranges to scan in batches until `gap_limit` consecutive addresses without outputs were found.
+struct AddressScan {
+    gap_limit: u32,
+    batch_size: u32,
+    next_index: u32,
+    empty_addresses_in_row: u32,
+}
+
+impl AddressScan {
+    fn new(gap_limit: u32, batch_size: u32) -> Self {
+        Self {
+            gap_limit: gap_limit.max(1),
+            batch_size: batch_size.max(1),
+            next_index: 0,
+            empty_addresses_in_row: 0,
+        }
+    }
+
+    // Returns the address range of the next batch, or `None` once the gap limit is reached.
+    fn next_batch(&mut self) -> Option<Range<u32>> {
+        if self.empty_addresses_in_row >= self.gap_limit {
+            return None;
+        }
+
+        let range = self.next_index..self.next_index + self.batch_size;
+        self.next_index = range.end;
+
+        Some(range)
+    }
+
+    // Records an address without outputs, returns false once the gap limit is reached, so the remaining addresses of
+    // the batch don't need to be queried.
+    fn record_empty(&mut self) -> bool {
+        self.empty_addresses_in_row += 1;
+        self.empty_addresses_in_row < self.gap_limit
+    }
+
+    fn record_used(&mut self) {
+        self.empty_addresses_in_row = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn affordability() {
+        assert_eq!(
+            Affordability::new(2_000_000, 1_500_000),
+            Affordability {
+                affordable: true,
+                available: "2000000".to_string(),
+                required: "1500000".to_string(),
+            }
+        );
+        assert!(Affordability::new(1_500_000, 1_500_000).affordable);
+        assert_eq!(
+            Affordability::new(1_000_000, 1_500_000),
+            Affordability {
+                affordable: false,
+                available: "1000000".to_string(),
+                required: "1500000".to_string(),
+            }
+        );
+    }
+
+    // Scans addresses of which only the provided indexes have outputs and returns the requested batches and the amount
+    // of queried addresses.
+    fn scan(gap_limit: u32, batch_size: u32, used_indexes: &[u32]) -> (Vec<Range<u32>>, u32) {
+        let mut address_scan = AddressScan::new(gap_limit, batch_size);
+        let mut batches = Vec::new();
+        let mut queried_addresses = 0;
+
+        while let Some(range) = address_scan.next_batch() {
+            batches.push(range.clone());
+            for index in range {
+                queried_addresses += 1;
+                if used_indexes.contains(&index) {
+                    address_scan.record_used();
+                } else if !address_scan.record_empty() {
+                    break;
+                }
+            }
+        }
+
+        (batches, queried_addresses)
+    }
+
+    #[test]
+    fn address_scan_batch_size() {
+        // Index 25 is behind a gap of 21 addresses, so it's never found
+        let used_indexes = [0, 3, 25];
+
+        let (batches, queried_addresses) = scan(20, 5, &used_indexes);
+        assert_eq!(batches, vec![0..5, 5..10, 10..15, 15..20, 20..25]);
+        // The scan stops at index 23, after 20 empty addresses in a row
+        assert_eq!(queried_addresses, 24);
+
+        let (batches, queried_addresses) = scan(20, 50, &used_indexes);
+        assert_eq!(batches, vec![0..50]);
+        assert_eq!(queried_addresses, 24);
+
+        // Defaulting to the gap limit as batch size
+        let (batches, _) = scan(20, 20, &used_indexes);
+        assert_eq!(batches, vec![0..20, 20..40]);
+    }
+}
@@ -13,20 +13,63 @@ use iota_types::block::{
     protocol::{dto::ProtocolParametersDto, ProtocolParameters},
 };
 #[cfg(not(target_family = "wasm"))]
-use tokio::runtime::Runtime;
+use {
+    std::sync::atomic::{AtomicUsize, Ordering},
+    tokio::runtime::{Builder, Runtime},
+};
 
 #[cfg(feature = "mqtt")]
 use crate::node_api::mqtt::{BrokerOptions, MqttEvent};
+#[cfg(not(target_family = "wasm"))]
+use crate::node_manager::syncing::{Bech32HrpCheck, SyncOptions};
 use crate::{
+    api::{BatchErrorMode, OutputReservations},
     client::Client,
-    constants::{DEFAULT_API_TIMEOUT, DEFAULT_REMOTE_POW_API_TIMEOUT, DEFAULT_TIPS_INTERVAL},
-    error::Result,
+    constants::{
+        DEFAULT_API_TIMEOUT, DEFAULT_MILESTONE_FETCH_CONCURRENCY, DEFAULT_OUTPUT_FETCH_CONCURRENCY,
+        DEFAULT_REMOTE_POW_API_TIMEOUT, DEFAULT_THREAD_NAME_PREFIX, DEFAULT_TIPS_INTERVAL,
+        DEFAULT_TIP_SELECTION_RETRY_DELAY,
+    },
+    error::{Error, ErrorMessageMapper, Result},
     node_manager::{
-        builder::validate_url,
+        builder::{validate_url, ReadRetry},
         node::{Node, NodeAuth},
     },
 };
 
+/// How addresses are represented in message interface responses. Only generated addresses and the `address` and
+/// `consolidationAddress` fields of responses are formatted, converted addresses like from `HexToBech32` are kept.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AddressFormat {
+    /// Bech32 encoded.
+    #[default]
+    Bech32,
+    /// Hex encoded packed address, starting with the address kind.
+    Hex,
+    /// Bech32 encoded, with the hex representation in an additional field with the `Hex` suffix, like `addressHex`.
+    /// Lists of generated addresses stay bech32 encoded.
+    Both,
+}
+
+/// Timeouts for categories of API requests, the general API timeout is used for categories without one.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTimeouts {
+    /// Timeout for node info and health requests.
+    #[serde(default)]
+    pub info: Option<Duration>,
+    /// Timeout for tips requests.
+    #[serde(default)]
+    pub tips: Option<Duration>,
+    /// Timeout for posting blocks with local proof of work, remote proof of work uses the remote PoW timeout.
+    #[serde(default)]
+    pub post_block: Option<Duration>,
+    /// Timeout for output requests.
+    #[serde(default)]
+    pub output: Option<Duration>,
+}
+
 /// Struct containing network and PoW related information
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct NetworkInfo {
@@ -128,11 +171,89 @@ pub struct ClientBuilder {
     /// Timeout when sending a block that requires remote proof of work
     #[serde(rename = "remotePowTimeout", default = "default_remote_pow_timeout")]
     pub remote_pow_timeout: Duration,
+    /// Timeouts for categories of API requests, which override the API timeout
+    #[serde(rename = "apiTimeouts", default)]
+    pub api_timeouts: ApiTimeouts,
     /// The amount of threads to be used for proof of work
     #[serde(rename = "powWorkerCount", default)]
     pub pow_worker_count: Option<usize>,
+    /// Maximum time local proof of work may take before building a block fails
+    #[serde(rename = "powDeadline", default)]
+    pub pow_deadline: Option<Duration>,
+    /// How batch requests handle failing items
+    #[serde(rename = "batchErrorMode", default)]
+    pub batch_error_mode: BatchErrorMode,
+    /// How often a failed tips request is retried before building a block fails
+    #[serde(rename = "tipSelectionRetries", default)]
+    pub tip_selection_retries: u32,
+    /// Delay before the first tips request retry, doubled for every further retry
+    #[serde(rename = "tipSelectionRetryDelay", default = "default_tip_selection_retry_delay")]
+    pub tip_selection_retry_delay: Duration,
+    /// The bech32 HRP the synced nodes are expected to report
+    #[serde(rename = "bech32Hrp", default)]
+    pub bech32_hrp: Option<String>,
+    /// Fail syncing instead of only logging a warning if the nodes report a different bech32 HRP
+    #[serde(rename = "strictBech32Hrp", default)]
+    pub strict_bech32_hrp: bool,
+    /// Whether fields without a value are serialized as `null` in message interface responses
+    #[serde(rename = "emitNulls", default = "default_emit_nulls")]
+    pub emit_nulls: bool,
+    /// How addresses are represented in message interface responses
+    #[serde(rename = "addressFormat", default)]
+    pub address_format: AddressFormat,
+    /// Whether the tag and data of tagged data payloads in message interface responses are also added decoded as
+    /// UTF-8, if they are valid UTF-8
+    #[serde(rename = "decodeTaggedDataUtf8", default)]
+    pub decode_tagged_data_utf8: bool,
+    /// How many addresses are generated and queried per batch when scanning the addresses of an account, defaults to
+    /// the gap limit of the scan or 20
+    #[serde(rename = "addressScanBatchSize", default)]
+    pub address_scan_batch_size: Option<u32>,
+    /// Prefix of the names of the threads of the runtime, worker threads are named `{prefix}-worker-{n}` and blocking
+    /// threads `{prefix}-blocking-{n}`
+    #[serde(rename = "threadNamePrefix", default = "default_thread_name_prefix")]
+    pub thread_name_prefix: String,
+    /// How many milestones are requested at once when fetching a range of milestones
+    #[serde(
+        rename = "milestoneFetchConcurrency",
+        default = "default_milestone_fetch_concurrency"
+    )]
+    pub milestone_fetch_concurrency: usize,
+    /// How many outputs are requested at once when fetching multiple outputs
+    #[serde(rename = "outputFetchConcurrency", default = "default_output_fetch_concurrency")]
+    pub output_fetch_concurrency: usize,
+    /// Whether inputs selected by in-flight transactions are reserved, so concurrent transactions don't select them
+    #[serde(rename = "outputReservation", default)]
+    pub output_reservation: bool,
+    /// User-friendly messages for errors in message interface responses, keyed by the `code` or `type` of the error
+    #[serde(rename = "errorMessages", default)]
+    pub error_messages: HashMap<String, String>,
+    /// Maps errors to user-friendly messages in message interface responses
+    #[serde(skip)]
+    pub(crate) error_message_mapper: ErrorMessageMapperFn,
+}
+
+// Holds the error message mapper, so the builder can still be compared and debug printed.
+#[derive(Clone, Default)]
+pub(crate) struct ErrorMessageMapperFn(Option<Arc<ErrorMessageMapper>>);
+
+impl std::fmt::Debug for ErrorMessageMapperFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.is_some() { "Some(..)" } else { "None" })
+    }
+}
+
+impl PartialEq for ErrorMessageMapperFn {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(mapper), Some(other_mapper)) => Arc::ptr_eq(mapper, other_mapper),
+            (mapper, other_mapper) => mapper.is_none() && other_mapper.is_none(),
+        }
+    }
 }
 
+impl Eq for ErrorMessageMapperFn {}
+
 fn default_api_timeout() -> Duration {
     DEFAULT_API_TIMEOUT
 }
@@ -141,6 +262,26 @@ fn default_remote_pow_timeout() -> Duration {
     DEFAULT_REMOTE_POW_API_TIMEOUT
 }
 
+fn default_tip_selection_retry_delay() -> Duration {
+    DEFAULT_TIP_SELECTION_RETRY_DELAY
+}
+
+fn default_emit_nulls() -> bool {
+    true
+}
+
+fn default_thread_name_prefix() -> String {
+    DEFAULT_THREAD_NAME_PREFIX.to_string()
+}
+
+fn default_milestone_fetch_concurrency() -> usize {
+    DEFAULT_MILESTONE_FETCH_CONCURRENCY
+}
+
+fn default_output_fetch_concurrency() -> usize {
+    DEFAULT_OUTPUT_FETCH_CONCURRENCY
+}
+
 impl Default for NetworkInfo {
     fn default() -> Self {
         Self {
@@ -163,7 +304,24 @@ impl Default for ClientBuilder {
             network_info: NetworkInfo::default(),
             api_timeout: DEFAULT_API_TIMEOUT,
             remote_pow_timeout: DEFAULT_REMOTE_POW_API_TIMEOUT,
+            api_timeouts: ApiTimeouts::default(),
             pow_worker_count: None,
+            pow_deadline: None,
+            batch_error_mode: BatchErrorMode::default(),
+            tip_selection_retries: 0,
+            tip_selection_retry_delay: DEFAULT_TIP_SELECTION_RETRY_DELAY,
+            bech32_hrp: None,
+            strict_bech32_hrp: false,
+            emit_nulls: true,
+            address_format: AddressFormat::default(),
+            decode_tagged_data_utf8: false,
+            address_scan_batch_size: None,
+            thread_name_prefix: DEFAULT_THREAD_NAME_PREFIX.to_string(),
+            milestone_fetch_concurrency: DEFAULT_MILESTONE_FETCH_CONCURRENCY,
+            output_fetch_concurrency: DEFAULT_OUTPUT_FETCH_CONCURRENCY,
+            output_reservation: false,
+            error_messages: HashMap::new(),
+            error_message_mapper: ErrorMessageMapperFn::default(),
         }
     }
 }
@@ -211,14 +369,17 @@ impl ClientBuilder {
         Ok(self)
     }
 
-    /// Adds an IOTA node by its URL to be used as primary node, with optional jwt and or basic authentication
+    /// Adds an IOTA node by its URL to be used as primary node, with optional jwt and or basic authentication.
+    /// Requests are always sent to the primary node first, even if it isn't healthy. If it fails, the request is sent
+    /// to the other nodes in random order, until one of them returns a response.
     pub fn with_primary_node(mut self, url: &str, auth: Option<NodeAuth>) -> Result<Self> {
         self.node_manager_builder = self.node_manager_builder.with_primary_node(url, auth)?;
         Ok(self)
     }
 
     /// Adds an IOTA node by its URL to be used as primary PoW node (for remote Pow), with optional jwt and or basic
-    /// authentication
+    /// authentication. Blocks that need remote PoW are posted to it first, before the primary node and the other nodes
+    /// that support remote PoW, which are used if it fails.
     pub fn with_primary_pow_node(mut self, url: &str, auth: Option<NodeAuth>) -> Result<Self> {
         self.node_manager_builder = self.node_manager_builder.with_primary_pow_node(url, auth)?;
         Ok(self)
@@ -242,12 +403,30 @@ impl ClientBuilder {
         Ok(self)
     }
 
+    /// Discovers further nodes from the peers of the seed node, which is added as node too. On every node sync the
+    /// peers are requested again and up to `max_nodes` reachable ones are added to the healthy node pool, if
+    /// `require_healthy` is set only nodes that report to be healthy. Has no effect if the node health is ignored.
+    pub fn with_node_discovery(mut self, seed_url: &str, max_nodes: usize, require_healthy: bool) -> Result<Self> {
+        self.node_manager_builder =
+            self.node_manager_builder
+                .with_node_discovery(seed_url, max_nodes, require_healthy)?;
+        Ok(self)
+    }
+
     /// Set the node sync interval
     pub fn with_node_sync_interval(mut self, node_sync_interval: Duration) -> Self {
         self.node_manager_builder = self.node_manager_builder.with_node_sync_interval(node_sync_interval);
         self
     }
 
+    /// Disables the node syncing, so requests are sent to the configured nodes directly, without checking their health.
+    /// The [`NetworkInfo`] is fetched once from the first node that responds when the client is built, but it isn't
+    /// updated afterwards.
+    pub fn with_node_sync_disabled(mut self) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_node_sync_disabled();
+        self
+    }
+
     /// Ignores the node health status.
     /// Every node will be considered healthy and ready to use.
     pub fn with_ignore_node_health(mut self) -> Self {
@@ -255,7 +434,8 @@ impl ClientBuilder {
         self
     }
 
-    /// Set if quorum should be used or not
+    /// Set if quorum should be used or not.
+    /// Block, output and indexer reads (and with them balances) are then compared between `min_quorum_size` nodes.
     pub fn with_quorum(mut self, quorum: bool) -> Self {
         self.node_manager_builder = self.node_manager_builder.with_quorum(quorum);
         self
@@ -282,7 +462,10 @@ impl ClientBuilder {
         self
     }
 
-    /// Sets whether the PoW should be done locally or remotely.
+    /// Sets whether the PoW should be done locally or remotely. With remote PoW, blocks are only posted to nodes with
+    /// the `pow` feature and if none supports it, posting fails with
+    /// [`Error::RemotePowNotSupported`](crate::Error::RemotePowNotSupported) unless
+    /// [`ClientBuilder::with_fallback_to_local_pow()`] is enabled.
     pub fn with_local_pow(mut self, local: bool) -> Self {
         self.network_info.local_pow = local;
         self
@@ -294,6 +477,14 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the maximum time local PoW may take. If no valid nonce was found until then, building the block fails with
+    /// [`Error::PowDeadlineExceeded`](crate::Error::PowDeadlineExceeded), so remote PoW can be offered instead. Default
+    /// is no deadline. Without multi-threading the deadline is only checked every full second.
+    pub fn with_pow_deadline(mut self, pow_deadline: Duration) -> Self {
+        self.pow_deadline.replace(pow_deadline);
+        self
+    }
+
     /// Sets whether the PoW should be done locally in case a node doesn't support remote PoW.
     pub fn with_fallback_to_local_pow(mut self, fallback_to_local_pow: bool) -> Self {
         self.network_info.fallback_to_local_pow = fallback_to_local_pow;
@@ -318,6 +509,150 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets timeouts for categories of API requests, like a short one for tips, which override the API timeout. A
+    /// request that times out returns [`Error::Timeout`](crate::Error::Timeout).
+    pub fn with_api_timeouts(mut self, api_timeouts: ApiTimeouts) -> Self {
+        self.api_timeouts = api_timeouts;
+        self
+    }
+
+    /// Sets how batch requests like [`Client::get_outputs()`] handle failing items, default is
+    /// [`BatchErrorMode::Collect`], which returns the successful items together with the errors of the failed ones.
+    pub fn with_batch_error_mode(mut self, batch_error_mode: BatchErrorMode) -> Self {
+        self.batch_error_mode = batch_error_mode;
+        self
+    }
+
+    /// Sets how often a failed tips request is retried when building a block, with a delay that starts at `delay`
+    /// and doubles for every retry. Default is no retries. If all retries fail,
+    /// [`Error::TipsUnavailable`](crate::Error::TipsUnavailable) is returned.
+    pub fn with_tip_selection_retries(mut self, retries: u32, delay: Duration) -> Self {
+        self.tip_selection_retries = retries;
+        self.tip_selection_retry_delay = delay;
+        self
+    }
+
+    /// Sets the bech32 HRP the nodes are expected to report. On a mismatch syncing the nodes fails with
+    /// [`Error::HrpMismatch`](crate::Error::HrpMismatch) if `strict` is set, otherwise a warning is logged.
+    pub fn with_bech32_hrp(mut self, bech32_hrp: impl Into<String>, strict: bool) -> Self {
+        self.bech32_hrp.replace(bech32_hrp.into());
+        self.strict_bech32_hrp = strict;
+        self
+    }
+
+    /// Sets whether fields without a value are serialized as `null` in message interface responses. If disabled, all
+    /// `null` fields are omitted. Default is enabled, which keeps the fields as the types serialize them.
+    pub fn with_emit_nulls(mut self, emit_nulls: bool) -> Self {
+        self.emit_nulls = emit_nulls;
+        self
+    }
+
+    /// Sets how addresses are represented in message interface responses, so consumers that need the hex encoding
+    /// don't have to convert them. Default is [`AddressFormat::Bech32`].
+    pub fn with_address_format(mut self, address_format: AddressFormat) -> Self {
+        self.address_format = address_format;
+        self
+    }
+
+    /// Sets whether the tag and data of tagged data payloads in message interface responses are also added decoded as
+    /// UTF-8, as `tagUtf8` and `dataUtf8` next to the hex encoded fields. A field that isn't valid UTF-8 is only kept
+    /// hex encoded. Default is disabled.
+    pub fn with_decode_tagged_data_utf8(mut self, decode_tagged_data_utf8: bool) -> Self {
+        self.decode_tagged_data_utf8 = decode_tagged_data_utf8;
+        self
+    }
+
+    /// Sets a function that maps errors to user-friendly messages in message interface responses. It gets the error
+    /// and its kind, which is the `type` of the serialized error, and returning `None` keeps the original message.
+    /// Mapped errors are wrapped in an [`Error::Mapped`], so their `type` and `code` stay the same.
+    pub fn with_error_message_mapper<F>(mut self, mapper: F) -> Self
+    where
+        F: Fn(&Error, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.error_message_mapper = ErrorMessageMapperFn(Some(Arc::new(mapper)));
+        self
+    }
+
+    /// Sets user-friendly messages for errors in message interface responses, keyed by the `code` or `type` of the
+    /// error. A message for the code is used before one for the type, and a mapper set with
+    /// [`Self::with_error_message_mapper()`] is asked first.
+    pub fn with_error_messages(mut self, error_messages: HashMap<String, String>) -> Self {
+        self.error_messages = error_messages;
+        self
+    }
+
+    /// Sets how many addresses are generated and queried per batch when scanning the addresses of an account, like
+    /// for [`Client::export_utxo_snapshot()`], the automatic input selection and [`Client::get_next_unused_address()`].
+    /// It's also the default range of [`Client::get_addresses()`]. Smaller batches stop closer to the gap limit,
+    /// larger ones need less address generation round trips. Default is the gap limit of the scan, or 20 if there's
+    /// none.
+    pub fn with_address_scan_batch_size(mut self, batch_size: u32) -> Self {
+        self.address_scan_batch_size.replace(batch_size);
+        self
+    }
+
+    /// Sets the prefix of the names of the threads of the runtime, so they can be told apart when profiling. Worker
+    /// threads are named `{prefix}-worker-{n}` and blocking threads `{prefix}-blocking-{n}`. Default is "iota-client".
+    pub fn with_thread_name_prefix(mut self, thread_name_prefix: impl Into<String>) -> Self {
+        self.thread_name_prefix = thread_name_prefix.into();
+        self
+    }
+
+    /// Sets how many milestones are requested at once when fetching a range of milestones, like with
+    /// [`Client::get_milestones()`], to not overwhelm shared public nodes. Default is 5.
+    pub fn with_milestone_fetch_concurrency(mut self, concurrency: usize) -> Self {
+        self.milestone_fetch_concurrency = concurrency;
+        self
+    }
+
+    /// Sets how many outputs are requested at once when fetching multiple outputs, like with
+    /// [`Client::get_outputs()`], to not overwhelm the node. Default is 100.
+    pub fn with_output_fetch_concurrency(mut self, concurrency: usize) -> Self {
+        self.output_fetch_concurrency = concurrency;
+        self
+    }
+
+    /// Sets whether the inputs selected by a transaction are reserved until its block is confirmed, so that concurrent
+    /// transactions of this client, like from multiple workers of the same account, don't select the same inputs and
+    /// conflict. Reservations of sent blocks are released by [`Client::check_pending_blocks()`] or
+    /// [`Client::retry_until_included()`] and can be persisted with [`Client::export_pending_state()`]. Default is
+    /// disabled.
+    pub fn with_output_reservation(mut self, output_reservation: bool) -> Self {
+        self.output_reservation = output_reservation;
+        self
+    }
+
+    /// Sets whether a read request is sent to the next node if the response of a node can't be parsed, which can
+    /// happen during a rolling upgrade of the nodes. If all nodes fail or retrying is disabled,
+    /// [`Error::ResponseParse`](crate::Error::ResponseParse) with the raw response body is returned. Default is
+    /// enabled.
+    pub fn with_retry_on_parse_error(mut self, retry_on_parse_error: bool) -> Self {
+        self.node_manager_builder = self
+            .node_manager_builder
+            .with_retry_on_parse_error(retry_on_parse_error);
+        self
+    }
+
+    /// Sets that read requests, like getting blocks, outputs or the node info, are attempted up to `max_attempts` times
+    /// with `backoff` between the attempts if they fail with a transient error on all nodes, like a timeout or a 5xx
+    /// response. Posting blocks isn't idempotent and is never retried, see
+    /// [`Message::is_idempotent()`](crate::message_interface::Message::is_idempotent) for the read messages. Default is
+    /// no retries.
+    pub fn with_read_retry(mut self, max_attempts: u32, backoff: Duration) -> Self {
+        self.node_manager_builder = self
+            .node_manager_builder
+            .with_read_retry(ReadRetry { max_attempts, backoff });
+        self
+    }
+
+    /// Sets that before a block is posted, the node is checked to be healthy and that its confirmed milestone lags at
+    /// most `max_milestone_lag` milestones behind its latest milestone. Otherwise the block is posted to the next node,
+    /// so it doesn't get stuck on a node that's out of sync. Default is no check.
+    pub fn with_post_health_check(mut self, max_milestone_lag: u32) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_post_max_milestone_lag(max_milestone_lag);
+        self
+    }
+
     /// Set User-Agent header for requests
     /// Default is "iota-client/{version}"
     pub fn with_user_agent(mut self, user_agent: String) -> Self {
@@ -325,6 +660,13 @@ impl ClientBuilder {
         self
     }
 
+    /// Adds headers to every request, including the node syncing, like for an authenticating proxy in front of the
+    /// nodes. A JWT for a node is set with [`ClientBuilder::with_node_auth()`] instead.
+    pub fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.node_manager_builder = self.node_manager_builder.with_headers(headers);
+        self
+    }
+
     /// Build the Client instance.
     pub fn finish(self) -> Result<Client> {
         let network_info = Arc::new(RwLock::new(self.network_info));
@@ -342,16 +684,38 @@ impl ClientBuilder {
 
             let healthy_nodes_ = healthy_nodes.clone();
             let network_info_ = network_info.clone();
-
+            let sync_options = SyncOptions {
+                ignore_node_health: self.node_manager_builder.ignore_node_health,
+                bech32_hrp_check: self.bech32_hrp.clone().map(|hrp| Bech32HrpCheck {
+                    hrp,
+                    strict: self.strict_bech32_hrp,
+                }),
+                node_discovery: self.node_manager_builder.node_discovery.clone(),
+                http_client: self.node_manager_builder.http_client(),
+            };
+
+            let node_sync_disabled = self.node_manager_builder.node_sync_disabled;
             let (runtime, sync_handle) = std::thread::spawn(move || {
-                let runtime = Runtime::new().expect("failed to create Tokio runtime");
-                if let Err(e) = runtime.block_on(Client::sync_nodes(
+                let runtime = build_runtime(&RuntimeOptions {
+                    thread_name_prefix: self.thread_name_prefix,
+                    ..Default::default()
+                })
+                .expect("failed to create Tokio runtime");
+                if node_sync_disabled {
+                    // The network info isn't updated by the syncing, so it's fetched once
+                    runtime.block_on(Client::fetch_network_info(&nodes, &network_info_, &sync_options))?;
+                    return Ok((runtime, None));
+                }
+                match runtime.block_on(Client::sync_nodes(
                     &healthy_nodes_,
                     &nodes,
                     &network_info_,
-                    self.node_manager_builder.ignore_node_health,
+                    &sync_options,
                 )) {
-                    panic!("failed to sync nodes: {e:?}");
+                    // A mismatching bech32 HRP is caused by the configuration, so it's returned instead of panicking
+                    Err(e @ crate::Error::HrpMismatch { .. }) => return Err(e),
+                    Err(e) => panic!("failed to sync nodes: {e:?}"),
+                    Ok(()) => {}
                 }
                 let sync_handle = Client::start_sync_process(
                     &runtime,
@@ -359,13 +723,13 @@ impl ClientBuilder {
                     nodes,
                     self.node_manager_builder.node_sync_interval,
                     network_info_,
-                    self.node_manager_builder.ignore_node_health,
+                    sync_options,
                 );
-                (runtime, sync_handle)
+                Ok((runtime, Some(sync_handle)))
             })
             .join()
-            .expect("failed to init node syncing process");
-            (Some(Arc::new(runtime)), Some(sync_handle))
+            .expect("failed to init node syncing process")?;
+            (Some(Arc::new(runtime)), sync_handle)
         };
 
         #[cfg(feature = "mqtt")]
@@ -384,11 +748,120 @@ impl ClientBuilder {
             broker_options: self.broker_options,
             #[cfg(feature = "mqtt")]
             mqtt_event_channel: (Arc::new(mqtt_event_tx), mqtt_event_rx),
+            #[cfg(feature = "mqtt")]
+            mqtt_unsubscribe_acks: Default::default(),
             network_info,
             api_timeout: self.api_timeout,
             remote_pow_timeout: self.remote_pow_timeout,
+            api_timeouts: self.api_timeouts,
             pow_worker_count: self.pow_worker_count,
+            pow_deadline: self.pow_deadline,
+            batch_error_mode: self.batch_error_mode,
+            tip_selection_retries: self.tip_selection_retries,
+            tip_selection_retry_delay: self.tip_selection_retry_delay,
+            emit_nulls: self.emit_nulls,
+            address_format: self.address_format,
+            decode_tagged_data_utf8: self.decode_tagged_data_utf8,
+            address_scan_batch_size: self.address_scan_batch_size,
+            milestone_fetch_concurrency: self.milestone_fetch_concurrency,
+            output_fetch_concurrency: self.output_fetch_concurrency,
+            output_reservations: self.output_reservation.then(OutputReservations::default),
+            unused_address_indexes: Default::default(),
+            error_messages: self.error_messages,
+            error_message_mapper: self.error_message_mapper.0,
         };
         Ok(client)
     }
 }
+
+/// Options for the runtimes created by [`build_runtime()`].
+#[cfg(not(target_family = "wasm"))]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeOptions {
+    /// The number of worker threads, defaults to the number of CPUs.
+    #[serde(default)]
+    pub worker_threads: Option<usize>,
+    /// Prefix of the thread names, worker threads are named `{prefix}-worker-{n}` and blocking threads
+    /// `{prefix}-blocking-{n}`. Defaults to "iota-client".
+    #[serde(default = "default_thread_name_prefix")]
+    pub thread_name_prefix: String,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Default for RuntimeOptions {
+    fn default() -> Self {
+        Self {
+            worker_threads: None,
+            thread_name_prefix: DEFAULT_THREAD_NAME_PREFIX.to_string(),
+        }
+    }
+}
+
+/// Builds a multi-threaded runtime with all drivers enabled, whose threads are named according to
+/// [`RuntimeOptions::thread_name_prefix`].
+#[cfg(not(target_family = "wasm"))]
+pub fn build_runtime(options: &RuntimeOptions) -> std::io::Result<Runtime> {
+    let worker_threads = match options.worker_threads {
+        Some(0) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "workerThreads must be greater than 0",
+            ));
+        }
+        Some(worker_threads) => worker_threads,
+        None => std::thread::available_parallelism().map_or(1, usize::from),
+    };
+    let thread_name_prefix = options.thread_name_prefix.clone();
+    let thread_count = AtomicUsize::new(0);
+
+    Builder::new_multi_thread()
+        .enable_all()
+        .worker_threads(worker_threads)
+        // The worker threads are started first when the runtime is built, all later threads are blocking threads
+        .thread_name_fn(move || {
+            let index = thread_count.fetch_add(1, Ordering::SeqCst);
+            if index < worker_threads {
+                format!("{thread_name_prefix}-worker-{index}")
+            } else {
+                format!("{thread_name_prefix}-blocking-{}", index - worker_threads)
+            }
+        })
+        .build()
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_thread_names() {
+        let runtime = build_runtime(&RuntimeOptions {
+            worker_threads: Some(2),
+            thread_name_prefix: "custom-prefix".to_string(),
+        })
+        .unwrap();
+
+        let thread_name = runtime
+            .block_on(runtime.spawn(async { std::thread::current().name().map(ToString::to_string) }))
+            .unwrap()
+            .unwrap();
+        assert!(thread_name.starts_with("custom-prefix-worker-"), "{thread_name}");
+
+        let thread_name = runtime
+            .block_on(runtime.spawn_blocking(|| std::thread::current().name().map(ToString::to_string)))
+            .unwrap()
+            .unwrap();
+        assert!(thread_name.starts_with("custom-prefix-blocking-"), "{thread_name}");
+    }
+
+    #[test]
+    fn runtime_without_worker_threads() {
+        let options = RuntimeOptions {
+            worker_threads: Some(0),
+            ..Default::default()
+        };
+
+        assert!(build_runtime(&options).is_err());
+    }
+}
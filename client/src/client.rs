@@ -4,24 +4,26 @@
 //! The Client module to connect through HORNET or Bee with API usages
 
 use std::{
-    sync::{Arc, RwLock},
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
     time::Duration,
 };
 
-use iota_types::block::{output::RentStructure, protocol::ProtocolParameters};
+use iota_types::block::{address::Address, output::RentStructure, protocol::ProtocolParameters};
 #[cfg(not(target_family = "wasm"))]
 use tokio::runtime::Runtime;
 #[cfg(feature = "mqtt")]
 use {
-    crate::node_api::mqtt::{BrokerOptions, MqttEvent, TopicHandlerMap},
+    crate::node_api::mqtt::{BrokerOptions, MqttEvent, TopicHandlerMap, UnsubscribeAcks},
     rumqttc::AsyncClient as MqttClient,
     tokio::sync::watch::{Receiver as WatchReceiver, Sender as WatchSender},
 };
 
 use crate::{
-    builder::{ClientBuilder, NetworkInfo},
+    api::{input_selection::required_storage_deposit_basic_outputs, BatchErrorMode, OutputReservations},
+    builder::{AddressFormat, ApiTimeouts, ClientBuilder, NetworkInfo},
     constants::DEFAULT_TIPS_INTERVAL,
-    error::Result,
+    error::{ErrorMessageMapper, Result},
 };
 
 /// An instance of the client using HORNET or Bee URI
@@ -44,14 +46,53 @@ pub struct Client {
     pub(crate) broker_options: BrokerOptions,
     #[cfg(feature = "mqtt")]
     pub(crate) mqtt_event_channel: (Arc<WatchSender<MqttEvent>>, WatchReceiver<MqttEvent>),
+    /// Unsubscribe requests that wait for the acknowledgement of the MQTT broker.
+    #[cfg(feature = "mqtt")]
+    pub(crate) mqtt_unsubscribe_acks: Arc<UnsubscribeAcks>,
     pub(crate) network_info: Arc<RwLock<NetworkInfo>>,
     /// HTTP request timeout.
     pub(crate) api_timeout: Duration,
     /// HTTP request timeout for remote PoW API call.
     pub(crate) remote_pow_timeout: Duration,
+    /// HTTP request timeouts for categories of API calls.
+    pub(crate) api_timeouts: ApiTimeouts,
     #[allow(dead_code)] // not used for wasm
     /// pow_worker_count for local PoW.
     pub(crate) pow_worker_count: Option<usize>,
+    /// Maximum time local PoW may take.
+    pub(crate) pow_deadline: Option<Duration>,
+    /// How batch requests handle failing items.
+    pub(crate) batch_error_mode: BatchErrorMode,
+    /// How often a failed tips request is retried when building a block.
+    pub(crate) tip_selection_retries: u32,
+    /// Delay before the first tips request retry.
+    pub(crate) tip_selection_retry_delay: Duration,
+    /// Whether fields without a value are serialized as `null` in message interface responses.
+    #[cfg_attr(not(feature = "message_interface"), allow(dead_code))]
+    pub(crate) emit_nulls: bool,
+    /// How addresses are represented in message interface responses.
+    #[cfg_attr(not(feature = "message_interface"), allow(dead_code))]
+    pub(crate) address_format: AddressFormat,
+    /// Whether tagged data payloads in message interface responses also contain their tag and data decoded as UTF-8.
+    #[cfg_attr(not(feature = "message_interface"), allow(dead_code))]
+    pub(crate) decode_tagged_data_utf8: bool,
+    /// How many addresses are queried per batch when scanning the addresses of an account.
+    pub(crate) address_scan_batch_size: Option<u32>,
+    /// How many milestones are requested at once when fetching a range of milestones.
+    pub(crate) milestone_fetch_concurrency: usize,
+    /// How many outputs are requested at once when fetching multiple outputs.
+    #[cfg_attr(target_family = "wasm", allow(dead_code))]
+    pub(crate) output_fetch_concurrency: usize,
+    /// Inputs reserved by in-flight transactions, if reserving is enabled.
+    pub(crate) output_reservations: Option<OutputReservations>,
+    /// Highest address index handed out by `get_next_unused_address()`, keyed by the first address of the account.
+    pub(crate) unused_address_indexes: Arc<RwLock<HashMap<Address, u32>>>,
+    /// User-friendly messages for errors in message interface responses, keyed by error code or type.
+    #[cfg_attr(not(feature = "message_interface"), allow(dead_code))]
+    pub(crate) error_messages: HashMap<String, String>,
+    /// Maps errors to user-friendly messages in message interface responses.
+    #[cfg_attr(not(feature = "message_interface"), allow(dead_code))]
+    pub(crate) error_message_mapper: Option<Arc<ErrorMessageMapper>>,
 }
 
 impl std::fmt::Debug for Client {
@@ -113,11 +154,11 @@ impl Client {
         #[cfg(target_family = "wasm")]
         {
             let info = self.get_info().await?.node_info;
-            let mut client_network_info = self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
+            let mut client_network_info = self.network_info.write().unwrap_or_else(PoisonError::into_inner);
             client_network_info.protocol_parameters = info.protocol.try_into()?;
         }
 
-        Ok(self.network_info.read().map_err(|_| crate::Error::PoisonError)?.clone())
+        Ok(self.network_info.read().unwrap_or_else(PoisonError::into_inner).clone())
     }
 
     /// Gets the protocol parameters of the node we're connecting to.
@@ -170,6 +211,18 @@ impl Client {
         Ok(self.get_network_info().await?.protocol_parameters.token_supply())
     }
 
+    /// Computes the total storage deposit of `output_count` basic outputs with only an address unlock condition with
+    /// the rent structure of the node we're connecting to.
+    pub async fn get_required_storage_deposit(&self, output_count: u32) -> Result<u64> {
+        let protocol_parameters = self.get_protocol_parameters().await?;
+
+        required_storage_deposit_basic_outputs(
+            protocol_parameters.rent_structure(),
+            output_count,
+            protocol_parameters.token_supply(),
+        )
+    }
+
     /// returns the tips interval
     pub fn get_tips_interval(&self) -> u64 {
         self.network_info
@@ -181,7 +234,8 @@ impl Client {
     pub fn get_local_pow(&self) -> bool {
         self.network_info
             .read()
-            .map_or(NetworkInfo::default().local_pow, |info| info.local_pow)
+            .unwrap_or_else(PoisonError::into_inner)
+            .local_pow
     }
 
     pub(crate) fn get_timeout(&self) -> Duration {
@@ -192,12 +246,61 @@ impl Client {
         self.remote_pow_timeout
     }
 
+    pub(crate) fn get_info_timeout(&self) -> Duration {
+        self.api_timeouts.info.unwrap_or(self.api_timeout)
+    }
+
+    pub(crate) fn get_tips_timeout(&self) -> Duration {
+        self.api_timeouts.tips.unwrap_or(self.api_timeout)
+    }
+
+    pub(crate) fn get_post_block_timeout(&self) -> Duration {
+        self.api_timeouts.post_block.unwrap_or(self.api_timeout)
+    }
+
+    pub(crate) fn get_output_timeout(&self) -> Duration {
+        self.api_timeouts.output.unwrap_or(self.api_timeout)
+    }
+
+    // Returns how many addresses are generated and queried per batch when scanning addresses, `default` if it isn't
+    // set.
+    pub(crate) fn get_address_scan_batch_size(&self, default: u32) -> u32 {
+        self.address_scan_batch_size.unwrap_or(default).max(1)
+    }
+
+    /// Returns how batch requests handle failing items.
+    pub fn get_batch_error_mode(&self) -> BatchErrorMode {
+        self.batch_error_mode
+    }
+
     /// returns the fallback_to_local_pow
     pub fn get_fallback_to_local_pow(&self) -> bool {
         self.network_info
             .read()
-            .map_or(NetworkInfo::default().fallback_to_local_pow, |info| {
-                info.fallback_to_local_pow
-            })
+            .unwrap_or_else(PoisonError::into_inner)
+            .fallback_to_local_pow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn network_info_recovers_from_poisoning() {
+        let client = Client::builder().with_local_pow(true).finish().unwrap();
+
+        // Panic while holding the lock, which poisons it
+        let network_info = client.network_info.clone();
+        std::thread::spawn(move || {
+            let _guard = network_info.write().unwrap();
+            panic!("panic while holding the network info lock");
+        })
+        .join()
+        .unwrap_err();
+        assert!(client.network_info.is_poisoned());
+
+        assert!(client.get_local_pow());
+        assert!(client.get_network_info().await.is_ok());
     }
 }
@@ -15,11 +15,30 @@ pub(crate) const DEFAULT_RETRY_UNTIL_INCLUDED_MAX_AMOUNT: u64 = 40;
 pub(crate) const DEFAULT_TIPS_INTERVAL: u64 = 5;
 /// Interval in which the node info will be requested and healthy nodes will be added to the healthy node pool
 pub(crate) const NODE_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+/// Default port of the REST API of a node, used for nodes discovered from the peers of a seed node
+pub(crate) const DEFAULT_NODE_API_PORT: u16 = 14265;
 pub(crate) const DEFAULT_MIN_QUORUM_SIZE: usize = 3;
 pub(crate) const DEFAULT_QUORUM_THRESHOLD: usize = 66;
+/// Default prefix of the names of the runtime worker threads
+pub const DEFAULT_THREAD_NAME_PREFIX: &str = "iota-client";
 pub(crate) const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 #[cfg(not(target_family = "wasm"))]
 pub(crate) const MAX_PARALLEL_API_REQUESTS: usize = 100;
+/// Default amount of milestones that are requested at once when fetching a range of milestones
+pub(crate) const DEFAULT_MILESTONE_FETCH_CONCURRENCY: usize = 5;
+/// Default amount of outputs that are requested at once when fetching multiple outputs
+pub(crate) const DEFAULT_OUTPUT_FETCH_CONCURRENCY: usize = 100;
+/// Max amount of milestones that can be requested with one range
+pub(crate) const MAX_MILESTONE_RANGE: u32 = 1000;
+/// Max amount of milestones whose changes are included in one address balance delta
+pub(crate) const MAX_BALANCE_DELTA_MILESTONES: u32 = 100;
+/// Max amount of block metadata requests when searching for a confirmed ancestor of a block
+pub(crate) const MAX_CONFIRMED_ANCESTOR_REQUESTS: usize = 1000;
+/// Default delay before the first retry of a failed tips request, doubled for every further retry
+pub(crate) const DEFAULT_TIP_SELECTION_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Default amount of consecutive addresses without outputs after which address scanning stops
+#[cfg(feature = "message_interface")]
+pub(crate) const DEFAULT_GAP_LIMIT: u32 = 20;
 /// Max allowed difference between the local time and latest milestone time, 5 minutes in seconds
 pub(crate) const FIVE_MINUTES_IN_SECONDS: u32 = 300;
 
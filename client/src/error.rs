@@ -5,7 +5,7 @@
 
 use std::fmt::Debug;
 
-use iota_types::block::semantic::ConflictReason;
+use iota_types::block::{semantic::ConflictReason, BlockId};
 use packable::error::UnexpectedEOF;
 use serde::{
     ser::{SerializeMap, Serializer},
@@ -17,6 +17,10 @@ use crate::{api::input_selection::Error as InputSelectionError, node_api::indexe
 /// Type alias of `Result` in iota-client
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// A function mapping an error and its kind (the `type` of the serialized error) to a user-friendly message.
+/// Returning `None` keeps the original message.
+pub type ErrorMessageMapper = dyn Fn(&Error, &str) -> Option<String> + Send + Sync;
+
 /// Error type of the iota client crate.
 #[derive(Debug, thiserror::Error)]
 #[allow(clippy::large_enum_variant)]
@@ -24,6 +28,9 @@ pub enum Error {
     /// Block dtos error
     #[error("{0}")]
     ApiTypes(#[from] iota_types::api::core::error::Error),
+    /// Errors of the failed items of a batch request, together with the index of the item
+    #[error("{} batch request(s) failed: {}", .0.len(), fmt_batch_errors(.0))]
+    Batch(Vec<(usize, Self)>),
     /// Blake2b256 Error
     #[error("{0}")]
     Blake2b256(&'static str),
@@ -39,6 +46,14 @@ pub enum Error {
     /// Crypto.rs error
     #[error("{0}")]
     Crypto(#[from] crypto::Error),
+    /// The node or an address has a different bech32 HRP than the configured or expected one
+    #[error("found the bech32 HRP `{found}`, but `{expected}` is expected")]
+    HrpMismatch {
+        /// The configured or expected bech32 HRP.
+        expected: String,
+        /// The bech32 HRP reported by the node or of the address.
+        found: String,
+    },
     /// Address not found
     #[error("address: {address} not found in range: {range}")]
     InputAddressNotFound {
@@ -47,6 +62,9 @@ pub enum Error {
         /// The range in which the address was not found.
         range: String,
     },
+    /// An input set on the block builder is already spent
+    #[error("input {0} is already spent")]
+    InputAlreadySpent(String),
     /// Invalid amount in API response
     #[error("invalid amount in API response: {0}")]
     InvalidAmount(String),
@@ -75,6 +93,25 @@ pub enum Error {
     /// JSON error
     #[error("{0}")]
     Json(#[from] serde_json::Error),
+    /// An error whose message got replaced by a user provided mapping, the underlying error is kept
+    #[error("{message}")]
+    Mapped {
+        /// The original error.
+        #[source]
+        error: Box<Self>,
+        /// The user-friendly message.
+        message: String,
+    },
+    /// The range of milestones to request at once is too large
+    #[error("the milestone range {start}..={end} exceeds the max. of {max} milestones")]
+    MilestoneRangeTooLarge {
+        /// The first index of the range
+        start: u32,
+        /// The last index of the range
+        end: u32,
+        /// The max. amount of milestones in a range
+        max: u32,
+    },
     /// Missing required parameters
     #[error("must provide required parameter: {0}")]
     MissingParameter(&'static str),
@@ -84,12 +121,37 @@ pub enum Error {
     /// The block doesn't need to be promoted or reattached
     #[error("block ID `{0}` doesn't need to be promoted or reattached")]
     NoNeedPromoteOrReattach(String),
+    /// The block isn't referenced by a milestone yet
+    #[error("block ID `{0}` isn't referenced by a milestone yet")]
+    NotConfirmed(String),
     /// The requested data was not found.
     #[error("the requested data {0} was not found.")]
     NotFound(String),
     /// Output Error
     #[error("output error: {0}")]
     Output(&'static str),
+    /// The amount of a single output exceeds the amount cap per block
+    #[error("output amount {amount} exceeds the amount cap of {cap} per block")]
+    OutputAmountAboveCap {
+        /// The amount of the output
+        amount: u64,
+        /// The amount cap per block
+        cap: u64,
+    },
+    /// Output reservation isn't enabled in the client
+    #[error("output reservation isn't enabled, see `ClientBuilder::with_output_reservation()`")]
+    OutputReservationDisabled,
+    /// Sending multiple blocks failed after some of them were already sent
+    #[error("{error}, after sending the block(s) {}", fmt_block_ids(.block_ids))]
+    PartiallySent {
+        /// The IDs of the blocks that were already sent
+        block_ids: Vec<BlockId>,
+        /// The error that stopped sending the remaining blocks
+        error: Box<Self>,
+    },
+    /// The node doesn't know the peer
+    #[error("peer {0} not found")]
+    PeerNotFound(String),
     /// PlaceholderSecretManager can't be used for address generation or signing
     #[error("placeholderSecretManager can't be used for address generation or signing")]
     PlaceholderSecretManager,
@@ -99,9 +161,18 @@ pub enum Error {
     /// PoW error
     #[error("{0}")]
     Pow(String),
+    /// Local PoW didn't find a valid nonce within the configured deadline
+    #[error("local PoW didn't find a valid nonce within {0:?}")]
+    PowDeadlineExceeded(std::time::Duration),
+    /// Only promotion is allowed, but the block can't be promoted anymore
+    #[error("block ID `{0}` can't be promoted and would need to be reattached")]
+    PromotionNotPossible(String),
     /// Prefix hex string convert error
     #[error("{0}")]
     PrefixHex(#[from] prefix_hex::Error),
+    /// The requested data has been pruned by the node
+    #[error("{0} has been pruned by the node")]
+    Pruned(String),
     /// Error on quorum because not enough nodes are available
     #[error("not enough nodes for quorum: {available_nodes} < {minimum_threshold}")]
     QuorumPoolSizeError {
@@ -117,6 +188,21 @@ pub enum Error {
         quorum_size: usize,
         /// The minimum quorum threshold.
         minimum_threshold: usize,
+        /// The distinct responses of the nodes, with the number of nodes that returned each.
+        responses: Vec<(String, usize)>,
+    },
+    /// Remote PoW is used, but no node supports it and falling back to local PoW is disabled
+    #[error("remote PoW isn't supported by the nodes: {0}, enable local PoW or the fallback to it")]
+    RemotePowNotSupported(String),
+    /// The response of a node couldn't be deserialized
+    #[error("couldn't parse the response from {url}: {error}")]
+    ResponseParse {
+        /// The url of the API.
+        url: String,
+        /// The deserialization error.
+        error: String,
+        /// The raw response body.
+        body: String,
     },
     /// Error from RestAPI calls with unexpected status code response
     #[error("response error with status code {code}: {text}, URL: {url}")]
@@ -157,15 +243,37 @@ pub enum Error {
         /// The timestamp of the latest milestone.
         milestone_timestamp: u32,
     },
+    /// The request to the node timed out
+    #[error("the request to {0} timed out")]
+    Timeout(String),
+    /// The node couldn't provide tips for a new block, also after retrying
+    #[error("no tips after {attempts} attempt(s): {error}")]
+    TipsUnavailable {
+        /// The number of tips requests.
+        attempts: u32,
+        /// The error of the last request.
+        error: Box<Self>,
+    },
     /// The semantic validation of a transaction failed.
     #[error("the semantic validation of a transaction failed with conflict reason: {} - {0:?}", *.0 as u8)]
     TransactionSemantic(ConflictReason),
+    /// The node denied the request, because the route requires authentication or isn't permitted
+    #[error("the node denied access to {0}, check the node authentication")]
+    Unauthorized(String),
     /// Unexpected API response error
     #[error("unexpected API response")]
     UnexpectedApiResponse,
     /// An indexer API request contains a query parameter not supported by the endpoint.
     #[error("an indexer API request contains a query parameter not supported by the endpoint: {0}.")]
     UnsupportedQueryParameter(QueryParameter),
+    /// The unlock condition isn't supported on basic outputs by the protocol version of the node
+    #[error("unlock condition kind {kind} isn't supported on basic outputs by protocol version {protocol_version}")]
+    UnsupportedUnlockCondition {
+        /// The kind of the unlock condition.
+        kind: u8,
+        /// The protocol version of the node.
+        protocol_version: u8,
+    },
     /// Unpack error
     #[error("{0}")]
     Unpack(#[from] packable::error::UnpackError<iota_types::block::Error, UnexpectedEOF>),
@@ -263,6 +371,18 @@ pub enum Error {
     StrongholdProcedure(#[from] iota_stronghold::procedures::ProcedureError),
 }
 
+fn fmt_block_ids(block_ids: &[BlockId]) -> String {
+    block_ids.iter().map(BlockId::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn fmt_batch_errors(errors: &[(usize, Error)]) -> String {
+    errors
+        .iter()
+        .map(|(index, error)| format!("[{index}] {error}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 // map most errors to a single error but there are some errors that
 // need special care.
 // LedgerDongleLocked: Ask the user to unlock the dongle
@@ -284,13 +404,13 @@ impl From<iota_ledger_nano::api::errors::APIError> for Error {
     }
 }
 
-// Serialize type with Display error
-impl Serialize for Error {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_map(Some(2))?;
+impl Error {
+    /// Returns the camelCase name of the error variant, as used for the `type` field of the serialized error.
+    /// For a [`Error::Mapped`] error, the name of the original error is returned.
+    pub fn kind(&self) -> String {
+        if let Self::Mapped { error, .. } = self {
+            return error.kind();
+        }
         let mut kind_dbg = format!("{self:?}");
         // Convert first char to lowercase
         if let Some(r) = kind_dbg.get_mut(0..1) {
@@ -298,9 +418,80 @@ impl Serialize for Error {
         }
         // Split by whitespace for struct variants and split by `(` for tuple variants
         // Safe to unwrap because kind_dbg is never an empty string
-        let kind = kind_dbg.split([' ', '(']).next().unwrap();
-        seq.serialize_entry("type", &kind)?;
+        kind_dbg.split([' ', '(']).next().unwrap().to_string()
+    }
+
+    /// Returns a machine-readable code for common errors that can be handled, like by retrying later or asking the
+    /// user for more funds. It's the `code` field of the serialized error.
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            Self::Mapped { error, .. } | Self::PartiallySent { error, .. } => error.code(),
+            Self::InputSelection(
+                crate::api::input_selection::Error::InsufficientAmount { .. }
+                | crate::api::input_selection::Error::InsufficientNativeTokenAmount { .. },
+            ) => Some("insufficientFunds"),
+            Self::InputAlreadySpent(_) => Some("inputAlreadySpent"),
+            Self::RemotePowNotSupported(_) => Some("remotePowNotSupported"),
+            Self::HealthyNodePoolEmpty => Some("noHealthyNode"),
+            Self::TimeNotSynced { .. } => Some("timeNotSynced"),
+            Self::NotFound(_) => Some("notFound"),
+            Self::Unauthorized(_) => Some("unauthorized"),
+            Self::Timeout(_) => Some("timeout"),
+            Self::TipsUnavailable { .. } => Some("nodeUnavailable"),
+            Self::ResponseError { code: 429, .. } => Some("rateLimited"),
+            Self::ResponseError { code, .. } if *code >= 500 => Some("nodeUnavailable"),
+            _ => None,
+        }
+    }
+
+    /// Returns whether a request could succeed when it's sent again later, like for an overloaded or unreachable node.
+    pub(crate) fn is_transient(&self) -> bool {
+        match self {
+            Self::HealthyNodePoolEmpty | Self::Timeout(_) => true,
+            Self::ResponseError { code, .. } => *code >= 500 || *code == 429,
+            #[cfg(not(target_family = "wasm"))]
+            Self::Reqwest(e) => e.is_timeout() || e.is_connect(),
+            #[cfg(target_family = "wasm")]
+            Self::Reqwest(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Wraps the error in [`Error::PartiallySent`] if blocks were already sent.
+    pub(crate) fn with_sent_blocks(self, block_ids: &[BlockId]) -> Self {
+        if block_ids.is_empty() {
+            self
+        } else {
+            Self::PartiallySent {
+                block_ids: block_ids.to_vec(),
+                error: Box::new(self),
+            }
+        }
+    }
+}
+
+// Serialize type with Display error
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let code = self.code();
+        let block_ids = match self {
+            Self::PartiallySent { block_ids, .. } => Some(block_ids),
+            _ => None,
+        };
+        let mut seq =
+            serializer.serialize_map(Some(2 + usize::from(code.is_some()) + usize::from(block_ids.is_some())))?;
+        seq.serialize_entry("type", &self.kind())?;
         seq.serialize_entry("error", &self.to_string())?;
+        if let Some(code) = code {
+            seq.serialize_entry("code", code)?;
+        }
+        // The blocks that were sent before the error, so they can be tracked
+        if let Some(block_ids) = block_ids {
+            seq.serialize_entry("blockIds", block_ids)?;
+        }
         seq.end()
     }
 }
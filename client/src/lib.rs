@@ -72,10 +72,10 @@ pub use url::Url;
 #[cfg(feature = "mqtt")]
 pub use self::node_api::mqtt;
 pub use self::{
-    builder::{ClientBuilder, NetworkInfo, NetworkInfoDto},
+    builder::{AddressFormat, ApiTimeouts, ClientBuilder, NetworkInfo, NetworkInfoDto},
     client::*,
     error::*,
-    node_api::core::routes::NodeInfoWrapper,
+    node_api::core::routes::{NodeHealth, NodeInfoWrapper},
     utils::*,
 };
 
@@ -86,10 +86,14 @@ mod async_runtime {
     use once_cell::sync::OnceCell;
     use tokio::runtime::Runtime;
 
+    use crate::builder::{build_runtime, RuntimeOptions};
+
     static RUNTIME: OnceCell<Mutex<Runtime>> = OnceCell::new();
 
     pub(crate) fn block_on<C: futures::Future>(cb: C) -> C::Output {
-        let runtime = RUNTIME.get_or_init(|| Mutex::new(Runtime::new().expect("failed to create Tokio runtime")));
+        let runtime = RUNTIME.get_or_init(|| {
+            Mutex::new(build_runtime(&RuntimeOptions::default()).expect("failed to create Tokio runtime"))
+        });
         runtime.lock().expect("failed to lock the runtime.").block_on(cb)
     }
 
@@ -98,7 +102,9 @@ mod async_runtime {
         F: futures::Future + Send + 'static,
         F::Output: Send + 'static,
     {
-        let runtime = RUNTIME.get_or_init(|| Mutex::new(Runtime::new().expect("failed to create Tokio runtime")));
+        let runtime = RUNTIME.get_or_init(|| {
+            Mutex::new(build_runtime(&RuntimeOptions::default()).expect("failed to create Tokio runtime"))
+        });
         runtime.lock().expect("failed to lock the runtime.").spawn(future);
     }
 }
@@ -0,0 +1,13 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::api::DoubleSpend;
+
+/// An event passed to the handler of a watch, like
+/// [`ClientMessageHandler::watch_double_spends()`](crate::message_interface::ClientMessageHandler::watch_double_spends).
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", content = "payload", rename_all = "camelCase")]
+pub enum Event {
+    /// A watched output got spent by another transaction than the expected one.
+    DoubleSpendDetected(DoubleSpend),
+}
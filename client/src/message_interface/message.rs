@@ -4,7 +4,7 @@
 use iota_types::block::{
     address::AliasAddress,
     output::{
-        dto::{AliasIdDto, NativeTokenDto, NftIdDto, TokenSchemeDto},
+        dto::{AliasIdDto, NativeTokenDto, NftIdDto, OutputDto, TokenSchemeDto},
         feature::dto::FeatureDto,
         unlock_condition::dto::UnlockConditionDto,
         AliasId, FoundryId, NftId, OutputId,
@@ -25,8 +25,9 @@ use serde::Deserialize;
 use crate::mqtt::Topic;
 use crate::{
     api::{
-        ClientBlockBuilderOptions as BuildBlockOptions, GetAddressesBuilderOptions as GenerateAddressesOptions,
-        PreparedTransactionDataDto, RemainderDataDto,
+        ClientBlockBuilderOptions as BuildBlockOptions, ConsolidationCheckpoint,
+        GetAddressesBuilderOptions as GenerateAddressesOptions, PayloadType, PendingState, PreparedTransactionDataDto,
+        RemainderDataDto, RetryStrategy, TransactionInclusionProofDto,
     },
     node_api::indexer::query_parameters::QueryParameter,
     node_manager::node::NodeAuth,
@@ -105,7 +106,8 @@ pub enum Message {
         #[serde(rename = "immutableFeatures")]
         immutable_features: Option<Vec<FeatureDto>>,
     },
-    /// Removes all listeners for the provided topics.
+    /// Removes all listeners for the provided topics, or for all topics if none are provided. Topics without
+    /// listeners are ignored.
     /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
     #[cfg(feature = "mqtt")]
     #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
@@ -113,6 +115,11 @@ pub enum Message {
         /// Topics for which listeners should be removed.
         topics: Vec<Topic>,
     },
+    /// Returns the topics with listeners.
+    /// Expected response: [`Topics`](crate::message_interface::Response::Topics)
+    #[cfg(feature = "mqtt")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
+    GetSubscribedTopics,
     /// Generate addresses.
     GenerateAddresses {
         /// Create secret manager from json
@@ -121,6 +128,30 @@ pub enum Message {
         /// Addresses generation options
         options: GenerateAddressesOptions,
     },
+    /// Generate the public and internal addresses of the range, interleaved by index with the public address first.
+    /// The `internal` option is ignored.
+    /// Expected response: [`GeneratedAllAddresses`](crate::message_interface::Response::GeneratedAllAddresses)
+    GenerateAllAddresses {
+        /// Create secret manager from json
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Addresses generation options
+        options: GenerateAddressesOptions,
+    },
+    /// Get the next public address of an account without any outputs. An address is never returned twice during the
+    /// lifetime of the client.
+    /// Expected response: [`Bech32Address`](crate::message_interface::Response::Bech32Address)
+    GetNextUnusedAddress {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Coin type, defaults to the Shimmer coin type
+        #[serde(rename = "coinType")]
+        coin_type: Option<u32>,
+        /// Account index
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+    },
     /// Build and post a block
     BuildAndPostBlock {
         /// Secret manager
@@ -129,6 +160,15 @@ pub enum Message {
         /// Options
         options: Option<BuildBlockOptions>,
     },
+    /// Post a block with only a tagged data payload, to store data without moving funds. The tag and the data are
+    /// decoded as hex if they start with `0x`, otherwise their UTF-8 bytes are used. The tag must not be empty.
+    /// Expected response: [`BlockId`](crate::message_interface::Response::BlockId)
+    PostTaggedData {
+        /// Tag
+        tag: String,
+        /// Data
+        data: String,
+    },
     /// Get a node candidate from the healthy node pool.
     GetNode,
     /// Gets the network related information such as network_id and min_pow_score
@@ -143,6 +183,12 @@ pub enum Message {
     GetTipsInterval,
     /// Returns the protocol parameters
     GetProtocolParameters,
+    /// Returns the total storage deposit of a number of basic outputs with only an address unlock condition
+    ComputeRequiredStorageDeposit {
+        /// Number of basic outputs
+        #[serde(rename = "outputCount")]
+        output_count: u32,
+    },
     /// Returns if local pow should be used or not
     GetLocalPow,
     /// Get fallback to local proof of work timeout
@@ -150,6 +196,10 @@ pub enum Message {
     /// Returns the unhealthy nodes.
     #[cfg(not(target_family = "wasm"))]
     UnhealthyNodes,
+    /// Returns if the node syncing is enabled and the synced nodes.
+    /// Expected response: [`NodeSyncStatus`](crate::message_interface::Response::NodeSyncStatus)
+    #[cfg(not(target_family = "wasm"))]
+    GetNodeSyncStatus,
     /// Get the ledger status
     /// Expected response: [`LedgerNanoStatus`](crate::message_interface::Response::LedgerNanoStatus)
     #[cfg(feature = "ledger_nano")]
@@ -216,6 +266,9 @@ pub enum Message {
         /// Url
         url: String,
     },
+    /// Get the health of every configured node
+    /// Expected response: [`NodesHealth`](crate::message_interface::Response::NodesHealth)
+    GetNodesHealth,
     /// Get node info
     GetNodeInfo {
         /// Url
@@ -225,8 +278,32 @@ pub enum Message {
     },
     /// Returns the node information together with the url of the used node
     GetInfo,
+    /// Returns the index and timestamp of the latest confirmed milestone, and if the local time differs too much
+    GetLatestMilestoneTimestamp,
     /// Get peers
     GetPeers,
+    /// Get a peer by its peer ID, including its gossip metrics
+    GetPeer {
+        /// Peer ID
+        #[serde(rename = "peerId")]
+        peer_id: String,
+    },
+    /// Add a peer to the node
+    /// Expected response: [`Peer`](crate::message_interface::Response::Peer)
+    AddPeer {
+        /// Multi address of the peer
+        #[serde(rename = "multiAddress")]
+        multi_address: String,
+        /// Alias of the peer
+        alias: Option<String>,
+    },
+    /// Remove a peer from the node
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    RemovePeer {
+        /// Peer ID
+        #[serde(rename = "peerId")]
+        peer_id: String,
+    },
     /// Get tips
     GetTips,
     /// Post block (JSON)
@@ -234,6 +311,11 @@ pub enum Message {
         /// Block
         block: BlockDto,
     },
+    /// Post block given as JSON, which gets validated first. Posted as bytes if the node doesn't accept JSON
+    PostBlockJson {
+        /// Block
+        block: serde_json::Value,
+    },
     /// Post block (raw)
     PostBlockRaw {
         /// Block
@@ -252,6 +334,20 @@ pub enum Message {
         #[serde(rename = "blockId")]
         block_id: BlockId,
     },
+    /// Get the ledger inclusion states of blocks, requested in parallel
+    /// Expected response: [`InclusionStates`](crate::message_interface::Response::InclusionStates)
+    GetInclusionStates {
+        /// Block IDs
+        #[serde(rename = "blockIds")]
+        block_ids: Vec<BlockId>,
+    },
+    /// Check in parallel whether outputs are spent
+    /// Expected response: [`OutputsSpent`](crate::message_interface::Response::OutputsSpent)
+    CheckOutputsSpent {
+        /// Output IDs
+        #[serde(rename = "outputIds")]
+        output_ids: Vec<OutputId>,
+    },
     /// Get block raw
     GetBlockRaw {
         /// Block ID
@@ -303,6 +399,40 @@ pub enum Message {
         /// Milestone Index
         index: u32,
     },
+    /// Get the milestones of a range of indexes.
+    GetMilestones {
+        /// Index of the first milestone
+        #[serde(rename = "startIndex")]
+        start_index: u32,
+        /// Index of the last milestone
+        #[serde(rename = "endIndex")]
+        end_index: u32,
+    },
+    /// Get the UTXO changes of a range of milestone indexes.
+    GetUtxoChangesByIndexRange {
+        /// Index of the first milestone
+        #[serde(rename = "startIndex")]
+        start_index: u32,
+        /// Index of the last milestone
+        #[serde(rename = "endIndex")]
+        end_index: u32,
+    },
+    /// Get the summed amount and number of the unspent outputs of an address.
+    GetAddressTotal {
+        /// Bech32 encoded address
+        address: String,
+    },
+    /// Get the net balance change of an address from the milestones confirmed after the given one.
+    GetAddressBalanceDelta {
+        /// Bech32 encoded address
+        address: String,
+        /// Milestone index after which the changes are included
+        #[serde(rename = "sinceMilestone")]
+        since_milestone: u32,
+        /// Milestone index up to which the changes are included, the latest confirmed one if not provided
+        #[serde(rename = "untilMilestone", default)]
+        until_milestone: Option<u32>,
+    },
     /// Get all receipts.
     GetReceipts,
     /// Get the receipts by the given milestone index.
@@ -313,18 +443,34 @@ pub enum Message {
     },
     /// Get the treasury output.
     GetTreasury,
+    /// Returns the total, treasury and circulating supply.
+    GetSupply,
     /// Returns the included block of the transaction.
     GetIncludedBlock {
         /// Transaction ID
         #[serde(rename = "transactionId")]
         transaction_id: TransactionId,
     },
+    /// Returns the block that included the transaction which created the output.
+    GetCreatingBlock {
+        /// Output ID
+        #[serde(rename = "outputId")]
+        output_id: OutputId,
+    },
     /// Returns the included block metadata of the transaction.
     GetIncludedBlockMetadata {
         /// Transaction ID
         #[serde(rename = "transactionId")]
         transaction_id: TransactionId,
     },
+    /// Returns a proof that the transaction was included in the ledger by a milestone.
+    /// Expected response:
+    /// [`TransactionInclusionProof`](crate::message_interface::Response::TransactionInclusionProof)
+    GetTransactionInclusionProof {
+        /// Transaction ID
+        #[serde(rename = "transactionId")]
+        transaction_id: TransactionId,
+    },
 
     //////////////////////////////////////////////////////////////////////
     // Node indexer API
@@ -375,7 +521,9 @@ pub enum Message {
     //////////////////////////////////////////////////////////////////////
     // High level API
     //////////////////////////////////////////////////////////////////////
-    /// Fetch OutputWithMetadataResponse from provided OutputIds (requests are sent in parallel)
+    /// Fetch OutputWithMetadataResponse from provided OutputIds (requests are sent in parallel). Failed requests are
+    /// handled according to the batch error mode of the client.
+    /// Expected response: [`OutputsBatch`](crate::message_interface::Response::OutputsBatch)
     GetOutputs {
         /// Output IDs
         #[serde(rename = "outputIds")]
@@ -388,11 +536,38 @@ pub enum Message {
         #[serde(rename = "outputIds")]
         output_ids: Vec<OutputId>,
     },
-    /// Find all blocks by provided block IDs.
+    /// Get the metadata of the blocks referenced by a milestone, call it for every index to go through a range of
+    /// milestones.
+    /// Expected response: [`BlocksMetadata`](crate::message_interface::Response::BlocksMetadata)
+    GetBlocksReferencedByMilestone {
+        /// Milestone index
+        index: u32,
+    },
+    /// Find all blocks by provided block IDs. Failed requests are handled according to the batch error mode of the
+    /// client.
+    /// Expected response: [`BlocksBatch`](crate::message_interface::Response::BlocksBatch)
     FindBlocks {
         /// BlockIDs
         #[serde(rename = "blockIds")]
         block_ids: Vec<BlockId>,
+        /// Only return blocks with one of these payload types, all blocks are returned if empty
+        #[serde(rename = "payloadTypes", default)]
+        payload_types: Vec<PayloadType>,
+    },
+    /// Get the milestone that referenced a block.
+    GetConfirmingMilestone {
+        /// Block ID
+        #[serde(rename = "blockId")]
+        block_id: BlockId,
+    },
+    /// Walk the parents of a block until a block referenced by a milestone is found.
+    FindConfirmedAncestor {
+        /// Block ID
+        #[serde(rename = "blockId")]
+        block_id: BlockId,
+        /// Maximum number of parent levels to search
+        #[serde(rename = "maxDepth")]
+        max_depth: u32,
     },
     /// Retries (promotes or reattaches) a block for provided block id. Block should only be
     /// retried only if they are valid and haven't been confirmed for a while.
@@ -400,7 +575,23 @@ pub enum Message {
         /// Block ID
         #[serde(rename = "blockId")]
         block_id: BlockId,
-    },
+        /// Which actions are allowed, promotion and reattachment by default
+        #[serde(default)]
+        strategy: RetryStrategy,
+    },
+    /// Returns the sent blocks that await confirmation with their reserved inputs, to persist them for a restart.
+    /// Expected response: [`PendingState`](crate::message_interface::Response::PendingState)
+    ExportPendingState,
+    /// Restores the reserved inputs of an exported pending state.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    ImportPendingState {
+        /// The exported pending state
+        state: PendingState,
+    },
+    /// Checks the pending blocks once, releases the reserved inputs of included or conflicting ones and returns the
+    /// still pending blocks.
+    /// Expected response: [`BlockIds`](crate::message_interface::Response::BlockIds)
+    CheckPendingBlocks,
     /// Retries (promotes or reattaches) a block for provided block id until it's included (referenced by a
     /// milestone). Default interval is 5 seconds and max attempts is 40. Returns the included block at first
     /// position and additional reattached blocks
@@ -413,6 +604,9 @@ pub enum Message {
         /// Maximum attempts
         #[serde(rename = "maxAttempts")]
         max_attempts: Option<u64>,
+        /// Which actions are allowed, promotion and reattachment by default
+        #[serde(default)]
+        strategy: RetryStrategy,
     },
     /// Function to consolidate all funds from a range of addresses to the address with the lowest index in that range
     /// Returns the address to which the funds got consolidated, if any were available
@@ -424,6 +618,114 @@ pub enum Message {
         #[serde(rename = "generateAddressesOptions")]
         generate_addresses_options: GenerateAddressesOptions,
     },
+    /// Consolidates all funds like `ConsolidateFunds`, but returns the ids of the consolidation blocks, which is empty
+    /// if there was nothing to consolidate.
+    /// Expected response: [`BlockIds`](crate::message_interface::Response::BlockIds)
+    ConsolidateFundsWithBlockIds {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Addresses generation options
+        #[serde(rename = "generateAddressesOptions")]
+        generate_addresses_options: GenerateAddressesOptions,
+    },
+    /// Collects the outputs of a range of addresses that need to be consolidated to the address with the lowest index
+    /// in that range, so an interrupted consolidation can be resumed with `ConsolidateFundsFromCheckpoint`.
+    /// Expected response: [`ConsolidationCheckpoint`](crate::message_interface::Response::ConsolidationCheckpoint)
+    ConsolidationCheckpoint {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Addresses generation options
+        #[serde(rename = "generateAddressesOptions")]
+        generate_addresses_options: GenerateAddressesOptions,
+    },
+    /// Consolidates the remaining outputs of a checkpoint and returns the checkpoint, which has no remaining outputs
+    /// once the consolidation is done. Outputs that already got spent are skipped, so the same checkpoint can be used
+    /// again if the consolidation gets interrupted.
+    /// Expected response: [`ConsolidationCheckpoint`](crate::message_interface::Response::ConsolidationCheckpoint)
+    ConsolidateFundsFromCheckpoint {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// The checkpoint of the consolidation
+        checkpoint: ConsolidationCheckpoint,
+    },
+    /// Sends the outputs in as many blocks as needed so that the summed amount of the outputs in a single block
+    /// doesn't exceed the cap.
+    /// Expected response: [`BlockIds`](crate::message_interface::Response::BlockIds)
+    SendOutputsWithAmountCap {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Outputs
+        outputs: Vec<OutputDto>,
+        /// Maximum summed amount of the outputs in a single block
+        #[serde(rename = "maxAmountPerBlock")]
+        max_amount_per_block: String,
+    },
+    /// Take a snapshot of all unspent outputs owned by the addresses of an account.
+    /// Expected response: [`UtxoSnapshot`](crate::message_interface::Response::UtxoSnapshot)
+    ExportUtxoSnapshot {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Coin type, defaults to the Shimmer coin type
+        #[serde(rename = "coinType")]
+        coin_type: Option<u32>,
+        /// Account index
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        /// Amount of consecutive addresses without outputs after which the scan stops, defaults to 20
+        #[serde(rename = "gapLimit")]
+        gap_limit: Option<u32>,
+        /// Time in milliseconds after which the scan stops and returns the outputs found until then
+        #[serde(rename = "scanDeadline")]
+        scan_deadline: Option<u64>,
+    },
+    /// Get the first address of an account that owns unspent outputs, stopping at the first hit.
+    /// Expected response: [`FirstFundedAddress`](crate::message_interface::Response::FirstFundedAddress)
+    GetFirstFundedAddress {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Coin type, defaults to the Shimmer coin type
+        #[serde(rename = "coinType")]
+        coin_type: Option<u32>,
+        /// Account index
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        /// Amount of consecutive addresses without outputs after which the scan stops, defaults to 20
+        #[serde(rename = "gapLimit")]
+        gap_limit: Option<u32>,
+    },
+    /// Check if the unspent outputs of an account cover the amount of the outputs, without doing input selection.
+    /// Expected response: [`Affordability`](crate::message_interface::Response::Affordability)
+    CanAfford {
+        /// Secret manager
+        #[serde(rename = "secretManager")]
+        secret_manager: SecretManagerDto,
+        /// Coin type, defaults to the Shimmer coin type
+        #[serde(rename = "coinType")]
+        coin_type: Option<u32>,
+        /// Account index
+        #[serde(rename = "accountIndex")]
+        account_index: u32,
+        /// Amount of consecutive addresses without outputs after which the scan stops, defaults to 20
+        #[serde(rename = "gapLimit")]
+        gap_limit: Option<u32>,
+        /// Outputs
+        outputs: Vec<OutputDto>,
+    },
+    /// Get the IDs of all unspent outputs owned by an address, following the pagination cursor of the indexer
+    /// Expected response: [`OutputIds`](crate::message_interface::Response::OutputIds)
+    AddressOutputIds {
+        /// Bech32 encoded address
+        address: String,
+        /// Only return outputs of this kind
+        #[serde(rename = "outputType", default)]
+        output_type: Option<u8>,
+    },
     /// Function to find inputs from addresses for a provided amount (useful for offline signing)
     FindInputs {
         /// Addresses
@@ -439,6 +741,19 @@ pub enum Message {
         output_ids: Vec<OutputId>,
         /// Addresses
         addresses: Vec<String>,
+        /// Keep outputs that are found for multiple criteria more than once. Duplicates are removed by default.
+        #[serde(rename = "keepDuplicates", default)]
+        keep_duplicates: bool,
+        /// Only return outputs with at least this amount
+        #[serde(rename = "minAmount", default)]
+        min_amount: Option<String>,
+        /// Only return outputs with at most this amount
+        #[serde(rename = "maxAmount", default)]
+        max_amount: Option<String>,
+        /// Group the outputs by the milestone that booked them, keeping only the groups of this many latest
+        /// milestones
+        #[serde(rename = "groupByMilestone", default)]
+        group_by_milestone: Option<usize>,
     },
     /// Reattaches blocks for provided block id. Blocks can be reattached only if they are valid and haven't been
     /// confirmed for a while.
@@ -514,17 +829,53 @@ pub enum Message {
         /// Address
         address: String,
     },
-    /// Checks if a String is a valid bech32 encoded address.
+    /// Returns an Address parsed from a bech32 or a `0x` prefixed hex encoded Ed25519 address.
+    /// Expected response: [`ParsedBech32Address`](crate::message_interface::Response::ParsedBech32Address)
+    ParseAddress {
+        /// Address
+        address: String,
+    },
+    /// Returns a UTXO input parsed from a hex encoded output ID.
+    /// Expected response: [`UtxoInput`](crate::message_interface::Response::UtxoInput)
+    ParseOutputId {
+        /// Output ID
+        #[serde(rename = "outputId")]
+        output_id: String,
+    },
+    /// Checks if a String is a valid bech32 encoded address, of the network with the expected bech32 HRP if it's set.
     IsAddressValid {
         /// Address
         address: String,
+        /// Expected bech32 HRP
+        #[serde(rename = "expectedHrp", default)]
+        expected_hrp: Option<String>,
+    },
+    /// Validates a bech32 encoded address like [`IsAddressValid`](Message::IsAddressValid), but returns the reason if
+    /// it's invalid as error, [`Error::HrpMismatch`](crate::Error::HrpMismatch) if it doesn't have the expected HRP.
+    /// Expected response: [`Ok`](crate::message_interface::Response::Ok)
+    ValidateAddress {
+        /// Address
+        address: String,
+        /// Expected bech32 HRP
+        #[serde(rename = "expectedHrp", default)]
+        expected_hrp: Option<String>,
     },
     /// Generates a new mnemonic.
     GenerateMnemonic,
+    /// Generates a new mnemonic with 12, 15, 18, 21 or 24 words.
+    /// Expected response: [`GeneratedMnemonic`](crate::message_interface::Response::GeneratedMnemonic)
+    GenerateMnemonicWithWordCount {
+        /// The number of words
+        #[serde(rename = "wordCount")]
+        word_count: usize,
+    },
     /// Returns a hex encoded seed for a mnemonic.
     MnemonicToHexSeed {
         /// Mnemonic
         mnemonic: String,
+        /// BIP39 passphrase, empty by default
+        #[serde(default)]
+        passphrase: Option<String>,
     },
     /// Returns a block ID (Blake2b256 hash of block bytes) from a block
     BlockId {
@@ -536,6 +887,11 @@ pub enum Message {
         /// Transaction Payload
         payload: TransactionPayloadDto,
     },
+    /// Computes the IDs of the outputs created by the provided transaction payload
+    ComputeOutputIds {
+        /// Transaction Payload
+        payload: TransactionPayloadDto,
+    },
     /// Computes the alias ID
     ComputeAliasId {
         /// Output ID
@@ -572,4 +928,94 @@ pub enum Message {
         /// The transaction essence
         essence: TransactionEssenceDto,
     },
+    /// Compute the hash of a packed transaction essence, which is what gets signed.
+    /// Expected response: [`TransactionEssenceHash`](crate::message_interface::Response::TransactionEssenceHash)
+    HashTransactionEssenceBytes {
+        /// Hex encoded transaction essence bytes
+        #[serde(rename = "essenceBytes")]
+        essence_bytes: String,
+    },
+    /// Computes the PoW score of a block and checks whether it meets the minimum score
+    VerifyPow {
+        /// Hex encoded block bytes
+        block: String,
+        /// The minimum score, defaults to the min PoW score of the network
+        #[serde(rename = "minPowScore")]
+        min_pow_score: Option<u32>,
+    },
+    /// Checks that the block of a transaction inclusion proof contains the transaction and that the audit path leads
+    /// to the inclusion merkle root of the milestone. The milestone signatures aren't checked.
+    /// Expected response:
+    /// [`IsTransactionInclusionProofValid`](crate::message_interface::Response::IsTransactionInclusionProofValid)
+    VerifyTransactionInclusionProof {
+        /// The transaction inclusion proof
+        proof: TransactionInclusionProofDto,
+        /// Transaction ID
+        #[serde(rename = "transactionId")]
+        transaction_id: TransactionId,
+    },
+}
+
+impl Message {
+    /// Returns whether the message only reads from the nodes, so it can safely be sent again. The requests of these
+    /// messages are retried on transient failures if configured with
+    /// [`ClientBuilder::with_read_retry()`](crate::ClientBuilder::with_read_retry). Messages that post blocks, like
+    /// `PostBlock` or `BuildAndPostBlock`, aren't idempotent and their blocks are never posted again.
+    pub fn is_idempotent(&self) -> bool {
+        matches!(
+            self,
+            Self::GetHealth { .. }
+                | Self::GetNodesHealth { .. }
+                | Self::GetNodeInfo { .. }
+                | Self::GetInfo { .. }
+                | Self::GetLatestMilestoneTimestamp { .. }
+                | Self::GetPeers { .. }
+                | Self::GetPeer { .. }
+                | Self::GetTips { .. }
+                | Self::GetBlock { .. }
+                | Self::GetBlockMetadata { .. }
+                | Self::GetInclusionStates { .. }
+                | Self::CheckOutputsSpent { .. }
+                | Self::GetBlockRaw { .. }
+                | Self::GetOutput { .. }
+                | Self::GetOutputMetadata { .. }
+                | Self::GetMilestoneById { .. }
+                | Self::GetMilestoneByIdRaw { .. }
+                | Self::GetMilestoneByIndex { .. }
+                | Self::GetMilestoneByIndexRaw { .. }
+                | Self::GetUtxoChangesById { .. }
+                | Self::GetUtxoChangesByIndex { .. }
+                | Self::GetMilestones { .. }
+                | Self::GetUtxoChangesByIndexRange { .. }
+                | Self::GetAddressTotal { .. }
+                | Self::GetAddressBalanceDelta { .. }
+                | Self::GetReceipts { .. }
+                | Self::GetReceiptsMigratedAt { .. }
+                | Self::GetTreasury { .. }
+                | Self::GetSupply { .. }
+                | Self::GetIncludedBlock { .. }
+                | Self::GetCreatingBlock { .. }
+                | Self::GetIncludedBlockMetadata { .. }
+                | Self::GetTransactionInclusionProof { .. }
+                | Self::BasicOutputIds { .. }
+                | Self::AliasOutputIds { .. }
+                | Self::AliasOutputId { .. }
+                | Self::NftOutputIds { .. }
+                | Self::NftOutputId { .. }
+                | Self::FoundryOutputIds { .. }
+                | Self::FoundryOutputId { .. }
+                | Self::GetOutputs { .. }
+                | Self::TryGetOutputs { .. }
+                | Self::GetBlocksReferencedByMilestone { .. }
+                | Self::FindBlocks { .. }
+                | Self::GetConfirmingMilestone { .. }
+                | Self::FindConfirmedAncestor { .. }
+                | Self::ExportUtxoSnapshot { .. }
+                | Self::GetFirstFundedAddress { .. }
+                | Self::CanAfford { .. }
+                | Self::AddressOutputIds { .. }
+                | Self::FindInputs { .. }
+                | Self::FindOutputs { .. }
+        )
+    }
 }
@@ -1,26 +1,28 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{any::Any, panic::AssertUnwindSafe};
+use std::{any::Any, collections::HashMap, panic::AssertUnwindSafe, time::Duration};
 
 use backtrace::Backtrace;
 use futures::{Future, FutureExt};
 use iota_types::block::{
-    address::dto::AddressDto,
+    address::{dto::AddressDto, Address},
     input::dto::UtxoInputDto,
     output::{
         dto::{OutputBuilderAmountDto, OutputDto, RentStructureDto},
-        AliasId, AliasOutput, BasicOutput, FoundryId, FoundryOutput, NftId, NftOutput, Output,
+        AliasId, AliasOutput, BasicOutput, FoundryId, FoundryOutput, NftId, NftOutput, Output, OutputId,
     },
     payload::{
         dto::{MilestonePayloadDto, PayloadDto},
-        transaction::TransactionEssence,
+        transaction::{TransactionEssence, TransactionId},
         Payload, TransactionPayload,
     },
     protocol::dto::ProtocolParametersDto,
     unlock::Unlock,
     Block, BlockDto, DtoError,
 };
+use packable::PackableExt;
+use serde::{Deserialize, Serialize};
 use zeroize::Zeroize;
 #[cfg(feature = "mqtt")]
 use {
@@ -31,11 +33,16 @@ use {
 #[cfg(feature = "ledger_nano")]
 use crate::secret::ledger_nano::LedgerSecretManager;
 use crate::{
-    api::{PreparedTransactionData, PreparedTransactionDataDto, RemainderData},
-    message_interface::{message::Message, response::Response},
+    api::{
+        filter_output_responses_by_amount, group_output_responses_by_milestone, PreparedTransactionData,
+        PreparedTransactionDataDto, RemainderData, TransactionInclusionProof, TransactionInclusionProofDto,
+        TypedBlockDto,
+    },
+    constants::{DEFAULT_GAP_LIMIT, SHIMMER_COIN_TYPE},
+    message_interface::{event::Event, message::Message, response::Response},
     request_funds_from_faucet,
     secret::{types::InputSigningData, SecretManage, SecretManager},
-    Client, Result,
+    AddressFormat, Client, Error, Result,
 };
 
 fn panic_to_response_message(panic: Box<dyn Any>) -> Response {
@@ -48,8 +55,10 @@ fn panic_to_response_message(panic: Box<dyn Any>) -> Response {
         },
         |message| format!("Internal error: {message}"),
     );
-    let current_backtrace = Backtrace::new();
-    Response::Panic(format!("{msg}\n\n{current_backtrace:?}"))
+    Response::Panic {
+        message: msg,
+        stack: format!("{:?}", Backtrace::new()),
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -95,39 +104,109 @@ impl ClientMessageHandler {
         Self { client }
     }
 
-    /// Listen to MQTT events
+    /// Serializes a response to JSON. If [`ClientBuilder::with_emit_nulls()`](crate::ClientBuilder::with_emit_nulls)
+    /// is disabled, `null` fields are omitted at every level of the response. Bech32 encoded addresses are
+    /// represented according to [`ClientBuilder::with_address_format()`](crate::ClientBuilder::with_address_format).
+    /// Tagged data payloads contain their tag and data decoded as UTF-8 if
+    /// [`ClientBuilder::with_decode_tagged_data_utf8()`](crate::ClientBuilder::with_decode_tagged_data_utf8) is
+    /// enabled. If any of these options changes the response, the order of the fields isn't kept.
+    pub fn response_to_json(&self, response: &Response) -> Result<String> {
+        to_json(&self.client, response)
+    }
+
+    // Wraps the error in an `Error::Mapped` if the client has a user-friendly message for it, see
+    // `ClientBuilder::with_error_message_mapper()` and `ClientBuilder::with_error_messages()`.
+    fn map_error(&self, error: Error) -> Error {
+        let kind = error.kind();
+        let message = self
+            .client
+            .error_message_mapper
+            .as_ref()
+            .and_then(|mapper| mapper(&error, &kind))
+            .or_else(|| {
+                error
+                    .code()
+                    .and_then(|code| self.client.error_messages.get(code))
+                    .or_else(|| self.client.error_messages.get(&kind))
+                    .cloned()
+            });
+
+        match message {
+            Some(message) => Error::Mapped {
+                error: Box::new(error),
+                message,
+            },
+            None => error,
+        }
+    }
+
+    /// Watches outputs that are expected to be spent by the given transactions, see
+    /// [`Client::watch_double_spends()`]. The handler is called with the JSON serialized
+    /// [`Event::DoubleSpendDetected`] if an output gets spent by another transaction. Returns once all watched outputs
+    /// are spent.
+    pub async fn watch_double_spends<F>(
+        &self,
+        watched_outputs: HashMap<OutputId, TransactionId>,
+        interval: Duration,
+        handler: F,
+    ) -> Result<()>
+    where
+        F: Fn(String) + Send + Sync,
+    {
+        self.client
+            .watch_double_spends(watched_outputs, interval, |double_spend| {
+                handler(
+                    to_json(&self.client, &Event::DoubleSpendDetected(double_spend))
+                        .expect("failed to serialize double spend event"),
+                );
+            })
+            .await
+    }
+
+    /// Listen to MQTT events. The handler is called with the JSON serialized event, which contains the `topic`, the
+    /// `payloadType` ("block", "milestone", "receipt" or "json") and the `payload` as DTO. The event is serialized with
+    /// the same options as [`Self::response_to_json()`].
     #[cfg(feature = "mqtt")]
     #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
     pub async fn listen<F>(&self, topics: Vec<Topic>, handler: F)
     where
         F: Fn(String) + 'static + Clone + Send + Sync,
     {
+        let client = self.client.clone();
         self.client
             .subscribe(topics, move |topic_event| {
                 #[derive(Serialize)]
                 struct MqttResponse {
                     topic: String,
-                    payload: String,
+                    #[serde(rename = "payloadType")]
+                    payload_type: &'static str,
+                    payload: serde_json::Value,
                 }
                 // convert types to DTOs
-                let payload = match &topic_event.payload {
-                    MqttPayload::Json(val) => {
-                        serde_json::to_string(&val).expect("failed to serialize MqttPayload::Json")
-                    }
-                    MqttPayload::Block(block) => {
-                        serde_json::to_string(&BlockDto::from(block)).expect("failed to serialize MqttPayload::Block")
-                    }
-                    MqttPayload::MilestonePayload(ms) => serde_json::to_string(&MilestonePayloadDto::from(ms))
-                        .expect("failed to serialize MqttPayload::MilestonePayload"),
-                    MqttPayload::Receipt(receipt) => serde_json::to_string(&ReceiptMilestoneOptionDto::from(receipt))
-                        .expect("failed to serialize MqttPayload::Receipt"),
+                let (payload_type, payload) = match &topic_event.payload {
+                    MqttPayload::Json(val) => ("json", val.clone()),
+                    MqttPayload::Block(block) => (
+                        "block",
+                        serde_json::to_value(BlockDto::from(block)).expect("failed to serialize MqttPayload::Block"),
+                    ),
+                    MqttPayload::MilestonePayload(ms) => (
+                        "milestone",
+                        serde_json::to_value(MilestonePayloadDto::from(ms))
+                            .expect("failed to serialize MqttPayload::MilestonePayload"),
+                    ),
+                    MqttPayload::Receipt(receipt) => (
+                        "receipt",
+                        serde_json::to_value(ReceiptMilestoneOptionDto::from(receipt))
+                            .expect("failed to serialize MqttPayload::Receipt"),
+                    ),
                 };
                 let response = MqttResponse {
                     topic: topic_event.topic.clone(),
+                    payload_type,
                     payload,
                 };
 
-                handler(serde_json::to_string(&response).expect("failed to serialize MQTT response"))
+                handler(to_json(&client, &response).expect("failed to serialize MQTT response"))
             })
             .await
             .expect("failed to listen to MQTT events");
@@ -143,6 +222,21 @@ impl ClientMessageHandler {
             } => {
                 log::debug!("Response: GenerateAddresses{{ secret_manager: <omitted>, options: {options:?} }}")
             }
+            Message::GenerateAllAddresses {
+                secret_manager: _,
+                options,
+            } => {
+                log::debug!("Response: GenerateAllAddresses{{ secret_manager: <omitted>, options: {options:?} }}")
+            }
+            Message::GetNextUnusedAddress {
+                secret_manager: _,
+                coin_type,
+                account_index,
+            } => {
+                log::debug!(
+                    "Response: GetNextUnusedAddress{{ secret_manager: <omitted>, coin_type: {coin_type:?}, account_index: {account_index} }}"
+                )
+            }
             Message::BuildAndPostBlock {
                 secret_manager: _,
                 options,
@@ -175,6 +269,71 @@ impl ClientMessageHandler {
                     "Response: ConsolidateFunds{{ secret_manager: <omitted>, generate_addresses_options: {generate_addresses_options:?} }}"
                 )
             }
+            Message::ConsolidateFundsWithBlockIds {
+                secret_manager: _,
+                generate_addresses_options,
+            } => {
+                log::debug!(
+                    "Response: ConsolidateFundsWithBlockIds{{ secret_manager: <omitted>, generate_addresses_options: {generate_addresses_options:?} }}"
+                )
+            }
+            Message::ConsolidationCheckpoint {
+                secret_manager: _,
+                generate_addresses_options,
+            } => {
+                log::debug!(
+                    "Response: ConsolidationCheckpoint{{ secret_manager: <omitted>, generate_addresses_options: {generate_addresses_options:?} }}"
+                )
+            }
+            Message::ConsolidateFundsFromCheckpoint {
+                secret_manager: _,
+                checkpoint,
+            } => {
+                log::debug!(
+                    "Response: ConsolidateFundsFromCheckpoint{{ secret_manager: <omitted>, checkpoint: {checkpoint:?} }}"
+                )
+            }
+            Message::SendOutputsWithAmountCap {
+                secret_manager: _,
+                outputs,
+                max_amount_per_block,
+            } => {
+                log::debug!(
+                    "Response: SendOutputsWithAmountCap{{ secret_manager: <omitted>, outputs: {outputs:?}, max_amount_per_block: {max_amount_per_block} }}"
+                )
+            }
+            Message::ExportUtxoSnapshot {
+                secret_manager: _,
+                coin_type,
+                account_index,
+                gap_limit,
+                scan_deadline,
+            } => {
+                log::debug!(
+                    "Response: ExportUtxoSnapshot{{ secret_manager: <omitted>, coin_type: {coin_type:?}, account_index: {account_index}, gap_limit: {gap_limit:?}, scan_deadline: {scan_deadline:?} }}"
+                )
+            }
+            Message::GetFirstFundedAddress {
+                secret_manager: _,
+                coin_type,
+                account_index,
+                gap_limit,
+            } => {
+                log::debug!(
+                    "Response: GetFirstFundedAddress{{ secret_manager: <omitted>, coin_type: {coin_type:?}, account_index: {account_index}, gap_limit: {gap_limit:?} }}"
+                )
+            }
+            Message::CanAfford {
+                secret_manager: _,
+                coin_type,
+                account_index,
+                gap_limit,
+                outputs,
+            } => {
+                log::debug!(
+                    "Response: CanAfford{{ secret_manager: <omitted>, coin_type: {coin_type:?}, account_index: {account_index}, gap_limit: {gap_limit:?}, outputs: {outputs:?} }}"
+                )
+            }
             Message::MnemonicToHexSeed { .. } => {
                 log::debug!("Response: MnemonicToHexSeed{{ <omitted> }}")
             }
@@ -185,7 +344,7 @@ impl ClientMessageHandler {
 
         let response = match result {
             Ok(r) => r,
-            Err(e) => Response::Error(e),
+            Err(e) => Response::Error(self.map_error(e)),
         };
 
         match response {
@@ -320,6 +479,31 @@ impl ClientMessageHandler {
                     .await?;
                 Ok(Response::GeneratedAddresses(addresses))
             }
+            Message::GenerateAllAddresses {
+                secret_manager,
+                options,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                let addresses = self
+                    .client
+                    .get_addresses(&secret_manager)
+                    .set_options(options)?
+                    .get_all()
+                    .await?;
+                Ok(Response::GeneratedAllAddresses(addresses.into_interleaved()))
+            }
+            Message::GetNextUnusedAddress {
+                secret_manager,
+                coin_type,
+                account_index,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                Ok(Response::Bech32Address(
+                    self.client
+                        .get_next_unused_address(&secret_manager, coin_type.unwrap_or(SHIMMER_COIN_TYPE), account_index)
+                        .await?,
+                ))
+            }
             Message::BuildAndPostBlock {
                 secret_manager,
                 options,
@@ -345,17 +529,43 @@ impl ClientMessageHandler {
 
                 Ok(Response::BlockIdWithBlock(block_id, BlockDto::from(&block)))
             }
+            Message::PostTaggedData { tag, data } => {
+                let tag = decode_hex_or_utf8(tag)?;
+                if tag.is_empty() {
+                    return Err(Error::TaggedData(
+                        "the tag is empty, a tag is required to post tagged data".to_string(),
+                    ));
+                }
+
+                let block = self
+                    .client
+                    .block()
+                    .with_tag(tag)
+                    .with_data(decode_hex_or_utf8(data)?)
+                    .finish()
+                    .await?;
+
+                Ok(Response::BlockId(block.id()))
+            }
             #[cfg(feature = "mqtt")]
             Message::ClearListeners { topics } => {
                 self.client.unsubscribe(topics).await?;
                 Ok(Response::Ok)
             }
+            #[cfg(feature = "mqtt")]
+            Message::GetSubscribedTopics => Ok(Response::Topics(self.client.subscribed_topics().await)),
             Message::GetNode => Ok(Response::Node(self.client.get_node()?)),
             Message::GetNetworkInfo => Ok(Response::NetworkInfo(self.client.get_network_info().await?.into())),
-            Message::GetNetworkId => Ok(Response::NetworkId(self.client.get_network_id().await?)),
+            Message::GetNetworkId => Ok(Response::NetworkId(self.client.get_network_id().await?.to_string())),
             Message::GetBech32Hrp => Ok(Response::Bech32Hrp(self.client.get_bech32_hrp().await?)),
             Message::GetMinPowScore => Ok(Response::MinPowScore(self.client.get_min_pow_score().await?)),
             Message::GetTipsInterval => Ok(Response::TipsInterval(self.client.get_tips_interval())),
+            Message::ComputeRequiredStorageDeposit { output_count } => Ok(Response::RequiredStorageDeposit(
+                self.client
+                    .get_required_storage_deposit(output_count)
+                    .await?
+                    .to_string(),
+            )),
             Message::GetProtocolParameters => {
                 let params = self.client.get_protocol_parameters().await?;
                 let protocol_response = ProtocolParametersDto {
@@ -477,10 +687,24 @@ impl ClientMessageHandler {
             Message::UnhealthyNodes => Ok(Response::UnhealthyNodes(
                 self.client.unhealthy_nodes().into_iter().cloned().collect(),
             )),
+            #[cfg(not(target_family = "wasm"))]
+            Message::GetNodeSyncStatus => Ok(Response::NodeSyncStatus(self.client.node_sync_status())),
             Message::GetHealth { url } => Ok(Response::Health(self.client.get_health(&url).await?)),
+            Message::GetNodesHealth => Ok(Response::NodesHealth(self.client.get_nodes_health().await?)),
             Message::GetNodeInfo { url, auth } => Ok(Response::NodeInfo(Client::get_node_info(&url, auth).await?)),
             Message::GetInfo => Ok(Response::Info(self.client.get_info().await?)),
+            Message::GetLatestMilestoneTimestamp => Ok(Response::MilestoneTimestamp(
+                self.client.get_latest_milestone_timestamp().await?,
+            )),
             Message::GetPeers => Ok(Response::Peers(self.client.get_peers().await?)),
+            Message::GetPeer { peer_id } => Ok(Response::Peer(self.client.get_peer(&peer_id).await?)),
+            Message::AddPeer { multi_address, alias } => Ok(Response::Peer(
+                self.client.add_peer(&multi_address, alias.as_deref()).await?,
+            )),
+            Message::RemovePeer { peer_id } => {
+                self.client.remove_peer(&peer_id).await?;
+                Ok(Response::Ok)
+            }
             Message::GetTips => Ok(Response::Tips(self.client.get_tips().await?)),
             Message::PostBlockRaw { block_bytes } => Ok(Response::BlockId(
                 self.client
@@ -498,11 +722,18 @@ impl ClientMessageHandler {
                     )?)
                     .await?,
             )),
-            Message::GetBlock { block_id } => Ok(Response::Block(BlockDto::from(
+            Message::PostBlockJson { block } => Ok(Response::BlockId(self.client.post_block_json(block).await?)),
+            Message::GetBlock { block_id } => Ok(Response::Block(TypedBlockDto::from(
                 &self.client.get_block(&block_id).await?,
             ))),
             Message::GetBlockMetadata { block_id } => Ok(Response::BlockMetadata(
-                self.client.get_block_metadata(&block_id).await?,
+                self.client.get_block_metadata(&block_id).await?.into(),
+            )),
+            Message::GetInclusionStates { block_ids } => Ok(Response::InclusionStates(
+                self.client.get_inclusion_states(block_ids).await?.into(),
+            )),
+            Message::CheckOutputsSpent { output_ids } => Ok(Response::OutputsSpent(
+                self.client.check_outputs_spent(output_ids).await?.into(),
             )),
             Message::GetBlockRaw { block_id } => Ok(Response::BlockRaw(self.client.get_block_raw(&block_id).await?)),
             Message::GetOutput { output_id } => Ok(Response::Output(self.client.get_output(&output_id).await?)),
@@ -527,17 +758,51 @@ impl ClientMessageHandler {
             Message::GetUtxoChangesByIndex { index } => Ok(Response::MilestoneUtxoChanges(
                 self.client.get_utxo_changes_by_index(index).await?,
             )),
+            Message::GetMilestones { start_index, end_index } => Ok(Response::Milestones(
+                self.client
+                    .get_milestones(start_index..=end_index)
+                    .await?
+                    .iter()
+                    .map(MilestonePayloadDto::from)
+                    .collect(),
+            )),
+            Message::GetUtxoChangesByIndexRange { start_index, end_index } => Ok(Response::MilestonesUtxoChanges(
+                self.client
+                    .get_utxo_changes_by_index_range(start_index..=end_index)
+                    .await?,
+            )),
+            Message::GetAddressTotal { address } => {
+                Ok(Response::AddressTotal(self.client.get_address_total(&address).await?))
+            }
+            Message::GetAddressBalanceDelta {
+                address,
+                since_milestone,
+                until_milestone,
+            } => Ok(Response::AddressBalanceDelta(
+                self.client
+                    .get_address_balance_delta(&address, since_milestone, until_milestone)
+                    .await?,
+            )),
             Message::GetReceipts => Ok(Response::Receipts(self.client.get_receipts().await?)),
             Message::GetReceiptsMigratedAt { milestone_index } => Ok(Response::Receipts(
                 self.client.get_receipts_migrated_at(milestone_index).await?,
             )),
             Message::GetTreasury => Ok(Response::Treasury(self.client.get_treasury().await?)),
-            Message::GetIncludedBlock { transaction_id } => Ok(Response::Block(BlockDto::from(
+            Message::GetSupply => Ok(Response::Supply(self.client.get_supply().await?)),
+            Message::GetIncludedBlock { transaction_id } => Ok(Response::Block(TypedBlockDto::from(
                 &self.client.get_included_block(&transaction_id).await?,
             ))),
+            Message::GetCreatingBlock { output_id } => Ok(Response::Block(TypedBlockDto::from(
+                &self.client.get_creating_block(&output_id).await?,
+            ))),
             Message::GetIncludedBlockMetadata { transaction_id } => Ok(Response::BlockMetadata(
-                self.client.get_included_block_metadata(&transaction_id).await?,
+                self.client.get_included_block_metadata(&transaction_id).await?.into(),
             )),
+            Message::GetTransactionInclusionProof { transaction_id } => {
+                Ok(Response::TransactionInclusionProof(TransactionInclusionProofDto::from(
+                    &self.client.get_transaction_inclusion_proof(&transaction_id).await?,
+                )))
+            }
             Message::BasicOutputIds { query_parameters } => Ok(Response::OutputIdsResponse(
                 self.client.basic_output_ids(query_parameters).await?,
             )),
@@ -555,30 +820,44 @@ impl ClientMessageHandler {
             Message::FoundryOutputId { foundry_id } => {
                 Ok(Response::OutputId(self.client.foundry_output_id(foundry_id).await?))
             }
-            Message::GetOutputs { output_ids } => Ok(Response::Outputs(self.client.get_outputs(output_ids).await?)),
+            Message::GetOutputs { output_ids } => Ok(Response::OutputsBatch(
+                self.client.get_outputs_batch(output_ids).await?.into(),
+            )),
             Message::TryGetOutputs { output_ids } => {
                 Ok(Response::Outputs(self.client.try_get_outputs(output_ids).await?))
             }
-            Message::FindBlocks { block_ids } => Ok(Response::Blocks(
+            Message::GetBlocksReferencedByMilestone { index } => Ok(Response::BlocksMetadata(
+                self.client.get_blocks_referenced_by_milestone(index).await?,
+            )),
+            Message::FindBlocks {
+                block_ids,
+                payload_types,
+            } => Ok(Response::BlocksBatch(
                 self.client
-                    .find_blocks(&block_ids)
+                    .find_blocks_with_payload_types_batch(&block_ids, &payload_types)
                     .await?
-                    .iter()
-                    .map(BlockDto::from)
-                    .collect(),
+                    .map(|block| BlockDto::from(&block))
+                    .into(),
             )),
-            Message::Retry { block_id } => {
-                let (block_id, block) = self.client.retry(&block_id).await?;
+            Message::GetConfirmingMilestone { block_id } => Ok(Response::Milestone(MilestonePayloadDto::from(
+                &self.client.get_confirming_milestone(&block_id).await?,
+            ))),
+            Message::FindConfirmedAncestor { block_id, max_depth } => Ok(Response::ConfirmedAncestor(
+                self.client.find_confirmed_ancestor(&block_id, max_depth).await?,
+            )),
+            Message::Retry { block_id, strategy } => {
+                let (block_id, block) = self.client.retry_with_strategy(&block_id, strategy).await?;
                 Ok(Response::BlockIdWithBlock(block_id, BlockDto::from(&block)))
             }
             Message::RetryUntilIncluded {
                 block_id,
                 interval,
                 max_attempts,
+                strategy,
             } => {
                 let res = self
                     .client
-                    .retry_until_included(&block_id, interval, max_attempts)
+                    .retry_until_included_with_strategy(&block_id, interval, max_attempts, strategy)
                     .await?;
                 let res = res
                     .into_iter()
@@ -586,6 +865,12 @@ impl ClientMessageHandler {
                     .collect();
                 Ok(Response::RetryUntilIncludedSuccessful(res))
             }
+            Message::ExportPendingState => Ok(Response::PendingState(self.client.export_pending_state())),
+            Message::ImportPendingState { state } => {
+                self.client.import_pending_state(state)?;
+                Ok(Response::Ok)
+            }
+            Message::CheckPendingBlocks => Ok(Response::BlockIds(self.client.check_pending_blocks().await?)),
             Message::ConsolidateFunds {
                 secret_manager,
                 generate_addresses_options,
@@ -597,6 +882,134 @@ impl ClientMessageHandler {
                         .await?,
                 ))
             }
+            Message::ConsolidateFundsWithBlockIds {
+                secret_manager,
+                generate_addresses_options,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                Ok(Response::BlockIds(
+                    self.client
+                        .consolidate_funds_with_block_ids(&secret_manager, generate_addresses_options)
+                        .await?,
+                ))
+            }
+            Message::ConsolidationCheckpoint {
+                secret_manager,
+                generate_addresses_options,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                Ok(Response::ConsolidationCheckpoint(
+                    self.client
+                        .consolidation_checkpoint(&secret_manager, generate_addresses_options)
+                        .await?,
+                ))
+            }
+            Message::ConsolidateFundsFromCheckpoint {
+                secret_manager,
+                mut checkpoint,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                self.client
+                    .consolidate_funds_from_checkpoint(&secret_manager, &mut checkpoint)
+                    .await?;
+                Ok(Response::ConsolidationCheckpoint(checkpoint))
+            }
+            Message::SendOutputsWithAmountCap {
+                secret_manager,
+                outputs,
+                max_amount_per_block,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                let max_amount_per_block = max_amount_per_block
+                    .parse::<u64>()
+                    .map_err(|_| Error::InvalidAmount(max_amount_per_block))?;
+                let token_supply = self.client.get_token_supply().await?;
+                let outputs = outputs
+                    .iter()
+                    .map(|output| Output::try_from_dto(output, token_supply))
+                    .collect::<std::result::Result<Vec<Output>, DtoError>>()?;
+                Ok(Response::BlockIds(
+                    self.client
+                        .send_outputs_with_amount_cap(&secret_manager, outputs, max_amount_per_block)
+                        .await?,
+                ))
+            }
+            Message::ExportUtxoSnapshot {
+                secret_manager,
+                coin_type,
+                account_index,
+                gap_limit,
+                scan_deadline,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                let coin_type = coin_type.unwrap_or(SHIMMER_COIN_TYPE);
+                let gap_limit = gap_limit.unwrap_or(DEFAULT_GAP_LIMIT);
+                let snapshot = match scan_deadline {
+                    Some(scan_deadline) => {
+                        self.client
+                            .export_utxo_snapshot_with_deadline(
+                                &secret_manager,
+                                coin_type,
+                                account_index,
+                                gap_limit,
+                                Duration::from_millis(scan_deadline),
+                            )
+                            .await?
+                    }
+                    None => {
+                        self.client
+                            .export_utxo_snapshot(&secret_manager, coin_type, account_index, gap_limit)
+                            .await?
+                    }
+                };
+                Ok(Response::UtxoSnapshot(snapshot))
+            }
+            Message::GetFirstFundedAddress {
+                secret_manager,
+                coin_type,
+                account_index,
+                gap_limit,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                Ok(Response::FirstFundedAddress(
+                    self.client
+                        .get_first_funded_address(
+                            &secret_manager,
+                            coin_type.unwrap_or(SHIMMER_COIN_TYPE),
+                            account_index,
+                            gap_limit.unwrap_or(DEFAULT_GAP_LIMIT),
+                        )
+                        .await?,
+                ))
+            }
+            Message::CanAfford {
+                secret_manager,
+                coin_type,
+                account_index,
+                gap_limit,
+                outputs,
+            } => {
+                let secret_manager = (&secret_manager).try_into()?;
+                let token_supply = self.client.get_token_supply().await?;
+                let outputs = outputs
+                    .iter()
+                    .map(|output| Output::try_from_dto(output, token_supply))
+                    .collect::<std::result::Result<Vec<Output>, DtoError>>()?;
+                Ok(Response::Affordability(
+                    self.client
+                        .can_afford(
+                            &secret_manager,
+                            coin_type.unwrap_or(SHIMMER_COIN_TYPE),
+                            account_index,
+                            gap_limit.unwrap_or(DEFAULT_GAP_LIMIT),
+                            &outputs,
+                        )
+                        .await?,
+                ))
+            }
+            Message::AddressOutputIds { address, output_type } => Ok(Response::OutputIds(
+                self.client.address_output_ids(&address, output_type).await?,
+            )),
             Message::FindInputs { addresses, amount } => Ok(Response::Inputs(
                 self.client
                     .find_inputs(addresses, amount)
@@ -605,9 +1018,41 @@ impl ClientMessageHandler {
                     .map(UtxoInputDto::from)
                     .collect(),
             )),
-            Message::FindOutputs { output_ids, addresses } => Ok(Response::Outputs(
-                self.client.find_outputs(&output_ids, &addresses).await?,
-            )),
+            Message::FindOutputs {
+                output_ids,
+                addresses,
+                keep_duplicates,
+                min_amount,
+                max_amount,
+                group_by_milestone,
+            } => {
+                let parse_amount = |amount: String| amount.parse::<u64>().map_err(|_| Error::InvalidAmount(amount));
+                let min_amount = min_amount.map(parse_amount).transpose()?;
+                let max_amount = max_amount.map(parse_amount).transpose()?;
+
+                let mut outputs = if keep_duplicates {
+                    self.client
+                        .find_outputs_with_duplicates(&output_ids, &addresses)
+                        .await?
+                } else {
+                    self.client.find_outputs(&output_ids, &addresses).await?
+                };
+
+                if min_amount.is_some() || max_amount.is_some() {
+                    outputs = filter_output_responses_by_amount(
+                        outputs,
+                        &(min_amount.unwrap_or(0)..=max_amount.unwrap_or(u64::MAX)),
+                    )?;
+                }
+
+                match group_by_milestone {
+                    Some(max_milestones) => Ok(Response::MilestoneOutputs(group_output_responses_by_milestone(
+                        outputs,
+                        max_milestones,
+                    ))),
+                    None => Ok(Response::Outputs(outputs)),
+                }
+            }
             Message::Reattach { block_id } => {
                 let (block_id, block) = self.client.reattach(&block_id).await?;
                 Ok(Response::Reattached((block_id, BlockDto::from(&block))))
@@ -642,12 +1087,34 @@ impl ClientMessageHandler {
             Message::ParseBech32Address { address } => Ok(Response::ParsedBech32Address(AddressDto::from(
                 &Client::parse_bech32_address(&address)?,
             ))),
-            Message::IsAddressValid { address } => Ok(Response::IsAddressValid(Client::is_address_valid(&address))),
+            Message::ParseAddress { address } => Ok(Response::ParsedBech32Address(AddressDto::from(
+                &Client::parse_address(&address)?,
+            ))),
+            Message::ParseOutputId { output_id } => Ok(Response::UtxoInput(UtxoInputDto::from(
+                &Client::parse_output_id(&output_id)?,
+            ))),
+            Message::IsAddressValid { address, expected_hrp } => Ok(Response::IsAddressValid(
+                Client::validate_address(&address, expected_hrp.as_deref()).is_ok(),
+            )),
+            Message::ValidateAddress { address, expected_hrp } => {
+                Client::validate_address(&address, expected_hrp.as_deref())?;
+                Ok(Response::Ok)
+            }
             Message::GenerateMnemonic => Ok(Response::GeneratedMnemonic(Client::generate_mnemonic()?)),
-            Message::MnemonicToHexSeed { mut mnemonic } => {
-                let response = Response::MnemonicHexSeed(Client::mnemonic_to_hex_seed(&mnemonic)?);
+            Message::GenerateMnemonicWithWordCount { word_count } => Ok(Response::GeneratedMnemonic(
+                Client::generate_mnemonic_with_word_count(word_count)?,
+            )),
+            Message::MnemonicToHexSeed {
+                mut mnemonic,
+                mut passphrase,
+            } => {
+                let response = Response::MnemonicHexSeed(Client::mnemonic_to_hex_seed_with_passphrase(
+                    &mnemonic,
+                    passphrase.as_deref().unwrap_or_default(),
+                )?);
 
                 mnemonic.zeroize();
+                passphrase.zeroize();
 
                 Ok(response)
             }
@@ -659,6 +1126,10 @@ impl ClientMessageHandler {
                 let payload = TransactionPayload::try_from_dto_unverified(&payload)?;
                 Ok(Response::TransactionId(payload.id()))
             }
+            Message::ComputeOutputIds { payload } => {
+                let payload = TransactionPayload::try_from_dto_unverified(&payload)?;
+                Ok(Response::OutputIds(Client::compute_output_ids(&payload)?))
+            }
             Message::ComputeAliasId { output_id } => Ok(Response::AliasId(AliasId::from(&output_id))),
             Message::ComputeNftId { output_id } => Ok(Response::NftId(NftId::from(&output_id))),
             Message::ComputeFoundryId {
@@ -674,6 +1145,159 @@ impl ClientMessageHandler {
             Message::HashTransactionEssence { essence } => Ok(Response::TransactionEssenceHash(prefix_hex::encode(
                 TransactionEssence::try_from_dto_unverified(&essence)?.hash(),
             ))),
+            Message::HashTransactionEssenceBytes { essence_bytes } => {
+                let essence = TransactionEssence::unpack_unverified(prefix_hex::decode::<Vec<u8>, _>(&essence_bytes)?)?;
+                Ok(Response::TransactionEssenceHash(prefix_hex::encode(essence.hash())))
+            }
+            Message::VerifyPow { block, min_pow_score } => Ok(Response::PowVerification(
+                self.client
+                    .verify_pow(&prefix_hex::decode::<Vec<u8>, _>(&block)?, min_pow_score)
+                    .await?,
+            )),
+            Message::VerifyTransactionInclusionProof { proof, transaction_id } => {
+                let proof =
+                    TransactionInclusionProof::try_from_dto(&proof, &self.client.get_protocol_parameters().await?)?;
+                Ok(Response::IsTransactionInclusionProofValid(
+                    proof.verify(&transaction_id),
+                ))
+            }
+        }
+    }
+}
+
+// Decodes a `0x` prefixed hex string, any other string is taken as UTF-8.
+fn decode_hex_or_utf8(value: String) -> Result<Vec<u8>> {
+    if value.starts_with("0x") {
+        Ok(prefix_hex::decode(value)?)
+    } else {
+        Ok(value.into_bytes())
+    }
+}
+
+// Serializes anything that is handed to the bindings with the output options of the client, so MQTT events are
+// formatted the same way as responses.
+fn to_json<T: Serialize>(client: &Client, value: &T) -> Result<String> {
+    if client.emit_nulls && client.address_format == AddressFormat::Bech32 && !client.decode_tagged_data_utf8 {
+        return Ok(serde_json::to_string(value)?);
+    }
+
+    let mut value = serde_json::to_value(value)?;
+    if !client.emit_nulls {
+        remove_null_fields(&mut value);
+    }
+    if client.address_format != AddressFormat::Bech32 {
+        format_addresses(&mut value, client.address_format);
+    }
+    if client.decode_tagged_data_utf8 {
+        // The `payload` of the response itself is its data, not a block payload.
+        if let Some(data) = value.get_mut("payload") {
+            decode_tagged_data_utf8(data);
+        }
+    }
+    Ok(serde_json::to_string(&value)?)
+}
+
+// Removes all object fields with a `null` value, also in nested objects and arrays.
+fn remove_null_fields(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(remove_null_fields);
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(remove_null_fields),
+        _ => {}
+    }
+}
+
+// Object fields of the response DTOs that contain a bech32 encoded address.
+const ADDRESS_FIELDS: [&str; 2] = ["address", "consolidationAddress"];
+
+// Represents the bech32 encoded addresses of the response according to the address format. Only the generated
+// addresses and the known address fields of objects are changed, so addresses that were explicitly converted to
+// bech32, like with `HexToBech32`, are kept. As a list of generated addresses can't have additional fields, it stays
+// bech32 encoded with `AddressFormat::Both`.
+fn format_addresses(response: &mut serde_json::Value, address_format: AddressFormat) {
+    if response.get("type").and_then(serde_json::Value::as_str) == Some("generatedAddresses") {
+        if let (Some(serde_json::Value::Array(addresses)), AddressFormat::Hex) =
+            (response.get_mut("payload"), address_format)
+        {
+            for address in addresses {
+                if let Some(hex) = address.as_str().and_then(packed_address_hex) {
+                    *address = serde_json::Value::String(hex);
+                }
+            }
+        }
+    } else if let Some(payload) = response.get_mut("payload") {
+        format_address_fields(payload, address_format);
+    }
+}
+
+// Formats the known address fields, also in nested objects and arrays. `AddressFormat::Both` keeps the bech32 field
+// and adds the hex representation as a field with the `Hex` suffix, like `addressHex`.
+fn format_address_fields(value: &mut serde_json::Value, address_format: AddressFormat) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut hex_fields = Vec::new();
+            for (field, value) in map.iter_mut() {
+                let hex = ADDRESS_FIELDS
+                    .contains(&field.as_str())
+                    .then(|| value.as_str().and_then(packed_address_hex))
+                    .flatten();
+                match (hex, address_format) {
+                    (Some(hex), AddressFormat::Hex) => *value = serde_json::Value::String(hex),
+                    (Some(hex), AddressFormat::Both) => hex_fields.push((format!("{field}Hex"), hex)),
+                    (Some(_), AddressFormat::Bech32) => {}
+                    (None, _) => format_address_fields(value, address_format),
+                }
+            }
+            for (field, hex) in hex_fields {
+                map.insert(field, serde_json::Value::String(hex));
+            }
+        }
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| format_address_fields(value, address_format)),
+        _ => {}
+    }
+}
+
+// Returns the hex encoded packed address, which starts with the address kind, or `None` if it's not a bech32 address.
+fn packed_address_hex(bech32: &str) -> Option<String> {
+    Address::try_from_bech32(bech32)
+        .ok()
+        .map(|(_, address)| prefix_hex::encode(address.pack_to_vec()))
+}
+
+// Adds the tag and data of tagged data payloads decoded as UTF-8 as `tagUtf8` and `dataUtf8`, also in nested objects
+// and arrays. Only values of `payload` fields that deserialize to a tagged data payload are changed, so other objects
+// with the same `type` stay untouched. Fields that aren't valid UTF-8 are skipped.
+fn decode_tagged_data_utf8(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(payload) = map.get_mut("payload") {
+                add_tagged_data_utf8(payload);
+            }
+            map.values_mut().for_each(decode_tagged_data_utf8);
+        }
+        serde_json::Value::Array(values) => values.iter_mut().for_each(decode_tagged_data_utf8),
+        _ => {}
+    }
+}
+
+fn add_tagged_data_utf8(payload: &mut serde_json::Value) {
+    let tagged_data = match PayloadDto::deserialize(&*payload) {
+        Ok(PayloadDto::TaggedData(tagged_data)) => tagged_data,
+        _ => return,
+    };
+
+    if let serde_json::Value::Object(map) = payload {
+        for (hex, utf8_field) in [(tagged_data.tag, "tagUtf8"), (tagged_data.data, "dataUtf8")] {
+            let utf8 = prefix_hex::decode::<Vec<u8>, _>(hex)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok());
+            if let Some(utf8) = utf8 {
+                map.insert(utf8_field.to_string(), serde_json::Value::String(utf8));
+            }
         }
     }
 }
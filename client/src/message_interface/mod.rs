@@ -3,11 +3,12 @@
 
 //! Message interface for bindings
 
+mod event;
 mod message;
 mod message_handler;
 mod response;
 
-pub use self::{message::Message, message_handler::ClientMessageHandler, response::Response};
+pub use self::{event::Event, message::Message, message_handler::ClientMessageHandler, response::Response};
 use crate::{ClientBuilder, Result};
 
 /// Create message handler with client options
@@ -33,9 +33,23 @@ use iota_types::{
 };
 use serde::Serialize;
 
+#[cfg(feature = "mqtt")]
+use crate::mqtt::Topic;
+#[cfg(not(target_family = "wasm"))]
+use crate::node_manager::node::NodeSyncStatus;
 #[cfg(feature = "ledger_nano")]
 use crate::secret::LedgerNanoStatus;
-use crate::{api::PreparedTransactionDataDto, node_manager::node::Node, Error, NetworkInfoDto, NodeInfoWrapper};
+use crate::{
+    api::{
+        AddressBalanceDelta, AddressTotal, AddressWithInternal, Affordability, BatchResponseDto,
+        BlockMetadataWithState, ConsolidationCheckpoint, FundedAddress, MilestoneOutputs, MilestoneTimestamp,
+        PendingState, PowVerification, PreparedTransactionDataDto, Supply, TransactionInclusionProofDto, TypedBlockDto,
+        UtxoSnapshot,
+    },
+    node_api::core::{BlockInclusionState, OutputSpentState},
+    node_manager::node::Node,
+    Error, NetworkInfoDto, NodeHealth, NodeInfoWrapper,
+};
 
 /// The response message.
 #[derive(Serialize, Debug)]
@@ -51,6 +65,14 @@ pub enum Response {
     /// - [`GenerateAddresses`](crate::message_interface::Message::GenerateAddresses)
     GeneratedAddresses(Vec<String>),
     /// Response for:
+    /// - [`GenerateAllAddresses`](crate::message_interface::Message::GenerateAllAddresses)
+    GeneratedAllAddresses(Vec<AddressWithInternal>),
+    /// Response for:
+    /// - [`GetSubscribedTopics`](crate::message_interface::Message::GetSubscribedTopics)
+    #[cfg(feature = "mqtt")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
+    Topics(Vec<Topic>),
+    /// Response for:
     /// - [`GetNode`](crate::message_interface::Message::GetNode)
     Node(Node),
     /// Response for:
@@ -58,7 +80,8 @@ pub enum Response {
     NetworkInfo(NetworkInfoDto),
     /// Response for:
     /// - [`GetNetworkId`](crate::message_interface::Message::GetNetworkId)
-    NetworkId(u64),
+    // Using a String to prevent overflow issues in other languages
+    NetworkId(String),
     /// Response for:
     /// - [`GetBech32Hrp`](crate::message_interface::Message::GetBech32Hrp)
     Bech32Hrp(String),
@@ -72,6 +95,9 @@ pub enum Response {
     /// - [`GetProtocolParameters`](crate::message_interface::Message::GetProtocolParameters)
     ProtocolParameters(ProtocolParametersDto),
     /// Response for:
+    /// - [`ComputeRequiredStorageDeposit`](crate::message_interface::Message::ComputeRequiredStorageDeposit)
+    RequiredStorageDeposit(String),
+    /// Response for:
     /// - [`GetLocalPow`](crate::message_interface::Message::GetLocalPow)
     LocalPow(bool),
     /// Response for:
@@ -96,24 +122,39 @@ pub enum Response {
     #[cfg(not(target_family = "wasm"))]
     UnhealthyNodes(HashSet<Node>),
     /// Response for:
+    /// - [`GetNodeSyncStatus`](crate::message_interface::Message::GetNodeSyncStatus)
+    #[cfg(not(target_family = "wasm"))]
+    NodeSyncStatus(NodeSyncStatus),
+    /// Response for:
     /// - [`GetHealth`](crate::message_interface::Message::GetHealth)
     Health(bool),
     /// Response for:
+    /// - [`GetNodesHealth`](crate::message_interface::Message::GetNodesHealth)
+    NodesHealth(Vec<NodeHealth>),
+    /// Response for:
     /// - [`GetNodeInfo`](crate::message_interface::Message::GetNodeInfo)
     NodeInfo(NodeInfo),
     /// Response for:
     /// - [`GetInfo`](crate::message_interface::Message::GetInfo)
     Info(NodeInfoWrapper),
     /// Response for:
+    /// - [`GetLatestMilestoneTimestamp`](crate::message_interface::Message::GetLatestMilestoneTimestamp)
+    MilestoneTimestamp(MilestoneTimestamp),
+    /// Response for:
     /// - [`GetPeers`](crate::message_interface::Message::GetPeers)
     Peers(Vec<PeerDto>),
     /// Response for:
+    /// - [`GetPeer`](crate::message_interface::Message::GetPeer)
+    /// - [`AddPeer`](crate::message_interface::Message::AddPeer)
+    Peer(PeerDto),
+    /// Response for:
     /// - [`GetTips`](crate::message_interface::Message::GetTips)
     Tips(Vec<BlockId>),
     /// Response for:
     /// - [`GetBlock`](crate::message_interface::Message::GetBlock)
+    /// - [`GetCreatingBlock`](crate::message_interface::Message::GetCreatingBlock)
     /// - [`GetIncludedBlock`](crate::message_interface::Message::GetIncludedBlock)
-    Block(BlockDto),
+    Block(TypedBlockDto),
     /// Response for:
     /// - [`BuildAndPostBlock`](crate::message_interface::Message::BuildAndPostBlock)
     /// - [`PostBlockPayload`](crate::message_interface::Message::PostBlockPayload)
@@ -121,7 +162,16 @@ pub enum Response {
     BlockIdWithBlock(BlockId, BlockDto),
     /// Response for:
     /// - [`GetBlockMetadata`](crate::message_interface::Message::GetBlockMetadata)
-    BlockMetadata(BlockMetadataResponse),
+    BlockMetadata(BlockMetadataWithState),
+    /// Response for:
+    /// - [`GetBlocksReferencedByMilestone`](crate::message_interface::Message::GetBlocksReferencedByMilestone)
+    BlocksMetadata(Vec<BlockMetadataResponse>),
+    /// Response for:
+    /// - [`GetInclusionStates`](crate::message_interface::Message::GetInclusionStates)
+    InclusionStates(BatchResponseDto<BlockInclusionState>),
+    /// Response for:
+    /// - [`CheckOutputsSpent`](crate::message_interface::Message::CheckOutputsSpent)
+    OutputsSpent(BatchResponseDto<OutputSpentState>),
     /// Response for:
     /// - [`GetBlockRaw`](crate::message_interface::Message::GetBlockRaw)
     BlockRaw(Vec<u8>),
@@ -133,10 +183,19 @@ pub enum Response {
     OutputMetadata(OutputMetadataDto),
     /// Response for:
     /// - [`GetOutputs`](crate::message_interface::Message::GetOutputs)
+    OutputsBatch(BatchResponseDto<OutputWithMetadataResponse>),
+    /// Response for:
     /// - [`TryGetOutputs`](crate::message_interface::Message::TryGetOutputs)
     /// - [`FindOutputs`](crate::message_interface::Message::FindOutputs)
     Outputs(Vec<OutputWithMetadataResponse>),
     /// Response for:
+    /// - [`FindOutputs`](crate::message_interface::Message::FindOutputs) with `groupByMilestone`
+    MilestoneOutputs(Vec<MilestoneOutputs>),
+    /// Response for:
+    /// - [`FindConfirmedAncestor`](crate::message_interface::Message::FindConfirmedAncestor)
+    ConfirmedAncestor(Option<Vec<BlockId>>),
+    /// Response for:
+    /// - [`GetConfirmingMilestone`](crate::message_interface::Message::GetConfirmingMilestone)
     /// - [`GetMilestoneById`](crate::message_interface::Message::GetMilestoneById)
     /// - [`GetMilestoneByIndex`](crate::message_interface::Message::GetMilestoneByIndex)
     Milestone(MilestonePayloadDto),
@@ -149,6 +208,18 @@ pub enum Response {
     /// - [`GetUtxoChangesByIndex`](crate::message_interface::Message::GetUtxoChangesByIndex)
     MilestoneUtxoChanges(MilestoneUTXOChanges),
     /// Response for:
+    /// - [`GetMilestones`](crate::message_interface::Message::GetMilestones)
+    Milestones(Vec<MilestonePayloadDto>),
+    /// Response for:
+    /// - [`GetUtxoChangesByIndexRange`](crate::message_interface::Message::GetUtxoChangesByIndexRange)
+    MilestonesUtxoChanges(Vec<MilestoneUTXOChanges>),
+    /// Response for:
+    /// - [`GetAddressTotal`](crate::message_interface::Message::GetAddressTotal)
+    AddressTotal(AddressTotal),
+    /// Response for:
+    /// - [`GetAddressBalanceDelta`](crate::message_interface::Message::GetAddressBalanceDelta)
+    AddressBalanceDelta(AddressBalanceDelta),
+    /// Response for:
     /// - [`GetReceipts`](crate::message_interface::Message::GetReceipts)
     /// - [`GetReceiptsMigratedAt`](crate::message_interface::Message::GetReceiptsMigratedAt)
     Receipts(Vec<ReceiptDto>),
@@ -156,11 +227,18 @@ pub enum Response {
     /// - [`GetTreasury`](crate::message_interface::Message::GetTreasury)
     Treasury(TreasuryResponse),
     /// Response for:
+    /// - [`GetSupply`](crate::message_interface::Message::GetSupply)
+    Supply(Supply),
+    /// Response for:
     /// - [`AliasOutputId`](crate::message_interface::Message::AliasOutputId)
     /// - [`NftOutputId`](crate::message_interface::Message::NftOutputId)
     /// - [`FoundryOutputId`](crate::message_interface::Message::FoundryOutputId)
     OutputId(OutputId),
     /// Response for:
+    /// - [`ComputeOutputIds`](crate::message_interface::Message::ComputeOutputIds)
+    /// - [`AddressOutputIds`](crate::message_interface::Message::AddressOutputIds)
+    OutputIds(Vec<OutputId>),
+    /// Response for:
     /// - [`BasicOutputIds`](crate::message_interface::Message::BasicOutputIds)
     /// - [`AliasOutputIds`](crate::message_interface::Message::AliasOutputIds)
     /// - [`NftOutputIds`](crate::message_interface::Message::NftOutputIds)
@@ -168,7 +246,7 @@ pub enum Response {
     OutputIdsResponse(OutputIdsResponse),
     /// Response for:
     /// - [`FindBlocks`](crate::message_interface::Message::FindBlocks)
-    Blocks(Vec<BlockDto>),
+    BlocksBatch(BatchResponseDto<BlockDto>),
     /// Response for:
     /// - [`RetryUntilIncluded`](crate::message_interface::Message::RetryUntilIncluded)
     RetryUntilIncludedSuccessful(Vec<(BlockId, BlockDto)>),
@@ -176,6 +254,27 @@ pub enum Response {
     /// - [`ConsolidateFunds`](crate::message_interface::Message::ConsolidateFunds)
     ConsolidatedFunds(String),
     /// Response for:
+    /// - [`ConsolidationCheckpoint`](crate::message_interface::Message::ConsolidationCheckpoint)
+    /// - [`ConsolidateFundsFromCheckpoint`](crate::message_interface::Message::ConsolidateFundsFromCheckpoint)
+    ConsolidationCheckpoint(ConsolidationCheckpoint),
+    /// Response for:
+    /// - [`SendOutputsWithAmountCap`](crate::message_interface::Message::SendOutputsWithAmountCap)
+    /// - [`CheckPendingBlocks`](crate::message_interface::Message::CheckPendingBlocks)
+    /// - [`ConsolidateFundsWithBlockIds`](crate::message_interface::Message::ConsolidateFundsWithBlockIds)
+    BlockIds(Vec<BlockId>),
+    /// Response for:
+    /// - [`ExportPendingState`](crate::message_interface::Message::ExportPendingState)
+    PendingState(PendingState),
+    /// Response for:
+    /// - [`ExportUtxoSnapshot`](crate::message_interface::Message::ExportUtxoSnapshot)
+    UtxoSnapshot(UtxoSnapshot),
+    /// Response for:
+    /// - [`CanAfford`](crate::message_interface::Message::CanAfford)
+    Affordability(Affordability),
+    /// Response for:
+    /// - [`GetFirstFundedAddress`](crate::message_interface::Message::GetFirstFundedAddress)
+    FirstFundedAddress(Option<FundedAddress>),
+    /// Response for:
     /// - [`FindInputs`](crate::message_interface::Message::FindInputs)
     Inputs(Vec<UtxoInputDto>),
     /// Response for:
@@ -191,18 +290,24 @@ pub enum Response {
     Bech32ToHex(String),
     /// Response for:
     /// - [`AliasIdToBech32`](crate::message_interface::Message::AliasIdToBech32)
+    /// - [`GetNextUnusedAddress`](crate::message_interface::Message::GetNextUnusedAddress)
     /// - [`HexPublicKeyToBech32Address`](crate::message_interface::Message::HexPublicKeyToBech32Address)
     /// - [`HexToBech32`](crate::message_interface::Message::HexToBech32)
     /// - [`NftIdToBech32`](crate::message_interface::Message::NftIdToBech32)
     Bech32Address(String),
     /// Response for:
     /// - [`ParseBech32Address`](crate::message_interface::Message::ParseBech32Address)
+    /// - [`ParseAddress`](crate::message_interface::Message::ParseAddress)
     ParsedBech32Address(AddressDto),
     /// Response for:
+    /// - [`ParseOutputId`](crate::message_interface::Message::ParseOutputId)
+    UtxoInput(UtxoInputDto),
+    /// Response for:
     /// - [`IsAddressValid`](crate::message_interface::Message::IsAddressValid)
     IsAddressValid(bool),
     /// Response for:
     /// - [`GenerateMnemonic`](crate::message_interface::Message::GenerateMnemonic)
+    /// - [`GenerateMnemonicWithWordCount`](crate::message_interface::Message::GenerateMnemonicWithWordCount)
     GeneratedMnemonic(String),
     /// Response for:
     /// - [`MnemonicToHexSeed`](crate::message_interface::Message::MnemonicToHexSeed)
@@ -210,7 +315,9 @@ pub enum Response {
     /// Response for:
     /// - [`BlockId`](crate::message_interface::Message::BlockId)
     /// - [`PostBlock`](crate::message_interface::Message::PostBlock)
+    /// - [`PostBlockJson`](crate::message_interface::Message::PostBlockJson)
     /// - [`PostBlockRaw`](crate::message_interface::Message::PostBlockRaw)
+    /// - [`PostTaggedData`](crate::message_interface::Message::PostTaggedData)
     BlockId(BlockId),
     /// Response for:
     /// - [`TransactionId`](crate::message_interface::Message::TransactionId)
@@ -229,13 +336,31 @@ pub enum Response {
     Faucet(String),
     /// Response for:
     /// - [`HashTransactionEssence`](crate::message_interface::Message::HashTransactionEssence)
+    /// - [`HashTransactionEssenceBytes`](crate::message_interface::Message::HashTransactionEssenceBytes)
     TransactionEssenceHash(String),
     /// Response for:
+    /// - [`VerifyPow`](crate::message_interface::Message::VerifyPow)
+    PowVerification(PowVerification),
+    /// Response for:
+    /// - [`GetTransactionInclusionProof`](crate::message_interface::Message::GetTransactionInclusionProof)
+    TransactionInclusionProof(TransactionInclusionProofDto),
+    /// Response for:
+    /// - [`VerifyTransactionInclusionProof`](crate::message_interface::Message::VerifyTransactionInclusionProof)
+    IsTransactionInclusionProofValid(bool),
+    /// Response for:
     /// - [`ClearListeners`](crate::message_interface::Message::ClearListeners)
     /// - [`StoreMnemonic`](crate::message_interface::Message::StoreMnemonic)
+    /// - [`ImportPendingState`](crate::message_interface::Message::ImportPendingState)
+    /// - [`RemovePeer`](crate::message_interface::Message::RemovePeer)
+    /// - [`ValidateAddress`](crate::message_interface::Message::ValidateAddress)
     Ok,
     /// Response for any method that returns an error.
     Error(Error),
     /// Response for any method that panics.
-    Panic(String),
+    Panic {
+        /// The panic message
+        message: String,
+        /// The backtrace of the panic
+        stack: String,
+    },
 }
@@ -6,26 +6,70 @@
 pub mod routes;
 
 use iota_types::{
-    api::core::response::OutputWithMetadataResponse,
-    block::output::{dto::OutputMetadataDto, OutputId},
+    api::core::{dto::LedgerInclusionStateDto, response::OutputWithMetadataResponse},
+    block::{
+        output::{dto::OutputMetadataDto, OutputId},
+        BlockId,
+    },
 };
 
 #[cfg(not(target_family = "wasm"))]
 use crate::constants::MAX_PARALLEL_API_REQUESTS;
-use crate::{Client, Result};
+use crate::{
+    api::{BatchResponse, BatchResults},
+    Client, Result,
+};
+
+/// The ledger inclusion state of a block, as returned by [`Client::get_inclusion_states()`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockInclusionState {
+    /// The block ID.
+    #[serde(rename = "blockId")]
+    pub block_id: BlockId,
+    /// The ledger inclusion state, `None` if the block isn't referenced by a milestone yet.
+    #[serde(rename = "ledgerInclusionState")]
+    pub ledger_inclusion_state: Option<LedgerInclusionStateDto>,
+}
+
+/// Whether an output is spent, as returned by [`Client::check_outputs_spent()`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OutputSpentState {
+    /// The output ID.
+    #[serde(rename = "outputId")]
+    pub output_id: OutputId,
+    /// Whether the output is spent.
+    #[serde(rename = "isSpent")]
+    pub is_spent: bool,
+}
 
 impl Client {
-    /// Request outputs by their output ID in parallel
+    /// Request outputs by their output ID in parallel, at most
+    /// [`output_fetch_concurrency`](crate::ClientBuilder::with_output_fetch_concurrency) at once. If any request
+    /// fails, the error or, with [`BatchErrorMode::Collect`](crate::api::BatchErrorMode::Collect), `Error::Batch` with
+    /// the errors of all failed requests is returned. Use [`Client::get_outputs_batch()`] to get the successful outputs
+    /// together with the errors.
     pub async fn get_outputs(&self, output_ids: Vec<OutputId>) -> Result<Vec<OutputWithMetadataResponse>> {
-        let mut outputs = Vec::new();
+        self.get_outputs_batch(output_ids).await?.into_items()
+    }
+
+    /// Request outputs by their output ID like [`Client::get_outputs()`]. Failed requests are handled according to the
+    /// [`BatchErrorMode`](crate::api::BatchErrorMode) of the client, the indexes of the returned outputs and errors
+    /// refer to the provided output IDs.
+    pub async fn get_outputs_batch(
+        &self,
+        output_ids: Vec<OutputId>,
+    ) -> Result<BatchResponse<OutputWithMetadataResponse>> {
+        let mut outputs = BatchResults::new(self.batch_error_mode);
 
         #[cfg(target_family = "wasm")]
-        for output_id in output_ids {
-            outputs.push(self.get_output(&output_id).await?);
+        for (index, output_id) in output_ids.iter().enumerate() {
+            outputs.push(index, self.get_output(output_id).await)?;
         }
 
         #[cfg(not(target_family = "wasm"))]
-        for output_ids_chunk in output_ids.chunks(MAX_PARALLEL_API_REQUESTS).map(<[OutputId]>::to_vec) {
+        let chunk_size = self.output_fetch_concurrency.max(1);
+        #[cfg(not(target_family = "wasm"))]
+        for (chunk_index, output_ids_chunk) in output_ids.chunks(chunk_size).map(<[OutputId]>::to_vec).enumerate() {
             let mut tasks = Vec::new();
             for output_id in output_ids_chunk {
                 let client_ = self.clone();
@@ -38,12 +82,108 @@ impl Client {
                     .await
                 });
             }
-            for res in futures::future::try_join_all(tasks).await? {
-                let output_response = res?;
-                outputs.push(output_response);
+            for (index, res) in futures::future::try_join_all(tasks).await?.into_iter().enumerate() {
+                outputs.push(chunk_index * chunk_size + index, res)?;
             }
         }
-        Ok(outputs)
+
+        Ok(outputs.finish())
+    }
+
+    /// Request the ledger inclusion states of blocks in parallel. The state is `None` if a block isn't referenced by a
+    /// milestone yet. Failed requests, like for unknown block IDs, are handled according to the
+    /// [`BatchErrorMode`](crate::api::BatchErrorMode) of the client, the indexes refer to the provided block IDs.
+    pub async fn get_inclusion_states(&self, block_ids: Vec<BlockId>) -> Result<BatchResponse<BlockInclusionState>> {
+        let mut inclusion_states = BatchResults::new(self.batch_error_mode);
+
+        #[cfg(target_family = "wasm")]
+        for (index, block_id) in block_ids.into_iter().enumerate() {
+            let inclusion_state = self
+                .get_block_metadata(&block_id)
+                .await
+                .map(|metadata| BlockInclusionState {
+                    block_id,
+                    ledger_inclusion_state: metadata.ledger_inclusion_state,
+                });
+            inclusion_states.push(index, inclusion_state)?;
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        for (chunk_index, block_ids_chunk) in block_ids
+            .chunks(MAX_PARALLEL_API_REQUESTS)
+            .map(<[BlockId]>::to_vec)
+            .enumerate()
+        {
+            let mut tasks = Vec::new();
+            for block_id in block_ids_chunk {
+                let client_ = self.clone();
+
+                tasks.push(async move {
+                    tokio::spawn(async move {
+                        client_
+                            .get_block_metadata(&block_id)
+                            .await
+                            .map(|metadata| BlockInclusionState {
+                                block_id,
+                                ledger_inclusion_state: metadata.ledger_inclusion_state,
+                            })
+                    })
+                    .await
+                });
+            }
+            for (index, res) in futures::future::try_join_all(tasks).await?.into_iter().enumerate() {
+                inclusion_states.push(chunk_index * MAX_PARALLEL_API_REQUESTS + index, res)?;
+            }
+        }
+
+        Ok(inclusion_states.finish())
+    }
+
+    /// Request in parallel whether outputs are spent, only the output metadata is requested to keep the responses
+    /// small. Failed requests, like for unknown output IDs, are handled according to the
+    /// [`BatchErrorMode`](crate::api::BatchErrorMode) of the client, the indexes refer to the provided output IDs.
+    pub async fn check_outputs_spent(&self, output_ids: Vec<OutputId>) -> Result<BatchResponse<OutputSpentState>> {
+        let mut spent = BatchResults::new(self.batch_error_mode);
+
+        #[cfg(target_family = "wasm")]
+        for (index, output_id) in output_ids.into_iter().enumerate() {
+            let spent_state = self
+                .get_output_metadata(&output_id)
+                .await
+                .map(|metadata| OutputSpentState {
+                    output_id,
+                    is_spent: metadata.is_spent,
+                });
+            spent.push(index, spent_state)?;
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        let chunk_size = self.output_fetch_concurrency.max(1);
+        #[cfg(not(target_family = "wasm"))]
+        for (chunk_index, output_ids_chunk) in output_ids.chunks(chunk_size).map(<[OutputId]>::to_vec).enumerate() {
+            let mut tasks = Vec::new();
+            for output_id in output_ids_chunk {
+                let client_ = self.clone();
+
+                tasks.push(async move {
+                    tokio::spawn(async move {
+                        client_
+                            .get_output_metadata(&output_id)
+                            .await
+                            .map(|metadata| OutputSpentState {
+                                output_id,
+                                is_spent: metadata.is_spent,
+                            })
+                    })
+                    .await
+                });
+            }
+            for (index, res) in futures::future::try_join_all(tasks).await?.into_iter().enumerate() {
+                spent.push(chunk_index * chunk_size + index, res)?;
+            }
+        }
+
+        Ok(spent.finish())
     }
 
     /// Request outputs by their output ID in parallel, ignoring failed requests
@@ -59,7 +199,10 @@ impl Client {
         }
 
         #[cfg(not(target_family = "wasm"))]
-        for output_ids_chunk in output_ids.chunks(MAX_PARALLEL_API_REQUESTS).map(<[OutputId]>::to_vec) {
+        for output_ids_chunk in output_ids
+            .chunks(self.output_fetch_concurrency.max(1))
+            .map(<[OutputId]>::to_vec)
+        {
             let mut tasks = Vec::new();
             for output_id in output_ids_chunk {
                 let client_ = self.clone();
@@ -85,7 +228,10 @@ impl Client {
         }
 
         #[cfg(not(target_family = "wasm"))]
-        for output_ids_chunk in output_ids.chunks(MAX_PARALLEL_API_REQUESTS).map(<[OutputId]>::to_vec) {
+        for output_ids_chunk in output_ids
+            .chunks(self.output_fetch_concurrency.max(1))
+            .map(<[OutputId]>::to_vec)
+        {
             let mut tasks = Vec::new();
             for output_id in output_ids_chunk {
                 let client_ = self.clone();
@@ -3,7 +3,7 @@
 
 //! Node core API routes.
 
-use std::str::FromStr;
+use std::{str::FromStr, sync::PoisonError};
 
 use iota_types::{
     api::core::{
@@ -28,7 +28,10 @@ use url::Url;
 
 use crate::{
     constants::{DEFAULT_API_TIMEOUT, DEFAULT_USER_AGENT},
-    node_manager::node::{Node, NodeAuth},
+    node_manager::{
+        http_client::HttpClient,
+        node::{Node, NodeAuth},
+    },
     Client, Error, Result,
 };
 
@@ -42,6 +45,15 @@ pub struct NodeInfoWrapper {
     pub url: String,
 }
 
+/// The health of a configured node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeHealth {
+    /// The url of the node
+    pub url: String,
+    /// If the node reported itself as healthy
+    pub healthy: bool,
+}
+
 impl Client {
     // Node routes.
 
@@ -52,14 +64,16 @@ impl Client {
 
         let mut url = Url::parse(url)?;
         url.set_path(path);
-        let status = crate::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
+        let status = self
+            .node_manager
+            .http_client
             .get(
                 Node {
                     url,
                     auth: None,
                     disabled: false,
                 },
-                DEFAULT_API_TIMEOUT,
+                self.get_info_timeout(),
             )
             .await?
             .status();
@@ -70,13 +84,39 @@ impl Client {
         }
     }
 
+    /// Returns the health of every configured node, including the primary node, sorted by url. A node that can't be
+    /// reached is reported as unhealthy.
+    /// GET /health
+    pub async fn get_nodes_health(&self) -> Result<Vec<NodeHealth>> {
+        let mut nodes = self
+            .node_manager
+            .primary_node
+            .iter()
+            .chain(self.node_manager.nodes.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        nodes.sort_by(|a, b| a.url.as_str().cmp(b.url.as_str()));
+        nodes.dedup_by(|a, b| a.url == b.url);
+
+        let timeout = self.get_info_timeout();
+        let health = futures::future::join_all(nodes.into_iter().map(|mut node| async move {
+            let url = node.url.to_string();
+            node.url.set_path("health");
+            let healthy = self.node_manager.http_client.get(node, timeout).await.is_ok();
+            NodeHealth { url, healthy }
+        }))
+        .await;
+
+        Ok(health)
+    }
+
     /// Returns the available API route groups of the node.
     /// GET /api/routes
     pub async fn get_routes(&self) -> Result<RoutesResponse> {
         let path = "api/routes";
 
         self.node_manager
-            .get_request(path, None, self.get_timeout(), false, false)
+            .get_request(path, None, self.get_info_timeout(), false, false)
             .await
     }
 
@@ -86,12 +126,21 @@ impl Client {
         let path = "api/core/v2/info";
 
         self.node_manager
-            .get_request(path, None, self.get_timeout(), false, false)
+            .get_request(path, None, self.get_info_timeout(), false, false)
             .await
     }
 
     /// GET /api/core/v2/info endpoint
     pub async fn get_node_info(url: &str, auth: Option<NodeAuth>) -> Result<InfoResponse> {
+        Self::get_node_info_with_client(&HttpClient::new(DEFAULT_USER_AGENT.to_string()), url, auth).await
+    }
+
+    // Requests the node info with the user agent and headers of the http client.
+    pub(crate) async fn get_node_info_with_client(
+        http_client: &HttpClient,
+        url: &str,
+        auth: Option<NodeAuth>,
+    ) -> Result<InfoResponse> {
         let mut url = crate::node_manager::builder::validate_url(Url::parse(url)?)?;
         if let Some(auth) = &auth {
             if let Some((name, password)) = &auth.basic_auth_name_pwd {
@@ -103,7 +152,7 @@ impl Client {
         let path = "api/core/v2/info";
         url.set_path(path);
 
-        let resp: InfoResponse = crate::node_manager::http_client::HttpClient::new(DEFAULT_USER_AGENT.to_string())
+        let resp: InfoResponse = http_client
             .get(
                 Node {
                     url,
@@ -128,7 +177,7 @@ impl Client {
 
         let resp = self
             .node_manager
-            .get_request::<TipsResponse>(path, None, self.get_timeout(), false, false)
+            .get_request::<TipsResponse>(path, None, self.get_tips_timeout(), false, false)
             .await?;
 
         resp.tips
@@ -145,7 +194,7 @@ impl Client {
         let path = "api/core/v2/blocks";
         let local_pow = self.get_local_pow();
         let timeout = if local_pow {
-            self.get_timeout()
+            self.get_post_block_timeout()
         } else {
             self.get_remote_pow_timeout()
         };
@@ -161,17 +210,12 @@ impl Client {
             Err(e) => {
                 if let Error::Node(e) = e {
                     let fallback_to_local_pow = self.get_fallback_to_local_pow();
-                    // hornet and bee return different error blocks
-                    if (e == *"No available nodes with remote Pow"
-                        || e.contains("proof of work is not enabled")
-                        || e.contains("`Pow` not enabled"))
-                        && fallback_to_local_pow
-                    {
+                    if is_remote_pow_unsupported(&e) && fallback_to_local_pow {
                         // Without this we get:within `impl Future<Output = [async output]>`, the trait `Send` is not
                         // implemented for `std::sync::RwLockWriteGuard<'_, NetworkInfo>`
                         {
                             let mut client_network_info =
-                                self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
+                                self.network_info.write().unwrap_or_else(PoisonError::into_inner);
                             // switch to local PoW
                             client_network_info.local_pow = true;
                         }
@@ -180,7 +224,7 @@ impl Client {
                             Ok(block) => {
                                 // reset local PoW state
                                 let mut client_network_info =
-                                    self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
+                                    self.network_info.write().unwrap_or_else(PoisonError::into_inner);
                                 client_network_info.local_pow = false;
                                 block
                             }
@@ -188,7 +232,7 @@ impl Client {
                                 // reset local PoW state
                                 self.network_info
                                     .write()
-                                    .map_err(|_| crate::Error::PoisonError)?
+                                    .unwrap_or_else(PoisonError::into_inner)
                                     .local_pow = false;
                                 return Err(e);
                             }
@@ -198,6 +242,8 @@ impl Client {
                         self.node_manager
                             .post_request_json(path, timeout, serde_json::to_value(block_dto)?, true)
                             .await?
+                    } else if is_remote_pow_unsupported(&e) {
+                        return Err(Error::RemotePowNotSupported(e));
                     } else {
                         return Err(Error::Node(e));
                     }
@@ -216,7 +262,7 @@ impl Client {
         let path = "api/core/v2/blocks";
         let local_pow = self.get_local_pow();
         let timeout = if local_pow {
-            self.get_timeout()
+            self.get_post_block_timeout()
         } else {
             self.get_remote_pow_timeout()
         };
@@ -231,17 +277,12 @@ impl Client {
             Err(e) => {
                 if let Error::Node(e) = e {
                     let fallback_to_local_pow = self.get_fallback_to_local_pow();
-                    // hornet and bee return different error blocks
-                    if (e == *"No available nodes with remote Pow"
-                        || e.contains("proof of work is not enabled")
-                        || e.contains("`Pow` not enabled"))
-                        && fallback_to_local_pow
-                    {
+                    if is_remote_pow_unsupported(&e) && fallback_to_local_pow {
                         // Without this we get:within `impl Future<Output = [async output]>`, the trait `Send` is not
                         // implemented for `std::sync::RwLockWriteGuard<'_, NetworkInfo>`
                         {
                             let mut client_network_info =
-                                self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
+                                self.network_info.write().unwrap_or_else(PoisonError::into_inner);
                             // switch to local PoW
                             client_network_info.local_pow = true;
                         }
@@ -250,7 +291,7 @@ impl Client {
                             Ok(block) => {
                                 // reset local PoW state
                                 let mut client_network_info =
-                                    self.network_info.write().map_err(|_| crate::Error::PoisonError)?;
+                                    self.network_info.write().unwrap_or_else(PoisonError::into_inner);
                                 client_network_info.local_pow = false;
                                 block
                             }
@@ -258,7 +299,7 @@ impl Client {
                                 // reset local PoW state
                                 self.network_info
                                     .write()
-                                    .map_err(|_| crate::Error::PoisonError)?
+                                    .unwrap_or_else(PoisonError::into_inner)
                                     .local_pow = false;
                                 return Err(e);
                             }
@@ -266,6 +307,8 @@ impl Client {
                         self.node_manager
                             .post_request_bytes(path, timeout, &block_with_local_pow.pack_to_vec(), true)
                             .await?
+                    } else if is_remote_pow_unsupported(&e) {
+                        return Err(Error::RemotePowNotSupported(e));
                     } else {
                         return Err(Error::Node(e));
                     }
@@ -278,6 +321,20 @@ impl Client {
         Ok(BlockId::from_str(&resp.block_id)?)
     }
 
+    /// Returns the BlockId of the submitted block, provided as JSON. The block gets validated before it's posted as
+    /// JSON, if the node doesn't accept JSON it's posted as bytes instead.
+    /// POST JSON or bytes to /api/core/v2/blocks
+    pub async fn post_block_json(&self, block: serde_json::Value) -> Result<BlockId> {
+        let block_dto = serde_json::from_value::<BlockDto>(block)?;
+        let block = Block::try_from_dto(&block_dto, &self.get_protocol_parameters().await?)?;
+
+        match self.post_block(&block).await {
+            // 415 Unsupported Media Type
+            Err(Error::ResponseError { code: 415, .. }) => self.post_block_raw(&block).await,
+            res => res,
+        }
+    }
+
     /// Finds a block by its BlockId. This method returns the given block object.
     /// GET /api/core/v2/blocks/{BlockId}
     pub async fn get_block(&self, block_id: &BlockId) -> Result<Block> {
@@ -322,7 +379,7 @@ impl Client {
         let path = &format!("api/core/v2/outputs/{output_id}");
 
         self.node_manager
-            .get_request(path, None, self.get_timeout(), false, true)
+            .get_request(path, None, self.get_output_timeout(), true, true)
             .await
     }
 
@@ -332,7 +389,7 @@ impl Client {
         let path = &format!("api/core/v2/outputs/{output_id}");
 
         self.node_manager
-            .get_request_bytes(path, None, self.get_timeout())
+            .get_request_bytes(path, None, self.get_output_timeout())
             .await
     }
 
@@ -342,7 +399,7 @@ impl Client {
         let path = &format!("api/core/v2/outputs/{output_id}/metadata");
 
         self.node_manager
-            .get_request::<OutputMetadataDto>(path, None, self.get_timeout(), false, true)
+            .get_request::<OutputMetadataDto>(path, None, self.get_output_timeout(), false, true)
             .await
     }
 
@@ -440,7 +497,9 @@ impl Client {
         }
     }
 
-    /// Gets the milestone by the given milestone id.
+    /// Gets the milestone by the given milestone id, as the packed bytes that got signed.
+    /// Returns [`Error::NotFound`] for pruned milestones too, the index of an unknown milestone ID can't be compared
+    /// with the pruning index.
     /// GET /api/core/v2/milestones/{milestoneId}
     pub async fn get_milestone_by_id_raw(&self, milestone_id: &MilestoneId) -> Result<Vec<u8>> {
         let path = &format!("api/core/v2/milestones/{milestone_id}");
@@ -479,14 +538,32 @@ impl Client {
         }
     }
 
-    /// Gets the milestone by the given milestone index.
+    /// Gets the milestone by the given milestone index, as the packed bytes that got signed.
+    /// Returns [`Error::Pruned`] if the node already pruned the milestone.
     /// GET /api/core/v2/milestones/{index}
     pub async fn get_milestone_by_index_raw(&self, index: u32) -> Result<Vec<u8>> {
         let path = &format!("api/core/v2/milestones/by-index/{index}");
 
-        self.node_manager
+        let result = self
+            .node_manager
             .get_request_bytes(path, None, self.get_timeout())
-            .await
+            .await;
+        self.map_pruned_milestone(result, index).await
+    }
+
+    // Maps `Error::NotFound` to `Error::Pruned` if the node pruned the milestone.
+    async fn map_pruned_milestone<T>(&self, result: Result<T>, index: u32) -> Result<T> {
+        match result {
+            Err(Error::NotFound(url)) => {
+                let pruning_index = self.get_info().await?.node_info.status.pruning_index;
+                if index <= pruning_index {
+                    Err(Error::Pruned(format!("milestone {index}")))
+                } else {
+                    Err(Error::NotFound(url))
+                }
+            }
+            res => res,
+        }
     }
 
     /// Gets all UTXO changes of a milestone by its milestone index.
@@ -501,6 +578,8 @@ impl Client {
 
     // Peers routes.
 
+    /// Returns the peers of the node, including their gossip metrics.
+    /// Returns [`Error::Unauthorized`] if the node denies access to the route.
     /// GET /api/core/v2/peers
     pub async fn get_peers(&self) -> Result<Vec<PeerDto>> {
         let path = "api/core/v2/peers";
@@ -508,11 +587,62 @@ impl Client {
         let resp = self
             .node_manager
             .get_request::<PeersResponse>(path, None, self.get_timeout(), false, false)
-            .await?;
+            .await
+            .map_err(map_unauthorized)?;
 
         Ok(resp.0)
     }
 
+    /// Returns the peer with the provided peer ID, including its gossip metrics.
+    /// Returns [`Error::PeerNotFound`] if the node doesn't know the peer and [`Error::Unauthorized`] if the node denies
+    /// access to the route.
+    /// GET /api/core/v2/peers/{peerId}
+    pub async fn get_peer(&self, peer_id: &str) -> Result<PeerDto> {
+        let path = &format!("api/core/v2/peers/{peer_id}");
+
+        match self
+            .node_manager
+            .get_request::<PeerDto>(path, None, self.get_timeout(), false, false)
+            .await
+        {
+            Err(Error::NotFound(_)) => Err(Error::PeerNotFound(peer_id.to_string())),
+            result => result.map_err(map_unauthorized),
+        }
+    }
+
+    /// Adds a peer to the node and returns it. The request is only sent to the first node, which is the primary node if
+    /// set. Returns [`Error::Unauthorized`] if the node denies access to the route.
+    /// POST /api/core/v2/peers
+    pub async fn add_peer(&self, multi_address: &str, alias: Option<&str>) -> Result<PeerDto> {
+        let path = "api/core/v2/peers";
+        let mut json = serde_json::json!({ "multiAddress": multi_address });
+        if let Some(alias) = alias {
+            json["alias"] = alias.into();
+        }
+
+        self.node_manager
+            .post_request_json_to_first_node(path, self.get_timeout(), json)
+            .await
+            .map_err(map_unauthorized)
+    }
+
+    /// Removes a peer from the node. The request is only sent to the first node, which is the primary node if set.
+    /// Returns [`Error::PeerNotFound`] if the node doesn't know the peer and [`Error::Unauthorized`] if the node denies
+    /// access to the route.
+    /// DELETE /api/core/v2/peers/{peerId}
+    pub async fn remove_peer(&self, peer_id: &str) -> Result<()> {
+        let path = &format!("api/core/v2/peers/{peer_id}");
+
+        match self
+            .node_manager
+            .delete_request_to_first_node(path, self.get_timeout())
+            .await
+        {
+            Err(Error::ResponseError { code: 404, .. }) => Err(Error::PeerNotFound(peer_id.to_string())),
+            result => result.map_err(map_unauthorized),
+        }
+    }
+
     // // RoutePeer is the route for getting peers by their peerID.
     // // GET returns the peer
     // // DELETE deletes the peer.
@@ -533,3 +663,20 @@ impl Client {
     // // POST creates a snapshot (full, delta or both).
     // RouteControlSnapshotsCreate = "/control/snapshots/create"
 }
+
+// Peer routes are protected by default, so a denied request gets a distinct error.
+fn map_unauthorized(error: Error) -> Error {
+    match error {
+        Error::ResponseError {
+            code: 401 | 403, url, ..
+        } => Error::Unauthorized(url),
+        error => error,
+    }
+}
+
+// Whether a node error means that remote PoW isn't available, hornet and bee return different messages.
+fn is_remote_pow_unsupported(error: &str) -> bool {
+    error.eq_ignore_ascii_case("no available nodes with remote Pow")
+        || error.contains("proof of work is not enabled")
+        || error.contains("`Pow` not enabled")
+}
@@ -17,4 +17,7 @@ pub enum Error {
     /// Invalid topic.
     #[error("invalid topic {0}")]
     InvalidTopic(String),
+    /// The broker didn't acknowledge the request in time.
+    #[error("the MQTT broker didn't acknowledge the request in time")]
+    Timeout,
 }
@@ -7,21 +7,23 @@ mod error;
 pub mod types;
 
 use std::{
-    sync::{Arc, RwLock as StdRwLock},
-    time::Instant,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock as StdRwLock},
 };
 
-use crypto::utils;
 use iota_types::block::{
     payload::{milestone::ReceiptMilestoneOption, MilestonePayload},
     Block,
 };
 use log::warn;
 use packable::PackableExt;
-use rumqttc::{AsyncClient, Event, EventLoop, Incoming, MqttOptions, NetworkOptions, QoS, SubscribeFilter, Transport};
+use rumqttc::{
+    AsyncClient, Event, EventLoop, Incoming, MqttOptions, NetworkOptions, Outgoing, QoS, SubscribeFilter, Transport,
+};
 use tokio::sync::{
+    oneshot,
     watch::{Receiver as WatchReceiver, Sender},
-    RwLock,
+    Mutex as AsyncMutex, RwLock,
 };
 
 pub use self::{error::Error, types::*};
@@ -42,11 +44,42 @@ impl Client {
         MqttManager::new(self).with_topics(topics).subscribe(callback).await
     }
 
-    /// Unsubscribe from MQTT events.
+    /// Unsubscribe from MQTT events and wait until the broker acknowledged it. If no topics are provided, all topics
+    /// are unsubscribed. Topics that aren't subscribed are ignored.
     pub async fn unsubscribe(&self, topics: Vec<Topic>) -> Result<(), Error> {
         MqttManager::new(self).with_topics(topics).unsubscribe().await
     }
 
+    /// Subscribe to MQTT events with a callback and return its handler, so only this subscription can be removed with
+    /// [`Client::unsubscribe_handler()`] without affecting other subscriptions to the same topics.
+    pub(crate) async fn subscribe_handler<C: Fn(&TopicEvent) + Send + Sync + 'static>(
+        &self,
+        topics: Vec<Topic>,
+        callback: C,
+    ) -> Result<Arc<TopicHandler>, Error> {
+        MqttManager::new(self)
+            .with_topics(topics)
+            .subscribe_handler(callback)
+            .await
+    }
+
+    /// Remove the handler from the topics, a topic is only unsubscribed once it has no handlers left.
+    pub(crate) async fn unsubscribe_handler(
+        &self,
+        topics: Vec<Topic>,
+        handler: &Arc<TopicHandler>,
+    ) -> Result<(), Error> {
+        MqttManager::new(self)
+            .with_topics(topics)
+            .unsubscribe_handler(handler)
+            .await
+    }
+
+    /// Returns the currently subscribed MQTT topics.
+    pub async fn subscribed_topics(&self) -> Vec<Topic> {
+        self.mqtt_topic_handlers.read().await.keys().cloned().collect()
+    }
+
     /// Returns the mqtt event receiver.
     pub fn mqtt_event_receiver(&self) -> WatchReceiver<MqttEvent> {
         self.mqtt_event_channel.1.clone()
@@ -81,9 +114,7 @@ async fn set_mqtt_client(client: &Client) -> Result<(), Error> {
         };
         for node in &nodes {
             let host = node.url.host_str().expect("can't get host from URL");
-            let mut entropy = [0u8; 8];
-            utils::rand::fill(&mut entropy)?;
-            let id = format!("iotars{}", prefix_hex::encode(entropy));
+            let id = client.broker_options.generate_client_id()?;
             let port = client.broker_options.port;
             let mut uri = format!(
                 "{}://{}:{}/api/mqtt/v1",
@@ -123,6 +154,7 @@ async fn set_mqtt_client(client: &Client) -> Result<(), Error> {
                     client.mqtt_topic_handlers.clone(),
                     client.broker_options.clone(),
                     client.mqtt_event_channel.0.clone(),
+                    client.mqtt_unsubscribe_acks.clone(),
                     connection,
                     client.network_info.clone(),
                 );
@@ -137,6 +169,7 @@ fn poll_mqtt(
     mqtt_topic_handlers_guard: Arc<RwLock<TopicHandlerMap>>,
     options: BrokerOptions,
     event_sender: Arc<Sender<MqttEvent>>,
+    unsubscribe_acks: Arc<UnsubscribeAcks>,
     mut event_loop: EventLoop,
     network_info: Arc<StdRwLock<NetworkInfo>>,
 ) {
@@ -152,7 +185,7 @@ fn poll_mqtt(
             // can perform the re-subscriptions and reset `is_subscribed` to true.
             // we need the flag since the first ConnAck must be ignored.
             let mut is_subscribed = true;
-            let mut error_instant = Instant::now();
+            // failed connection attempts since the last ConnAck
             let mut connection_failure_count = 0;
 
             loop {
@@ -161,6 +194,7 @@ fn poll_mqtt(
 
                 match event {
                     Ok(Event::Incoming(Incoming::ConnAck(_))) => {
+                        connection_failure_count = 0;
                         let _ = event_sender.send(MqttEvent::Connected);
                         if !is_subscribed {
                             is_subscribed = true;
@@ -187,7 +221,10 @@ fn poll_mqtt(
                                 let event = {
                                     if topic.contains("blocks") || topic.contains("included-block") {
                                         let payload = &*p.payload;
-                                        let protocol_parameters = &network_info.read().unwrap().protocol_parameters;
+                                        let protocol_parameters = &network_info
+                                            .read()
+                                            .unwrap_or_else(PoisonError::into_inner)
+                                            .protocol_parameters;
 
                                         match Block::unpack_verified(payload, protocol_parameters) {
                                             Ok(block) => Ok(TopicEvent {
@@ -201,7 +238,10 @@ fn poll_mqtt(
                                         }
                                     } else if topic.contains("milestones") {
                                         let payload = &*p.payload;
-                                        let protocol_parameters = &network_info.read().unwrap().protocol_parameters;
+                                        let protocol_parameters = &network_info
+                                            .read()
+                                            .unwrap_or_else(PoisonError::into_inner)
+                                            .protocol_parameters;
 
                                         match MilestonePayload::unpack_verified(payload, protocol_parameters) {
                                             Ok(milestone_payload) => Ok(TopicEvent {
@@ -215,7 +255,10 @@ fn poll_mqtt(
                                         }
                                     } else if topic.contains("receipts") {
                                         let payload = &*p.payload;
-                                        let protocol_parameters = &network_info.read().unwrap().protocol_parameters;
+                                        let protocol_parameters = &network_info
+                                            .read()
+                                            .unwrap_or_else(PoisonError::into_inner)
+                                            .protocol_parameters;
 
                                         match ReceiptMilestoneOption::unpack_verified(payload, protocol_parameters) {
                                             Ok(receipt) => Ok(TopicEvent {
@@ -248,18 +291,25 @@ fn poll_mqtt(
                             }
                         });
                     }
+                    Ok(Event::Outgoing(Outgoing::Unsubscribe(pkid))) => {
+                        unsubscribe_acks.sent(pkid);
+                    }
+                    Ok(Event::Incoming(Incoming::UnsubAck(unsuback))) => {
+                        unsubscribe_acks.acknowledged(unsuback.pkid);
+                    }
                     Err(_) => {
-                        if error_instant.elapsed().as_secs() < 5 {
-                            connection_failure_count += 1;
-                        } else {
-                            connection_failure_count = 1;
-                        }
+                        // The subscriptions are lost on reconnection, so sent unsubscribe requests are done
+                        unsubscribe_acks.release_sent();
+                        connection_failure_count += 1;
                         if connection_failure_count == options.max_reconnection_attempts {
                             let _ = event_sender.send(MqttEvent::Disconnected);
                             break;
                         }
-                        error_instant = Instant::now();
+                        if connection_failure_count == 1 {
+                            let _ = event_sender.send(MqttEvent::Reconnecting);
+                        }
                         is_subscribed = false;
+                        tokio::time::sleep(options.reconnection_backoff(connection_failure_count)).await;
                     }
                     _ => {}
                 }
@@ -340,8 +390,16 @@ impl<'a> MqttTopicManager<'a> {
         self,
         callback: C,
     ) -> Result<(), Error> {
-        let cb =
-            Arc::new(Box::new(callback) as Box<dyn Fn(&crate::node_api::mqtt::TopicEvent) + Send + Sync + 'static>);
+        self.subscribe_handler(callback).await?;
+        Ok(())
+    }
+
+    // Subscribes to the given topics with the callback and returns its handler.
+    async fn subscribe_handler<C: Fn(&crate::node_api::mqtt::TopicEvent) + Send + Sync + 'static>(
+        self,
+        callback: C,
+    ) -> Result<Arc<TopicHandler>, Error> {
+        let cb = Arc::new(Box::new(callback) as TopicHandler);
         set_mqtt_client(self.client).await?;
         self.client
             .mqtt_client
@@ -369,11 +427,12 @@ impl<'a> MqttTopicManager<'a> {
                 }
             }
         }
-        Ok(())
+        Ok(cb)
     }
 
-    /// Unsubscribe from the given topics.
-    /// If no topics were provided, the function will unsubscribe from every subscribed topic.
+    /// Unsubscribe from the given topics and wait until the broker acknowledged it.
+    /// If no topics were provided, the function will unsubscribe from every subscribed topic. Topics that aren't
+    /// subscribed are ignored.
     pub async fn unsubscribe(self) -> Result<(), Error> {
         let topics = {
             let mqtt_topic_handlers = &self.client.mqtt_topic_handlers;
@@ -382,28 +441,174 @@ impl<'a> MqttTopicManager<'a> {
                 mqtt_topic_handlers.keys().cloned().collect()
             } else {
                 self.topics
+                    .into_iter()
+                    .filter(|topic| mqtt_topic_handlers.contains_key(topic))
+                    .collect::<Vec<_>>()
             }
         };
 
-        if let Some(client) = &*self.client.mqtt_client.write().await {
-            for topic in &topics {
-                client.unsubscribe(topic.topic()).await?;
-            }
+        if topics.is_empty() {
+            return Ok(());
         }
 
         let empty_topic_handlers = {
             let mqtt_topic_handlers = &self.client.mqtt_topic_handlers;
             let mut mqtt_topic_handlers = mqtt_topic_handlers.write().await;
-            for topic in topics {
-                mqtt_topic_handlers.remove(&topic);
+            for topic in &topics {
+                mqtt_topic_handlers.remove(topic);
             }
             mqtt_topic_handlers.is_empty()
         };
 
-        if self.client.broker_options.automatic_disconnect && empty_topic_handlers {
-            MqttManager::new(self.client).disconnect().await?;
+        unsubscribe_from_broker(self.client, topics, empty_topic_handlers).await
+    }
+
+    // Removes the handler from the given topics and unsubscribes from the topics without handlers left.
+    async fn unsubscribe_handler(self, handler: &Arc<TopicHandler>) -> Result<(), Error> {
+        let (topics, empty_topic_handlers) = {
+            let mqtt_topic_handlers = &self.client.mqtt_topic_handlers;
+            let mut mqtt_topic_handlers = mqtt_topic_handlers.write().await;
+            let mut topics = Vec::new();
+            for topic in self.topics {
+                if let Some(handlers) = mqtt_topic_handlers.get_mut(&topic) {
+                    handlers.retain(|h| !Arc::ptr_eq(h, handler));
+                    if handlers.is_empty() {
+                        mqtt_topic_handlers.remove(&topic);
+                        topics.push(topic);
+                    }
+                }
+            }
+            (topics, mqtt_topic_handlers.is_empty())
+        };
+
+        if topics.is_empty() {
+            return Ok(());
         }
 
-        Ok(())
+        unsubscribe_from_broker(self.client, topics, empty_topic_handlers).await
+    }
+}
+
+// Unsubscribes the topics, whose handlers are already removed, from the broker and waits until it acknowledged it.
+async fn unsubscribe_from_broker(client: &Client, topics: Vec<Topic>, empty_topic_handlers: bool) -> Result<(), Error> {
+    let mqtt_client = client.mqtt_client.read().await.clone();
+
+    if let Some(mqtt_client) = &mqtt_client {
+        let mut acks = Vec::new();
+        for topic in &topics {
+            acks.push(
+                client
+                    .mqtt_unsubscribe_acks
+                    .unsubscribe(mqtt_client, topic.topic())
+                    .await?,
+            );
+        }
+
+        // Every unsubscribed topic is acknowledged by the broker, a dropped waiter means the connection was lost and
+        // with it the subscription.
+        tokio::time::timeout(client.broker_options.timeout, futures::future::join_all(acks))
+            .await
+            .map_err(|_| Error::Timeout)?;
+    }
+
+    if client.broker_options.automatic_disconnect && empty_topic_handlers {
+        MqttManager::new(client).disconnect().await?;
+    }
+
+    Ok(())
+}
+
+/// Unsubscribe requests that wait for the acknowledgement of the broker, which refers to them by their packet ID.
+#[derive(Default)]
+pub(crate) struct UnsubscribeAcks {
+    // Keeps the requests in the order of `queued` while they are queued
+    send_lock: AsyncMutex<()>,
+    waiters: Mutex<UnsubscribeWaiters>,
+}
+
+#[derive(Default)]
+struct UnsubscribeWaiters {
+    // Requests that are queued in the MQTT client, in the order they are sent
+    queued: VecDeque<oneshot::Sender<()>>,
+    // Requests that are sent to the broker, by their packet ID
+    sent: HashMap<u16, oneshot::Sender<()>>,
+}
+
+impl UnsubscribeAcks {
+    // Queues an unsubscribe request, the returned receiver resolves once the broker acknowledged it.
+    async fn unsubscribe(&self, mqtt_client: &AsyncClient, topic: &str) -> Result<oneshot::Receiver<()>, Error> {
+        let _send_lock = self.send_lock.lock().await;
+        let (sender, receiver) = oneshot::channel();
+        self.waiters().queued.push_back(sender);
+        if let Err(e) = mqtt_client.unsubscribe(topic).await {
+            self.waiters().queued.pop_back();
+            return Err(e.into());
+        }
+        Ok(receiver)
+    }
+
+    // The event loop sent the oldest queued request with the packet ID.
+    fn sent(&self, pkid: u16) {
+        let mut waiters = self.waiters();
+        if let Some(waiter) = waiters.queued.pop_front() {
+            waiters.sent.insert(pkid, waiter);
+        }
+    }
+
+    fn acknowledged(&self, pkid: u16) {
+        if let Some(waiter) = self.waiters().sent.remove(&pkid) {
+            let _ = waiter.send(());
+        }
+    }
+
+    // Requests that are still queued are sent after reconnecting.
+    fn release_sent(&self) {
+        self.waiters().sent.clear();
+    }
+
+    fn waiters(&self) -> MutexGuard<'_, UnsubscribeWaiters> {
+        self.waiters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn unsubscribe_handler_keeps_other_subscriptions() {
+        let client = Client::builder().with_ignore_node_health().finish().unwrap();
+        let topic = Topic::try_new("milestone-info/latest".to_string()).unwrap();
+        let handler: Arc<TopicHandler> = Arc::new(Box::new(|_: &TopicEvent| {}));
+        let other_handler: Arc<TopicHandler> = Arc::new(Box::new(|_: &TopicEvent| {}));
+        client
+            .mqtt_topic_handlers
+            .write()
+            .await
+            .insert(topic.clone(), vec![handler.clone(), other_handler.clone()]);
+
+        client.unsubscribe_handler(vec![topic.clone()], &handler).await.unwrap();
+        assert_eq!(client.subscribed_topics().await, vec![topic.clone()]);
+
+        client.unsubscribe_handler(vec![topic], &other_handler).await.unwrap();
+        assert!(client.subscribed_topics().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_acks_are_matched_by_packet_id() {
+        let (mqtt_client, _event_loop) = AsyncClient::new(MqttOptions::new("test", "localhost", 1883), 10);
+        let acks = UnsubscribeAcks::default();
+        let mut first = acks.unsubscribe(&mqtt_client, "milestones/latest").await.unwrap();
+        let mut second = acks.unsubscribe(&mqtt_client, "milestones/confirmed").await.unwrap();
+        acks.sent(1);
+        acks.sent(2);
+
+        // The acknowledgement of the second request doesn't complete the first one
+        acks.acknowledged(2);
+        assert!(second.try_recv().is_ok());
+        assert!(first.try_recv().is_err());
+
+        acks.acknowledged(1);
+        assert!(first.try_recv().is_ok());
     }
 }
@@ -5,7 +5,9 @@
 
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
+use crypto::utils;
 use iota_types::block::{
+    address::Address,
     payload::{milestone::ReceiptMilestoneOption, MilestonePayload},
     Block,
 };
@@ -15,7 +17,7 @@ use serde_json::Value;
 
 use super::Error;
 
-type TopicHandler = Box<dyn Fn(&TopicEvent) + Send + Sync>;
+pub(crate) type TopicHandler = Box<dyn Fn(&TopicEvent) + Send + Sync>;
 
 pub(crate) type TopicHandlerMap = HashMap<Topic, Vec<Arc<TopicHandler>>>;
 
@@ -49,6 +51,8 @@ pub enum MqttPayload {
 pub enum MqttEvent {
     /// Client was connected.
     Connected,
+    /// Connection was lost, the client reconnects with an exponential backoff and resubscribes the topics.
+    Reconnecting,
     /// Client was disconnected.
     Disconnected,
 }
@@ -68,6 +72,10 @@ pub struct BrokerOptions {
     pub(crate) port: u16,
     #[serde(default = "default_max_reconnection_attempts", rename = "maxReconnectionAttempts")]
     pub(crate) max_reconnection_attempts: usize,
+    #[serde(default = "default_max_reconnection_backoff", rename = "maxReconnectionBackoff")]
+    pub(crate) max_reconnection_backoff: Duration,
+    #[serde(default, rename = "clientId", skip_serializing_if = "Option::is_none")]
+    pub(crate) client_id: Option<String>,
 }
 
 fn default_broker_automatic_disconnect() -> bool {
@@ -90,6 +98,10 @@ fn default_max_reconnection_attempts() -> usize {
     0
 }
 
+fn default_max_reconnection_backoff() -> Duration {
+    Duration::from_secs(30)
+}
+
 impl Default for BrokerOptions {
     fn default() -> Self {
         Self {
@@ -98,6 +110,8 @@ impl Default for BrokerOptions {
             use_ws: default_broker_use_ws(),
             port: default_broker_port(),
             max_reconnection_attempts: default_max_reconnection_attempts(),
+            max_reconnection_backoff: default_max_reconnection_backoff(),
+            client_id: None,
         }
     }
 }
@@ -137,6 +151,39 @@ impl BrokerOptions {
         self.max_reconnection_attempts = max_reconnection_attempts;
         self
     }
+
+    /// Sets the maximum delay between reconnection attempts, the delay starts at one second and doubles with every
+    /// failed attempt. Default is 30 seconds.
+    pub fn max_reconnection_backoff(mut self, max_reconnection_backoff: Duration) -> Self {
+        self.max_reconnection_backoff = max_reconnection_backoff;
+        self
+    }
+
+    /// Returns the delay before the next reconnection attempt after `failed_attempts` attempts failed.
+    pub(crate) fn reconnection_backoff(&self, failed_attempts: usize) -> Duration {
+        let exponent = failed_attempts.saturating_sub(1).min(16) as u32;
+        Duration::from_secs(1)
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.max_reconnection_backoff)
+    }
+
+    /// Sets a fixed client ID for the MQTT connection, instead of a random one, like for brokers that only accept
+    /// known clients or for deterministic tests.
+    pub fn client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id.replace(client_id.into());
+        self
+    }
+
+    /// Returns a random client ID for a new MQTT connection, or the fixed one if set.
+    pub(crate) fn generate_client_id(&self) -> Result<String, Error> {
+        if let Some(client_id) = &self.client_id {
+            return Ok(client_id.clone());
+        }
+
+        let mut entropy = [0u8; 8];
+        utils::rand::fill(&mut entropy)?;
+        Ok(format!("iotars{}", prefix_hex::encode(entropy)))
+    }
 }
 
 /// A MQTT topic.
@@ -162,7 +209,12 @@ impl<'de> Deserialize<'de> for Topic {
 }
 
 impl Topic {
-    /// Creates a new topic and checks if it's valid.
+    /// Creates a new topic and checks if it's valid, otherwise [`Error::InvalidTopic`] is returned.
+    ///
+    /// Milestones are published on `milestone-info/latest` and `milestone-info/confirmed` as JSON with the index,
+    /// timestamp and milestone ID, and on `milestones` as the full milestone payload. The other accepted topics are
+    /// the block, block metadata, transaction, output and receipt topics of the node event API. Outputs of an address
+    /// are published on `outputs/unlock/address/{bech32 address}`, the address has to be a valid bech32 address.
     pub fn try_new(topic: impl Into<String>) -> Result<Self, Error> {
         let valid_topics = lazy_static!(
         RegexSet::new([
@@ -192,11 +244,19 @@ impl Topic {
         ]).expect("cannot build regex set") => RegexSet);
         let topic = topic.into();
 
-        if valid_topics.is_match(&topic) {
-            Ok(Self(topic))
-        } else {
-            Err(Error::InvalidTopic(topic))
+        if !valid_topics.is_match(&topic) {
+            return Err(Error::InvalidTopic(topic));
         }
+
+        // Check the address of unlock condition topics, the node would accept the subscription but never publish.
+        if let Some(unlock_condition) = topic.strip_prefix("outputs/unlock/") {
+            let address = unlock_condition.split('/').nth(1).unwrap_or_default();
+            if Address::try_from_bech32(address).is_err() {
+                return Err(Error::InvalidTopic(topic));
+            }
+        }
+
+        Ok(Self(topic))
     }
 
     /// Creates a new topic without checking if the given string represents a valid topic.
@@ -209,3 +269,39 @@ impl Topic {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_client_id() {
+        let broker_options = BrokerOptions::new().client_id("iotars-test");
+
+        assert_eq!(broker_options.generate_client_id().unwrap(), "iotars-test");
+        assert_eq!(broker_options.generate_client_id().unwrap(), "iotars-test");
+
+        let broker_options = serde_json::from_str::<BrokerOptions>(r#"{"clientId":"iotars-test"}"#).unwrap();
+        assert_eq!(broker_options.generate_client_id().unwrap(), "iotars-test");
+    }
+
+    #[test]
+    fn reconnection_backoff() {
+        let broker_options = BrokerOptions::new().max_reconnection_backoff(Duration::from_secs(10));
+
+        assert_eq!(broker_options.reconnection_backoff(1), Duration::from_secs(1));
+        assert_eq!(broker_options.reconnection_backoff(2), Duration::from_secs(2));
+        assert_eq!(broker_options.reconnection_backoff(4), Duration::from_secs(8));
+        assert_eq!(broker_options.reconnection_backoff(5), Duration::from_secs(10));
+        assert_eq!(broker_options.reconnection_backoff(usize::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn random_client_id() {
+        let broker_options = BrokerOptions::new();
+        let client_id = broker_options.generate_client_id().unwrap();
+
+        assert!(client_id.starts_with("iotars0x"));
+        assert_ne!(client_id, broker_options.generate_client_id().unwrap());
+    }
+}
@@ -14,15 +14,28 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::{
-    constants::{DEFAULT_MIN_QUORUM_SIZE, DEFAULT_QUORUM_THRESHOLD, DEFAULT_USER_AGENT, NODE_SYNC_INTERVAL},
+    constants::{
+        DEFAULT_MIN_QUORUM_SIZE, DEFAULT_NODE_API_PORT, DEFAULT_QUORUM_THRESHOLD, DEFAULT_USER_AGENT,
+        NODE_SYNC_INTERVAL,
+    },
     error::{Error, Result},
     node_manager::{
         http_client::HttpClient,
-        node::{Node, NodeAuth, NodeDto},
+        node::{Node, NodeAuth, NodeDiscovery, NodeDto},
         NodeManager,
     },
 };
 
+/// How read requests are retried on transient failures, like timeouts or 5xx responses of all nodes
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadRetry {
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay between attempts
+    pub backoff: Duration,
+}
+
 /// Node manager builder
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct NodeManagerBuilder {
@@ -44,6 +57,9 @@ pub struct NodeManagerBuilder {
     /// gets updated
     #[serde(rename = "nodeSyncInterval", default = "default_node_sync_interval")]
     pub node_sync_interval: Duration,
+    /// If the node syncing is disabled, requests are sent to the configured nodes directly then
+    #[serde(rename = "nodeSyncDisabled", default)]
+    pub node_sync_disabled: bool,
     /// If node quorum is enabled. Will compare the responses from multiple nodes and only returns the response if
     /// `quorum_threshold`% of the nodes return the same one
     #[serde(default)]
@@ -57,6 +73,22 @@ pub struct NodeManagerBuilder {
     /// The User-Agent header for requests
     #[serde(rename = "userAgent", default = "default_user_agent")]
     pub user_agent: String,
+    /// If a request should be sent to the next node when a response can't be parsed
+    #[serde(rename = "retryOnParseError", default = "default_retry_on_parse_error")]
+    pub retry_on_parse_error: bool,
+    /// If set, blocks are only posted to nodes that are healthy and whose confirmed milestone lags at most this many
+    /// milestones behind their latest milestone
+    #[serde(rename = "postMaxMilestoneLag", default)]
+    pub post_max_milestone_lag: Option<u32>,
+    /// Retries of read requests on transient failures, posting is never retried
+    #[serde(rename = "readRetry", default)]
+    pub read_retry: Option<ReadRetry>,
+    /// Discovers further nodes from the peers of a seed node on every sync
+    #[serde(rename = "nodeDiscovery", default)]
+    pub node_discovery: Option<NodeDiscovery>,
+    /// Headers which are added to every request, like for an authenticating proxy in front of the nodes
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
 }
 
 fn default_user_agent() -> String {
@@ -67,6 +99,10 @@ fn default_node_sync_interval() -> Duration {
     NODE_SYNC_INTERVAL
 }
 
+fn default_retry_on_parse_error() -> bool {
+    true
+}
+
 fn default_min_quorum_size() -> usize {
     DEFAULT_MIN_QUORUM_SIZE
 }
@@ -159,6 +195,11 @@ impl NodeManagerBuilder {
         self
     }
 
+    pub(crate) fn with_node_sync_disabled(mut self) -> Self {
+        self.node_sync_disabled = true;
+        self
+    }
+
     pub(crate) fn with_node_auth(mut self, url: &str, auth: Option<NodeAuth>) -> Result<Self> {
         let mut url = validate_url(Url::parse(url)?)?;
         if let Some(auth) = &auth {
@@ -188,6 +229,23 @@ impl NodeManagerBuilder {
         Ok(self)
     }
 
+    pub(crate) fn with_node_discovery(
+        mut self,
+        seed_url: &str,
+        max_nodes: usize,
+        require_healthy: bool,
+    ) -> Result<Self> {
+        let seed_node = Node::from(validate_url(Url::parse(seed_url)?)?);
+        self.nodes.insert(NodeDto::Node(seed_node.clone()));
+        self.node_discovery.replace(NodeDiscovery {
+            seed_node,
+            max_nodes,
+            require_healthy,
+            api_port: DEFAULT_NODE_API_PORT,
+        });
+        Ok(self)
+    }
+
     pub(crate) fn with_node_sync_interval(mut self, node_sync_interval: Duration) -> Self {
         self.node_sync_interval = node_sync_interval;
         self
@@ -213,7 +271,34 @@ impl NodeManagerBuilder {
         self
     }
 
+    pub(crate) fn with_retry_on_parse_error(mut self, retry_on_parse_error: bool) -> Self {
+        self.retry_on_parse_error = retry_on_parse_error;
+        self
+    }
+
+    pub(crate) fn with_post_max_milestone_lag(mut self, max_milestone_lag: u32) -> Self {
+        self.post_max_milestone_lag.replace(max_milestone_lag);
+        self
+    }
+
+    pub(crate) fn with_read_retry(mut self, read_retry: ReadRetry) -> Self {
+        self.read_retry.replace(read_retry);
+        self
+    }
+
+    pub(crate) fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    // The HTTP client for all requests, including the ones of the node syncing.
+    pub(crate) fn http_client(&self) -> HttpClient {
+        HttpClient::new(self.user_agent.clone()).with_headers(self.headers.clone())
+    }
+
     pub(crate) fn build(self, healthy_nodes: Arc<RwLock<HashMap<Node, InfoResponse>>>) -> NodeManager {
+        let http_client = self.http_client();
+
         NodeManager {
             primary_node: self.primary_node.map(|node| node.into()),
             primary_pow_node: self.primary_pow_node.map(|node| node.into()),
@@ -221,13 +306,17 @@ impl NodeManagerBuilder {
             permanodes: self
                 .permanodes
                 .map(|nodes| nodes.into_iter().map(|node| node.into()).collect()),
-            ignore_node_health: self.ignore_node_health,
+            // Without syncing there are no healthy nodes, so the configured ones are used
+            ignore_node_health: self.ignore_node_health || self.node_sync_disabled,
             node_sync_interval: self.node_sync_interval,
             healthy_nodes,
             quorum: self.quorum,
             min_quorum_size: self.min_quorum_size,
             quorum_threshold: self.quorum_threshold,
-            http_client: HttpClient::new(self.user_agent),
+            retry_on_parse_error: self.retry_on_parse_error,
+            post_max_milestone_lag: self.post_max_milestone_lag,
+            read_retry: self.read_retry,
+            http_client,
         }
     }
 }
@@ -241,10 +330,16 @@ impl Default for NodeManagerBuilder {
             permanodes: None,
             ignore_node_health: false,
             node_sync_interval: NODE_SYNC_INTERVAL,
+            node_sync_disabled: false,
             quorum: false,
             min_quorum_size: DEFAULT_MIN_QUORUM_SIZE,
             quorum_threshold: DEFAULT_QUORUM_THRESHOLD,
             user_agent: DEFAULT_USER_AGENT.to_string(),
+            retry_on_parse_error: true,
+            post_max_milestone_lag: None,
+            read_retry: None,
+            node_discovery: None,
+            headers: HashMap::new(),
         }
     }
 }
@@ -3,7 +3,7 @@
 
 //! The node manager that takes care of sending requests with healthy nodes and quorum if enabled
 
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use reqwest::RequestBuilder;
 use serde::de::DeserializeOwned;
@@ -21,7 +21,14 @@ impl Response {
     }
 
     pub(crate) async fn into_json<T: DeserializeOwned>(self) -> Result<T> {
-        self.0.json().await.map_err(Into::into)
+        let url = self.0.url().to_string();
+        let body = self.0.text().await?;
+
+        serde_json::from_str(&body).map_err(|e| Error::ResponseParse {
+            url,
+            error: e.to_string(),
+            body,
+        })
     }
 
     pub(crate) async fn into_text(self) -> Result<String> {
@@ -33,10 +40,11 @@ impl Response {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub(crate) struct HttpClient {
     client: reqwest::Client,
     user_agent: String,
+    headers: HashMap<String, String>,
 }
 
 impl HttpClient {
@@ -44,9 +52,27 @@ impl HttpClient {
         Self {
             client: reqwest::Client::new(),
             user_agent,
+            headers: HashMap::new(),
         }
     }
 
+    // Headers that are added to every request.
+    pub(crate) fn with_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    // Timeouts get a distinct error, so they can be told apart from other request failures.
+    async fn send(request_builder: RequestBuilder, url: &url::Url) -> Result<reqwest::Response> {
+        request_builder.send().await.map_err(|e| {
+            if e.is_timeout() {
+                Error::Timeout(url.to_string())
+            } else {
+                e.into()
+            }
+        })
+    }
+
     async fn parse_response(response: reqwest::Response, url: &url::Url) -> Result<Response> {
         let status = response.status();
         if status.is_success() {
@@ -63,6 +89,10 @@ impl HttpClient {
     fn build_request(&self, request_builder: RequestBuilder, node: &Node, _timeout: Duration) -> RequestBuilder {
         let mut request_builder = request_builder.header(reqwest::header::USER_AGENT, &self.user_agent);
 
+        for (name, value) in &self.headers {
+            request_builder = request_builder.header(name, value);
+        }
+
         if let Some(node_auth) = &node.auth {
             if let Some(jwt) = &node_auth.jwt {
                 request_builder = request_builder.bearer_auth(jwt);
@@ -79,7 +109,7 @@ impl HttpClient {
         let mut request_builder = self.client.get(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         let start_time = instant::Instant::now();
-        let resp = request_builder.send().await?;
+        let resp = Self::send(request_builder, &node.url).await?;
         log::debug!(
             "GET: {:?} ms for {} {}",
             start_time.elapsed().as_millis(),
@@ -94,20 +124,30 @@ impl HttpClient {
         let mut request_builder = self.client.get(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         request_builder = request_builder.header("accept", "application/vnd.iota.serializer-v1");
-        let resp = request_builder.send().await?;
+        let resp = Self::send(request_builder, &node.url).await?;
         Self::parse_response(resp, &node.url).await
     }
 
     pub(crate) async fn post_json(&self, node: Node, timeout: Duration, json: Value) -> Result<Response> {
         let mut request_builder = self.client.post(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
-        Self::parse_response(request_builder.json(&json).send().await?, &node.url).await
+        Self::parse_response(Self::send(request_builder.json(&json), &node.url).await?, &node.url).await
+    }
+
+    pub(crate) async fn delete(&self, node: Node, timeout: Duration) -> Result<Response> {
+        let mut request_builder = self.client.delete(node.url.clone());
+        request_builder = self.build_request(request_builder, &node, timeout);
+        Self::parse_response(Self::send(request_builder, &node.url).await?, &node.url).await
     }
 
     pub(crate) async fn post_bytes(&self, node: Node, timeout: Duration, body: &[u8]) -> Result<Response> {
         let mut request_builder = self.client.post(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         request_builder = request_builder.header("Content-Type", "application/vnd.iota.serializer-v1");
-        Self::parse_response(request_builder.body(body.to_vec()).send().await?, &node.url).await
+        Self::parse_response(
+            Self::send(request_builder.body(body.to_vec()), &node.url).await?,
+            &node.url,
+        )
+        .await
     }
 }
@@ -10,18 +10,20 @@ pub mod node;
 pub(crate) mod syncing;
 
 use std::{
+    cmp::Reverse,
     collections::{HashMap, HashSet},
     sync::{Arc, RwLock},
     time::Duration,
 };
 
-use iota_types::api::core::response::InfoResponse;
+use futures::Future;
+use iota_types::api::core::response::{InfoResponse, StatusResponse};
 use serde_json::Value;
 
 use self::{http_client::HttpClient, node::Node};
 use crate::{
     error::{Error, Result},
-    node_manager::builder::NodeManagerBuilder,
+    node_manager::builder::{NodeManagerBuilder, ReadRetry},
 };
 
 // The node manager takes care of selecting node(s) for requests until a result is returned or if quorum is enabled it
@@ -38,6 +40,9 @@ pub(crate) struct NodeManager {
     quorum: bool,
     min_quorum_size: usize,
     quorum_threshold: usize,
+    retry_on_parse_error: bool,
+    post_max_milestone_lag: Option<u32>,
+    read_retry: Option<ReadRetry>,
     pub(crate) http_client: HttpClient,
 }
 
@@ -53,7 +58,10 @@ impl std::fmt::Debug for NodeManager {
         d.field("healthy_nodes", &self.healthy_nodes);
         d.field("quorum", &self.quorum);
         d.field("min_quorum_size", &self.min_quorum_size);
-        d.field("quorum_threshold", &self.quorum_threshold).finish()
+        d.field("quorum_threshold", &self.quorum_threshold);
+        d.field("retry_on_parse_error", &self.retry_on_parse_error);
+        d.field("post_max_milestone_lag", &self.post_max_milestone_lag);
+        d.field("read_retry", &self.read_retry).finish()
     }
 }
 
@@ -95,6 +103,10 @@ impl NodeManager {
             }
         }
 
+        // Whether healthy nodes were skipped because they don't support remote PoW
+        #[cfg_attr(target_family = "wasm", allow(unused_mut))]
+        let mut skipped_pow_nodes = false;
+
         // Add other nodes in random order, so they are not always used in the same order
         let nodes_random_order = if !self.ignore_node_health {
             #[cfg(not(target_family = "wasm"))]
@@ -111,6 +123,7 @@ impl NodeManager {
                             if info.features.contains(&pow_feature) {
                                 Some(n.clone())
                             } else {
+                                skipped_pow_nodes = true;
                                 None
                             }
                         } else {
@@ -138,6 +151,9 @@ impl NodeManager {
         nodes_with_modified_url.retain(|n| !n.disabled);
 
         if nodes_with_modified_url.is_empty() {
+            if skipped_pow_nodes {
+                return Err(Error::Node("no available nodes with remote Pow".into()));
+            }
             return Err(crate::Error::HealthyNodePoolEmpty);
         }
 
@@ -160,6 +176,7 @@ impl NodeManager {
         Ok(nodes_with_modified_url)
     }
 
+    // GET requests are idempotent, so they're retried on transient errors if configured
     pub(crate) async fn get_request<T: serde::de::DeserializeOwned + std::fmt::Debug + serde::Serialize>(
         &self,
         path: &str,
@@ -168,7 +185,20 @@ impl NodeManager {
         need_quorum: bool,
         prefer_permanode: bool,
     ) -> Result<T> {
-        let mut result: HashMap<String, usize> = HashMap::new();
+        self.with_read_retry(|| self.try_get_request(path, query, timeout, need_quorum, prefer_permanode))
+            .await
+    }
+
+    async fn try_get_request<T: serde::de::DeserializeOwned + std::fmt::Debug + serde::Serialize>(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        timeout: Duration,
+        need_quorum: bool,
+        prefer_permanode: bool,
+    ) -> Result<T> {
+        // Responses for quorum, by their comparison key, with the first response text and the number of nodes
+        let mut result: HashMap<String, (String, usize)> = HashMap::new();
         // primary_pow_node should only be used for post request with remote PoW
         // Get node urls and set path
         let nodes = self.get_nodes(path, query, false, prefer_permanode)?;
@@ -187,7 +217,7 @@ impl NodeManager {
         let wasm = true;
         #[cfg(not(target_family = "wasm"))]
         let wasm = false;
-        if !wasm && self.quorum && need_quorum && query.is_none() {
+        if !wasm && self.quorum && need_quorum {
             #[cfg(not(target_family = "wasm"))]
             {
                 let mut tasks = Vec::new();
@@ -204,8 +234,7 @@ impl NodeManager {
                                 log::warn!("couldn't convert node response to text");
                             },
                             |res_text| {
-                                let counters = result.entry(res_text).or_insert(0);
-                                *counters += 1;
+                                count_response(&mut result, res_text);
                                 result_counter += 1;
                             },
                         ),
@@ -238,19 +267,18 @@ impl NodeManager {
 
                                 match res.into_json::<T>().await {
                                     Ok(result_data) => {
-                                        let counters = result.entry(serde_json::to_string(&result_data)?).or_insert(0);
-                                        *counters += 1;
+                                        count_response(&mut result, serde_json::to_string(&result_data)?);
                                         result_counter += 1;
                                         // Without quorum it's enough if we got one response
-                                        if !self.quorum
-                                            || result_counter >= self.min_quorum_size
-                                            || !need_quorum
-                                            // with query we ignore quorum because the nodes can store a different amount of history
-                                            || query.is_some()
-                                        {
+                                        if !self.quorum || result_counter >= self.min_quorum_size || !need_quorum {
                                             break;
                                         }
                                     }
+                                    // A node with an incompatible version can return a response that can't be
+                                    // parsed, another node might not
+                                    Err(e @ Error::ResponseParse { .. }) if !self.retry_on_parse_error => {
+                                        return Err(e);
+                                    }
                                     Err(e) => {
                                         error.replace(e);
                                     }
@@ -277,22 +305,24 @@ impl NodeManager {
         }
 
         let res = result
-            .into_iter()
-            .max_by_key(|v| v.1)
+            .values()
+            .max_by_key(|(_, count)| *count)
+            .cloned()
             .ok_or_else(|| error.unwrap_or_else(|| Error::Node("couldn't get a result from any node".into())))?;
 
         // Return if quorum is false or check if quorum was reached
         if !self.quorum
             || res.1 as f64 >= self.min_quorum_size as f64 * (self.quorum_threshold as f64 / 100.0)
             || !need_quorum
-            // with query we ignore quorum because the nodes can store a different amount of history
-            || query.is_some()
         {
             Ok(serde_json::from_str(&res.0)?)
         } else {
+            let mut responses = result.into_values().collect::<Vec<_>>();
+            responses.sort_by_key(|(_, count)| Reverse(*count));
             Err(Error::QuorumThresholdError {
                 quorum_size: res.1,
                 minimum_threshold: self.min_quorum_size,
+                responses,
             })
         }
     }
@@ -304,6 +334,11 @@ impl NodeManager {
         query: Option<&str>,
         timeout: Duration,
     ) -> Result<Vec<u8>> {
+        self.with_read_retry(|| self.try_get_request_bytes(path, query, timeout))
+            .await
+    }
+
+    async fn try_get_request_bytes(&self, path: &str, query: Option<&str>, timeout: Duration) -> Result<Vec<u8>> {
         // primary_pow_node should only be used for post request with remote Pow
         // Get node urls and set path
         let nodes = self.get_nodes(path, query, false, false)?;
@@ -350,6 +385,12 @@ impl NodeManager {
         let mut error = None;
         // Send requests
         for node in nodes {
+            if let Some(max_milestone_lag) = self.post_max_milestone_lag {
+                if let Err(e) = self.check_synced_for_post(&node, timeout, max_milestone_lag).await {
+                    error.replace(e);
+                    continue;
+                }
+            }
             match self.http_client.post_bytes(node, timeout, body).await {
                 Ok(res) => {
                     match res.status() {
@@ -387,6 +428,12 @@ impl NodeManager {
         let mut error = None;
         // Send requests
         for node in nodes {
+            if let Some(max_milestone_lag) = self.post_max_milestone_lag {
+                if let Err(e) = self.check_synced_for_post(&node, timeout, max_milestone_lag).await {
+                    error.replace(e);
+                    continue;
+                }
+            }
             match self.http_client.post_json(node, timeout, json.clone()).await {
                 Ok(res) => {
                     match res.status() {
@@ -401,6 +448,10 @@ impl NodeManager {
                         )),
                     };
                 }
+                // Keep the status code, so callers can fall back to posting bytes if the node doesn't accept JSON
+                Err(e @ Error::ResponseError { code: 415, .. }) => {
+                    error.replace(e);
+                }
                 Err(e) => {
                     error.replace(crate::Error::Node(e.to_string()));
                 }
@@ -408,4 +459,112 @@ impl NodeManager {
         }
         Err(error.unwrap_or_else(|| Error::Node("couldn't get a result from any node".into())))
     }
+
+    // Requests that change the node, like managing its peers, are only sent to the first node, which is the primary
+    // node if set, so they're never applied to a different node than intended.
+    pub(crate) async fn post_request_json_to_first_node<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        timeout: Duration,
+        json: Value,
+    ) -> Result<T> {
+        let node = self.get_first_node(path)?;
+        self.http_client.post_json(node, timeout, json).await?.into_json().await
+    }
+
+    pub(crate) async fn delete_request_to_first_node(&self, path: &str, timeout: Duration) -> Result<()> {
+        let node = self.get_first_node(path)?;
+        self.http_client.delete(node, timeout).await?;
+        Ok(())
+    }
+
+    fn get_first_node(&self, path: &str) -> Result<Node> {
+        self.get_nodes(path, None, false, false)?
+            .into_iter()
+            .next()
+            .ok_or(Error::HealthyNodePoolEmpty)
+    }
+
+    // Calls the request again while it fails with a transient error, up to the attempts of the read retry config.
+    async fn with_read_retry<T, F, Fut>(&self, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match (request().await, self.read_retry) {
+                (Err(e), Some(read_retry)) if attempt < read_retry.max_attempts && e.is_transient() => {
+                    log::debug!("Read request failed in attempt {attempt}, retrying: {e}");
+                    attempt += 1;
+
+                    #[cfg(target_family = "wasm")]
+                    gloo_timers::future::TimeoutFuture::new(read_retry.backoff.as_millis().try_into().unwrap()).await;
+
+                    #[cfg(not(target_family = "wasm"))]
+                    tokio::time::sleep(read_retry.backoff).await;
+                }
+                (result, _) => return result,
+            }
+        }
+    }
+
+    // Checks that a node is healthy and its confirmed milestone doesn't lag behind its latest milestone by more than
+    // `max_milestone_lag` before a block is posted to it.
+    async fn check_synced_for_post(&self, node: &Node, timeout: Duration, max_milestone_lag: u32) -> Result<()> {
+        // Only the status of the node info is needed
+        #[derive(Deserialize)]
+        struct NodeStatus {
+            status: StatusResponse,
+        }
+
+        let mut info_node = node.clone();
+        info_node.url.set_path("api/core/v2/info");
+        let NodeStatus { status } = self.http_client.get(info_node, timeout).await?.into_json().await?;
+
+        if is_synced(&status, max_milestone_lag) {
+            Ok(())
+        } else {
+            Err(Error::Node(format!(
+                "{} isn't healthy or its confirmed milestone lags behind, not posting to it",
+                node.url.origin().ascii_serialization()
+            )))
+        }
+    }
+}
+
+// Returns whether a node is healthy and its confirmed milestone is at most `max_milestone_lag` milestones behind its
+// latest one.
+fn is_synced(status: &StatusResponse, max_milestone_lag: u32) -> bool {
+    let milestone_lag = status
+        .latest_milestone
+        .index
+        .saturating_sub(status.confirmed_milestone.index);
+
+    status.is_healthy && milestone_lag <= max_milestone_lag
+}
+
+// Counts a node response for quorum. Responses are compared without their `ledgerIndex` fields, so nodes that are at
+// different milestones still agree when the content is the same.
+fn count_response(result: &mut HashMap<String, (String, usize)>, response: String) {
+    let key = serde_json::from_str::<Value>(&response).map_or_else(
+        |_| response.clone(),
+        |mut value| {
+            remove_ledger_index(&mut value);
+            value.to_string()
+        },
+    );
+    result.entry(key).or_insert_with(|| (response, 0)).1 += 1;
+}
+
+fn remove_ledger_index(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("ledgerIndex");
+            map.values_mut().for_each(remove_ledger_index);
+        }
+        Value::Array(values) => values.iter_mut().for_each(remove_ledger_index),
+        _ => {}
+    }
 }
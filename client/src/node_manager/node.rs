@@ -1,7 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::hash::Hash;
+use std::{collections::HashSet, hash::Hash};
 
 use serde::{Deserialize, Serialize};
 pub use url::Url;
@@ -65,3 +65,100 @@ impl From<&NodeDto> for Node {
         }
     }
 }
+
+/// The status of the node syncing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSyncStatus {
+    /// If the nodes are synced in the background.
+    pub enabled: bool,
+    /// The nodes that were healthy and synced at the last sync, requests are sent to them.
+    pub synced_nodes: HashSet<Node>,
+}
+
+/// Options to discover nodes from the peers of a seed node.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeDiscovery {
+    /// The node whose peers are discovered.
+    pub seed_node: Node,
+    /// Maximum amount of discovered nodes that are added to the node pool.
+    pub max_nodes: usize,
+    /// Only add discovered nodes that report to be healthy.
+    pub require_healthy: bool,
+    /// The port of the REST API of the peers, which isn't part of their gossip addresses.
+    #[serde(default = "default_api_port")]
+    pub api_port: u16,
+}
+
+fn default_api_port() -> u16 {
+    crate::constants::DEFAULT_NODE_API_PORT
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl NodeDiscovery {
+    /// Converts a gossip multiaddress of a peer, like `/ip4/127.0.0.1/tcp/15600`, to the URL of its REST API, using
+    /// the scheme of the seed node.
+    pub(crate) fn api_url(&self, multiaddress: &str) -> Option<Url> {
+        let mut parts = multiaddress.split('/').skip(1);
+        let host = match (parts.next()?, parts.next()?) {
+            ("ip4" | "dns" | "dns4" | "dns6", host) => host.to_string(),
+            ("ip6", host) => format!("[{host}]"),
+            _ => return None,
+        };
+
+        Url::parse(&format!("{}://{host}:{}", self.seed_node.url.scheme(), self.api_port)).ok()
+    }
+
+    // Selects the nodes that get added to the pool from the reachable candidates and whether they're healthy.
+    pub(crate) fn select<T>(&self, candidates: impl IntoIterator<Item = (T, bool)>) -> Vec<T> {
+        candidates
+            .into_iter()
+            .filter(|(_, healthy)| *healthy || !self.require_healthy)
+            .map(|(candidate, _)| candidate)
+            .take(self.max_nodes)
+            .collect()
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+
+    fn discovery(require_healthy: bool) -> NodeDiscovery {
+        NodeDiscovery {
+            seed_node: Url::parse("https://seed.example.com").unwrap().into(),
+            max_nodes: 2,
+            require_healthy,
+            api_port: 14265,
+        }
+    }
+
+    #[test]
+    fn api_url_from_multiaddress() {
+        let discovery = discovery(true);
+
+        assert_eq!(
+            discovery.api_url("/ip4/10.0.0.1/tcp/15600/p2p/12D3KooWRVt4Engu27jHnF2RjfX48EqiAqJbgLfFdHNt3Vn6BtJK"),
+            Some(Url::parse("https://10.0.0.1:14265").unwrap())
+        );
+        assert_eq!(
+            discovery.api_url("/ip6/::1/tcp/15600"),
+            Some(Url::parse("https://[::1]:14265").unwrap())
+        );
+        assert_eq!(
+            discovery.api_url("/dns/node.example.com/tcp/15600"),
+            Some(Url::parse("https://node.example.com:14265").unwrap())
+        );
+        assert_eq!(discovery.api_url("/unix/tmp/node.sock"), None);
+    }
+
+    #[test]
+    fn select_healthy_discovered_nodes() {
+        let candidates = [("a", false), ("b", true), ("c", true), ("d", true)];
+
+        // Unhealthy nodes are skipped and the pool is limited to `max_nodes`
+        assert_eq!(discovery(true).select(candidates), vec!["b", "c"]);
+        assert_eq!(discovery(false).select(candidates), vec!["a", "b"]);
+    }
+}
@@ -3,12 +3,19 @@
 
 #[cfg(not(target_family = "wasm"))]
 use {
-    crate::NetworkInfo,
-    iota_types::{api::core::response::InfoResponse, block::protocol::ProtocolParameters},
+    super::{
+        http_client::HttpClient,
+        node::{NodeDiscovery, NodeSyncStatus},
+    },
+    crate::{constants::DEFAULT_API_TIMEOUT, NetworkInfo},
+    iota_types::{
+        api::core::response::{InfoResponse, PeersResponse},
+        block::protocol::ProtocolParameters,
+    },
     std::collections::HashMap,
     std::{
         collections::HashSet,
-        sync::{Arc, RwLock},
+        sync::{Arc, PoisonError, RwLock},
         time::Duration,
     },
     tokio::{runtime::Runtime, time::sleep},
@@ -44,6 +51,18 @@ impl Client {
             })
     }
 
+    /// Returns if the node syncing is enabled and the nodes that are synced.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn node_sync_status(&self) -> NodeSyncStatus {
+        NodeSyncStatus {
+            enabled: self.sync_handle.is_some(),
+            synced_nodes: self.node_manager.healthy_nodes.read().map_or_else(
+                |_| HashSet::new(),
+                |healthy_nodes| healthy_nodes.keys().cloned().collect(),
+            ),
+        }
+    }
+
     /// Sync the node lists per node_sync_interval milliseconds
     #[cfg(not(target_family = "wasm"))]
     pub(crate) fn start_sync_process(
@@ -52,14 +71,14 @@ impl Client {
         nodes: HashSet<Node>,
         node_sync_interval: Duration,
         network_info: Arc<RwLock<NetworkInfo>>,
-        ignore_node_health: bool,
+        options: SyncOptions,
     ) -> tokio::task::JoinHandle<()> {
         runtime.spawn(async move {
             loop {
                 // Delay first since the first `sync_nodes` call is made by the builder to ensure the node list is
                 // filled before the client is used.
                 sleep(node_sync_interval).await;
-                if let Err(e) = Self::sync_nodes(&sync, &nodes, &network_info, ignore_node_health).await {
+                if let Err(e) = Self::sync_nodes(&sync, &nodes, &network_info, &options).await {
                     log::warn!("Syncing nodes failed: {e}");
                 }
             }
@@ -71,7 +90,7 @@ impl Client {
         sync: &Arc<RwLock<HashMap<Node, InfoResponse>>>,
         nodes: &HashSet<Node>,
         network_info: &Arc<RwLock<NetworkInfo>>,
-        ignore_node_health: bool,
+        options: &SyncOptions,
     ) -> Result<()> {
         log::debug!("sync_nodes");
         let mut healthy_nodes = HashMap::new();
@@ -79,8 +98,10 @@ impl Client {
 
         for node in nodes {
             // Put the healthy node url into the network_nodes
-            if let Ok(info) = Self::get_node_info(node.url.as_ref(), node.auth.clone()).await {
-                if info.status.is_healthy || ignore_node_health {
+            if let Ok(info) =
+                Self::get_node_info_with_client(&options.http_client, node.url.as_ref(), node.auth.clone()).await
+            {
+                if info.status.is_healthy || options.ignore_node_health {
                     match network_nodes.get_mut(&info.protocol.network_name) {
                         Some(network_node_entry) => {
                             network_node_entry.push((info, node.clone()));
@@ -97,6 +118,21 @@ impl Client {
             }
         }
 
+        // Discovering again on every sync keeps the pool up to date with the peers of the seed node
+        if let Some(node_discovery) = &options.node_discovery {
+            match Self::discover_nodes(&options.http_client, node_discovery, nodes).await {
+                Ok(discovered_nodes) => {
+                    for (info, node) in discovered_nodes {
+                        network_nodes
+                            .entry(info.protocol.network_name.clone())
+                            .or_default()
+                            .push((info, node));
+                    }
+                }
+                Err(e) => log::warn!("Discovering nodes from {} failed: {e}", node_discovery.seed_node.url),
+            }
+        }
+
         // Get network_id with the most nodes
         let mut most_nodes = ("network_id", 0);
         for (network_id, node) in &network_nodes {
@@ -108,7 +144,11 @@ impl Client {
 
         if let Some(nodes) = network_nodes.get(most_nodes.0) {
             if let Some((info, _node_url)) = nodes.first() {
-                let mut network_info = network_info.write().map_err(|_| crate::Error::PoisonError)?;
+                if let Some(bech32_hrp_check) = &options.bech32_hrp_check {
+                    bech32_hrp_check.check(&info.protocol.bech32_hrp)?;
+                }
+
+                let mut network_info = network_info.write().unwrap_or_else(PoisonError::into_inner);
 
                 network_info.latest_milestone_timestamp = info.status.latest_milestone.timestamp;
                 network_info.protocol_parameters = ProtocolParameters::try_from(info.protocol.clone())?;
@@ -124,4 +164,155 @@ impl Client {
 
         Ok(())
     }
+
+    /// Updates the network info once from the first node that returns its info, used when the node syncing is disabled
+    /// so the protocol parameters are still the ones of the network. Keeps the configured network info if no node
+    /// responds.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) async fn fetch_network_info(
+        nodes: &HashSet<Node>,
+        network_info: &Arc<RwLock<NetworkInfo>>,
+        options: &SyncOptions,
+    ) -> Result<()> {
+        for node in nodes {
+            match Self::get_node_info_with_client(&options.http_client, node.url.as_ref(), node.auth.clone()).await {
+                Ok(info) => {
+                    if let Some(bech32_hrp_check) = &options.bech32_hrp_check {
+                        bech32_hrp_check.check(&info.protocol.bech32_hrp)?;
+                    }
+
+                    let mut network_info = network_info.write().unwrap_or_else(PoisonError::into_inner);
+                    network_info.latest_milestone_timestamp = info.status.latest_milestone.timestamp;
+                    network_info.protocol_parameters = ProtocolParameters::try_from(info.protocol)?;
+
+                    return Ok(());
+                }
+                Err(e) => log::debug!("Couldn't get the node info from {}: {e}", node.url),
+            }
+        }
+
+        if !nodes.is_empty() {
+            log::warn!("Couldn't get the node info from any node, the configured network info is used");
+        }
+
+        Ok(())
+    }
+
+    // Requests the peers of the seed node and returns the reachable ones that aren't configured already, together with
+    // their node info.
+    #[cfg(not(target_family = "wasm"))]
+    async fn discover_nodes(
+        http_client: &HttpClient,
+        node_discovery: &NodeDiscovery,
+        configured_nodes: &HashSet<Node>,
+    ) -> Result<Vec<(InfoResponse, Node)>> {
+        let mut url = node_discovery.seed_node.url.clone();
+        url.set_path("api/core/v2/peers");
+
+        let peers: PeersResponse = http_client
+            .get(
+                Node {
+                    url,
+                    ..node_discovery.seed_node.clone()
+                },
+                DEFAULT_API_TIMEOUT,
+            )
+            .await?
+            .into_json()
+            .await?;
+
+        let mut candidate_urls = HashSet::new();
+        for peer in peers.0.iter().filter(|peer| peer.connected) {
+            if let Some(url) = peer
+                .multi_addresses
+                .iter()
+                .find_map(|multiaddress| node_discovery.api_url(multiaddress))
+            {
+                if !configured_nodes.iter().any(|node| node.url == url) {
+                    candidate_urls.insert(url);
+                }
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for url in candidate_urls {
+            match Self::get_node_info_with_client(http_client, url.as_str(), None).await {
+                Ok(info) => {
+                    let healthy = info.status.is_healthy;
+                    candidates.push(((info, Node::from(url)), healthy));
+                }
+                Err(e) => log::debug!("Discovered node {url} isn't reachable: {e}"),
+            }
+        }
+
+        Ok(node_discovery.select(candidates))
+    }
+}
+
+/// Options for syncing the nodes.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Clone, Debug)]
+pub(crate) struct SyncOptions {
+    /// Also add nodes that aren't healthy to the healthy node pool.
+    pub(crate) ignore_node_health: bool,
+    /// Checks the bech32 HRP reported by the nodes.
+    pub(crate) bech32_hrp_check: Option<Bech32HrpCheck>,
+    /// Discovers further nodes from the peers of a seed node.
+    pub(crate) node_discovery: Option<NodeDiscovery>,
+    /// The HTTP client with the configured user agent and headers.
+    pub(crate) http_client: HttpClient,
+}
+
+/// Compares the bech32 HRP reported by the synced nodes against a configured one.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Clone, Debug)]
+pub(crate) struct Bech32HrpCheck {
+    /// The configured bech32 HRP.
+    pub(crate) hrp: String,
+    /// Fail the sync instead of only logging a warning on a mismatch.
+    pub(crate) strict: bool,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Bech32HrpCheck {
+    fn check(&self, node_bech32_hrp: &str) -> Result<()> {
+        if self.hrp != node_bech32_hrp {
+            if self.strict {
+                return Err(Error::HrpMismatch {
+                    expected: self.hrp.clone(),
+                    found: node_bech32_hrp.to_string(),
+                });
+            }
+            log::warn!(
+                "The node reports the bech32 HRP `{node_bech32_hrp}`, but `{}` is configured",
+                self.hrp
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bech32_hrp_mismatch() {
+        let strict = Bech32HrpCheck {
+            hrp: "smr".to_string(),
+            strict: true,
+        };
+        assert!(strict.check("smr").is_ok());
+        assert!(matches!(
+            strict.check("rms"),
+            Err(Error::HrpMismatch { expected, found }) if expected == "smr" && found == "rms"
+        ));
+
+        let lenient = Bech32HrpCheck {
+            strict: false,
+            ..strict
+        };
+        assert!(lenient.check("rms").is_ok());
+    }
 }
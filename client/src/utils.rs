@@ -12,8 +12,9 @@ use crypto::{
 };
 use iota_types::block::{
     address::{Address, AliasAddress, Ed25519Address, NftAddress},
-    output::{AliasId, NftId},
-    payload::TaggedDataPayload,
+    input::UtxoInput,
+    output::{AliasId, NftId, OutputId},
+    payload::{transaction::TransactionEssence, TaggedDataPayload, TransactionPayload},
 };
 use zeroize::Zeroize;
 
@@ -63,14 +64,57 @@ pub fn parse_bech32_address(address: &str) -> Result<Address> {
     Ok(Address::try_from_bech32(address)?.1)
 }
 
+/// Parses a bech32 encoded address or a `0x` prefixed hex encoded Ed25519 address, like returned by
+/// [`bech32_to_hex()`] for Ed25519 addresses.
+pub fn parse_address(address: &str) -> Result<Address> {
+    if address.starts_with("0x") {
+        Ok(Address::Ed25519(address.parse::<Ed25519Address>()?))
+    } else {
+        parse_bech32_address(address)
+    }
+}
+
+/// Parses a hex encoded output ID, the transaction ID followed by the little endian output index, into a
+/// [`UtxoInput`].
+pub fn parse_output_id(output_id: &str) -> Result<UtxoInput> {
+    Ok(UtxoInput::from(output_id.parse::<OutputId>()?))
+}
+
 /// Checks if a String is a valid bech32 encoded address.
 pub fn is_address_valid(address: &str) -> bool {
     Address::try_from_bech32(address).is_ok()
 }
 
+/// Validates a bech32 encoded address without a request to a node.
+///
+/// If `expected_hrp` is set, an address of another network is rejected with [`Error::HrpMismatch`], otherwise
+/// the parsing error is returned.
+pub fn validate_address(address: &str, expected_hrp: Option<&str>) -> Result<()> {
+    let (hrp, _) = Address::try_from_bech32(address)?;
+
+    match expected_hrp {
+        Some(expected_hrp) if expected_hrp != hrp => Err(Error::HrpMismatch {
+            expected: expected_hrp.to_string(),
+            found: hrp,
+        }),
+        _ => Ok(()),
+    }
+}
+
 /// Generates a new mnemonic.
 pub fn generate_mnemonic() -> Result<String> {
-    let mut entropy = [0u8; 32];
+    generate_mnemonic_with_word_count(24)
+}
+
+/// Generates a new mnemonic with 12, 15, 18, 21 or 24 words.
+pub fn generate_mnemonic_with_word_count(word_count: usize) -> Result<String> {
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        return Err(crate::Error::InvalidMnemonic(format!(
+            "unsupported word count {word_count}, expected 12, 15, 18, 21 or 24"
+        )));
+    }
+    // Every 3 words encode 32 bits of entropy and a 1 bit checksum
+    let mut entropy = vec![0u8; word_count / 3 * 4];
     utils::rand::fill(&mut entropy)?;
     let mnemonic = wordlist::encode(&entropy, &crypto::keys::bip39::wordlist::ENGLISH)
         .map_err(|e| crate::Error::InvalidMnemonic(format!("{e:?}")))?;
@@ -80,14 +124,21 @@ pub fn generate_mnemonic() -> Result<String> {
 
 /// Returns a hex encoded seed for a mnemonic.
 pub fn mnemonic_to_hex_seed(mnemonic: &str) -> Result<String> {
+    mnemonic_to_hex_seed_with_passphrase(mnemonic, "")
+}
+
+/// Returns a hex encoded seed for a mnemonic protected by a BIP39 passphrase.
+pub fn mnemonic_to_hex_seed_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<String> {
     // trim because empty spaces could create a different seed https://github.com/iotaledger/crypto.rs/issues/125
     let mnemonic = mnemonic.trim();
     // first we check if the mnemonic is valid to give meaningful errors
     crypto::keys::bip39::wordlist::verify(mnemonic, &crypto::keys::bip39::wordlist::ENGLISH)
         .map_err(|e| crate::Error::InvalidMnemonic(format!("{e:?}")))?;
     let mut mnemonic_seed = [0u8; 64];
-    crypto::keys::bip39::mnemonic_to_seed(mnemonic, "", &mut mnemonic_seed);
-    Ok(prefix_hex::encode(mnemonic_seed))
+    crypto::keys::bip39::mnemonic_to_seed(mnemonic, passphrase, &mut mnemonic_seed);
+    let hex_seed = prefix_hex::encode(mnemonic_seed);
+    mnemonic_seed.zeroize();
+    Ok(hex_seed)
 }
 
 /// Returns a seed for a mnemonic.
@@ -102,6 +153,16 @@ pub fn mnemonic_to_seed(mnemonic: &str) -> Result<Seed> {
     Ok(Seed::from_bytes(&mnemonic_seed))
 }
 
+/// Computes the IDs of the outputs a transaction creates, in the same order as the outputs of its essence
+pub fn compute_output_ids(transaction_payload: &TransactionPayload) -> Result<Vec<OutputId>> {
+    let transaction_id = transaction_payload.id();
+    let TransactionEssence::Regular(essence) = transaction_payload.essence();
+
+    (0..essence.outputs().len())
+        .map(|index| Ok(OutputId::new(transaction_id, index as u16)?))
+        .collect()
+}
+
 /// Requests funds from a faucet
 pub async fn request_funds_from_faucet(url: &str, bech32_address: &str) -> Result<String> {
     let mut map = HashMap::new();
@@ -155,17 +216,37 @@ impl Client {
         parse_bech32_address(address)
     }
 
+    /// Parses a bech32 encoded address or a `0x` prefixed hex encoded Ed25519 address.
+    pub fn parse_address(address: &str) -> crate::Result<Address> {
+        parse_address(address)
+    }
+
+    /// Parses a hex encoded output ID into a [`UtxoInput`].
+    pub fn parse_output_id(output_id: &str) -> crate::Result<UtxoInput> {
+        parse_output_id(output_id)
+    }
+
     /// Checks if a String is a valid bech32 encoded address.
     #[must_use]
     pub fn is_address_valid(address: &str) -> bool {
         is_address_valid(address)
     }
 
+    /// Validates a bech32 encoded address, optionally for the network with the expected bech32 HRP.
+    pub fn validate_address(address: &str, expected_hrp: Option<&str>) -> crate::Result<()> {
+        validate_address(address, expected_hrp)
+    }
+
     /// Generates a new mnemonic.
     pub fn generate_mnemonic() -> Result<String> {
         generate_mnemonic()
     }
 
+    /// Generates a new mnemonic with 12, 15, 18, 21 or 24 words.
+    pub fn generate_mnemonic_with_word_count(word_count: usize) -> Result<String> {
+        generate_mnemonic_with_word_count(word_count)
+    }
+
     /// Returns a seed for a mnemonic.
     pub fn mnemonic_to_seed(mnemonic: &str) -> Result<Seed> {
         mnemonic_to_seed(mnemonic)
@@ -176,6 +257,16 @@ impl Client {
         mnemonic_to_hex_seed(mnemonic)
     }
 
+    /// Returns a hex encoded seed for a mnemonic protected by a BIP39 passphrase.
+    pub fn mnemonic_to_hex_seed_with_passphrase(mnemonic: &str, passphrase: &str) -> Result<String> {
+        mnemonic_to_hex_seed_with_passphrase(mnemonic, passphrase)
+    }
+
+    /// Computes the IDs of the outputs a transaction creates.
+    pub fn compute_output_ids(transaction_payload: &TransactionPayload) -> Result<Vec<OutputId>> {
+        compute_output_ids(transaction_payload)
+    }
+
     /// UTF-8 encodes the `tag` of a given TaggedDataPayload.
     pub fn tag_to_utf8(payload: &TaggedDataPayload) -> Result<String> {
         String::from_utf8(payload.tag().to_vec()).map_err(|_| Error::TaggedData("found invalid UTF-8".to_string()))
@@ -1,6 +1,9 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod common;
+
+use common::setup_client_with_node_health_ignored;
 #[cfg(feature = "message_interface")]
 use iota_client::api::GetAddressesBuilderOptions;
 #[cfg(feature = "message_interface")]
@@ -48,6 +51,28 @@ async fn addresses() {
     );
 }
 
+#[tokio::test]
+async fn address_range() {
+    let secret_manager = SecretManager::Mnemonic(
+        MnemonicSecretManager::try_from_hex_seed("0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2")
+            .unwrap(),
+    );
+    let generate = |range| {
+        GetAddressesBuilder::new(&secret_manager)
+            .with_coin_type(IOTA_COIN_TYPE)
+            .with_bech32_hrp(IOTA_TESTNET_BECH32_HRP)
+            .with_range(range)
+            .finish()
+    };
+
+    assert!(generate(3..3).await.unwrap().is_empty());
+
+    let addresses = generate(0..10).await.unwrap();
+    let offset_addresses = generate(5..10).await.unwrap();
+    assert_eq!(offset_addresses.len(), 5);
+    assert_eq!(offset_addresses, addresses[5..]);
+}
+
 #[tokio::test]
 async fn public_key_to_address() {
     let client = Client::builder().finish().unwrap();
@@ -303,3 +328,96 @@ async fn address_generation() {
         }
     }
 }
+
+#[tokio::test]
+async fn address_scan_batch_size() {
+    let client = Client::builder().with_address_scan_batch_size(3).finish().unwrap();
+    let secret_manager = SecretManager::try_from_mnemonic(&Client::generate_mnemonic().unwrap()).unwrap();
+
+    // The batch size is the default range of generated addresses
+    let addresses = client
+        .get_addresses(&secret_manager)
+        .with_bech32_hrp(SHIMMER_BECH32_HRP)
+        .finish()
+        .await
+        .unwrap();
+
+    assert_eq!(addresses.len(), 3);
+}
+
+#[ignore]
+#[tokio::test]
+async fn next_unused_address() {
+    let client = setup_client_with_node_health_ignored();
+    let secret_manager = SecretManager::try_from_mnemonic(&Client::generate_mnemonic().unwrap()).unwrap();
+
+    let mut unused_addresses = Vec::new();
+    for _ in 0..3 {
+        unused_addresses.push(
+            client
+                .get_next_unused_address(&secret_manager, SHIMMER_COIN_TYPE, 0)
+                .await
+                .unwrap(),
+        );
+    }
+
+    // A new mnemonic has no outputs, so every call has to return the address with the next higher index
+    let addresses = client
+        .get_addresses(&secret_manager)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_range(0..3)
+        .finish()
+        .await
+        .unwrap();
+
+    assert_eq!(unused_addresses, addresses);
+}
+
+#[test]
+fn parse_address_and_output_id() {
+    let bech32 = "atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r";
+    let hex = iota_client::bech32_to_hex(bech32).unwrap();
+
+    let address = Client::parse_address(bech32).unwrap();
+    assert_eq!(address.to_bech32("atoi"), bech32);
+    assert_eq!(Client::parse_address(&hex).unwrap(), address);
+    assert!(Client::parse_address("0x1234").is_err());
+    assert!(Client::parse_address("atoi1invalid").is_err());
+
+    let output_id = "0xb51d7d7ee2ff1d7bc7a51fba3e3ba1fd7fa56a2ca8f2a45d0f4e2a9e9a4c6a8d0100";
+    let input = Client::parse_output_id(output_id).unwrap();
+    assert_eq!(input.output_id().index(), 1);
+    assert_eq!(input.output_id().to_string(), output_id);
+    assert!(Client::parse_output_id("0xb51d").is_err());
+}
+
+#[tokio::test]
+async fn bech32_hex_conversion() {
+    let client = Client::builder().finish().unwrap();
+    let bech32 = "atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r";
+
+    let hex = Client::bech32_to_hex(bech32).unwrap();
+    assert_eq!(client.hex_to_bech32(&hex, Some("atoi")).await.unwrap(), bech32);
+
+    // Invalid hex length and a wrong checksum are errors.
+    assert!(client.hex_to_bech32("0x1234", Some("atoi")).await.is_err());
+    assert!(Client::bech32_to_hex("atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0s").is_err());
+}
+
+#[test]
+fn validate_address() {
+    let testnet_address = "atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r";
+    let mainnet_address = Client::parse_bech32_address(testnet_address)
+        .unwrap()
+        .to_bech32(IOTA_BECH32_HRP);
+
+    assert!(Client::validate_address(testnet_address, None).is_ok());
+    assert!(Client::validate_address(testnet_address, Some(IOTA_TESTNET_BECH32_HRP)).is_ok());
+    assert!(Client::validate_address(&mainnet_address, None).is_ok());
+    assert!(matches!(
+        Client::validate_address(&mainnet_address, Some(IOTA_TESTNET_BECH32_HRP)),
+        Err(iota_client::Error::HrpMismatch { expected, found })
+            if expected == IOTA_TESTNET_BECH32_HRP && found == IOTA_BECH32_HRP
+    ));
+    assert!(Client::validate_address("atoi1invalid", None).is_err());
+}
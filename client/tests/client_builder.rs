@@ -132,3 +132,67 @@ async fn client_builder() {
 
     let _client_builder = serde_json::from_str::<ClientBuilder>(client_builder_json).unwrap();
 }
+
+#[tokio::test]
+async fn node_discovery() {
+    let client_builder = Client::builder()
+        .with_node_discovery("http://localhost:14265", 5, true)
+        .unwrap();
+    let client_builder_json = serde_json::to_value(&client_builder).unwrap();
+
+    assert_eq!(
+        client_builder_json["nodeDiscovery"],
+        serde_json::json!({
+            "seedNode": {
+                "url": "http://localhost:14265/",
+                "auth": null,
+                "disabled": false
+            },
+            "maxNodes": 5,
+            "requireHealthy": true,
+            "apiPort": 14265
+        })
+    );
+    // The seed node is used as node too
+    assert_eq!(client_builder_json["nodes"][0]["url"], "http://localhost:14265/");
+
+    let client_builder = serde_json::from_value::<ClientBuilder>(client_builder_json).unwrap();
+    assert!(client_builder.node_manager_builder.node_discovery.is_some());
+}
+
+#[tokio::test]
+async fn headers() {
+    let client_builder =
+        Client::builder().with_headers([("X-Tenant".to_string(), "tenant-1".to_string())].into_iter().collect());
+    let client_builder_json = serde_json::to_value(&client_builder).unwrap();
+    assert_eq!(
+        client_builder_json["headers"],
+        serde_json::json!({ "X-Tenant": "tenant-1" })
+    );
+
+    let client_builder = serde_json::from_value::<ClientBuilder>(client_builder_json).unwrap();
+    assert_eq!(
+        client_builder
+            .node_manager_builder
+            .headers
+            .get("X-Tenant")
+            .map(String::as_str),
+        Some("tenant-1")
+    );
+}
+
+#[tokio::test]
+async fn output_fetch_concurrency() {
+    let client_builder = Client::builder().with_output_fetch_concurrency(10);
+    let client_builder_json = serde_json::to_value(&client_builder).unwrap();
+    assert_eq!(client_builder_json["outputFetchConcurrency"], 10);
+
+    // Defaults to 100 if not set
+    let mut client_builder_json = serde_json::to_value(Client::builder()).unwrap();
+    client_builder_json
+        .as_object_mut()
+        .unwrap()
+        .remove("outputFetchConcurrency");
+    let client_builder = serde_json::from_value::<ClientBuilder>(client_builder_json).unwrap();
+    assert_eq!(client_builder.output_fetch_concurrency, 100);
+}
@@ -0,0 +1,200 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A local HTTP server answering requests with canned responses, so node API behaviour can be tested without a
+//! running node, and builders for the JSON bodies it serves.
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use iota_client::{Client, ClientBuilder};
+use iota_types::block::{output::OutputId, payload::transaction::TransactionId, BlockId};
+
+/// Serves requests with the status and body returned by `respond` for the request path and returns the url of the
+/// server.
+pub fn serve<B: AsRef<str>>(respond: impl Fn(&str) -> (u16, B) + Send + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = [0; 4096];
+            let read = stream.read(&mut request).unwrap_or(0);
+            let request = String::from_utf8_lossy(&request[..read]);
+            let (status, body) = respond(request.split_whitespace().nth(1).unwrap_or_default());
+            let body = body.as_ref();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status} Status\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+
+    url
+}
+
+/// Serves requests with the body of the first route whose path the request starts with, `{}` for other paths, and
+/// returns the url of the server.
+pub fn serve_json<B: Into<String>>(routes: impl IntoIterator<Item = (&'static str, B)>) -> String {
+    let routes = routes
+        .into_iter()
+        .map(|(route, body)| (route, body.into()))
+        .collect::<Vec<_>>();
+
+    serve(move |path| {
+        let body = routes
+            .iter()
+            .find(|(route, _)| path.starts_with(route))
+            .map_or("{}", |(_, body)| body.as_str());
+        (200, body.to_string())
+    })
+}
+
+/// Serves the responses in order for requests to the path and `{}` for other paths, the last response is repeated.
+/// Returns the url and the number of requests to the path.
+pub fn serve_sequence<B: Into<String>>(
+    path: &'static str,
+    responses: impl IntoIterator<Item = (u16, B)>,
+) -> (String, Arc<AtomicUsize>) {
+    let responses = responses
+        .into_iter()
+        .map(|(status, body)| (status, body.into()))
+        .collect::<Vec<_>>();
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+
+    let url = serve(move |request_path| {
+        if request_path.starts_with(path) {
+            let index = counter.fetch_add(1, Ordering::SeqCst);
+            responses[index.min(responses.len() - 1)].clone()
+        } else {
+            (200, "{}".to_string())
+        }
+    });
+
+    (url, requests)
+}
+
+/// Returns a builder for a client using only the mock node, without syncing or health checks.
+pub fn mock_client_builder(url: &str) -> ClientBuilder {
+    Client::builder().with_node(url).unwrap().with_ignore_node_health()
+}
+
+/// Returns a client using only the mock node.
+pub fn mock_client(url: &str) -> Client {
+    mock_client_builder(url).finish().unwrap()
+}
+
+/// Returns the hex encoded id of a block whose bytes are all `byte`.
+pub fn block_id(byte: u8) -> String {
+    BlockId::new([byte; 32]).to_string()
+}
+
+/// Returns the hex encoded id of an output of a transaction whose id bytes are all `byte`.
+pub fn output_id(byte: u8, index: u16) -> String {
+    OutputId::new(TransactionId::new([byte; 32]), index)
+        .unwrap()
+        .to_string()
+}
+
+/// Returns the response of the node to a posted block.
+pub fn block_id_json(byte: u8) -> String {
+    format!(r#"{{"blockId":"{}"}}"#, block_id(byte))
+}
+
+/// Returns the metadata of the block whose id bytes are all `byte`, with the ledger inclusion state if it's
+/// referenced by a milestone.
+pub fn block_metadata_json(byte: u8, ledger_inclusion_state: Option<&str>) -> String {
+    let ledger_inclusion_state = ledger_inclusion_state
+        .map(|state| format!(r#","ledgerInclusionState":"{state}""#))
+        .unwrap_or_default();
+
+    format!(
+        r#"{{"blockId":"{}","parents":["{}"],"isSolid":true{ledger_inclusion_state}}}"#,
+        block_id(byte),
+        block_id(0x33)
+    )
+}
+
+/// Returns a tips response with a single tip.
+pub fn tips_json() -> String {
+    format!(r#"{{"tips":["{}"]}}"#, block_id(0xb0))
+}
+
+/// Returns the info response of a healthy node with the given milestone indexes and default protocol parameters.
+pub fn info_json(latest_milestone_index: u32, confirmed_milestone_index: u32, pruning_index: u32) -> String {
+    format!(
+        r#"{{"name":"HORNET","version":"2.0.0","status":{{"isHealthy":true,"latestMilestone":{{"index":{latest_milestone_index}}},"confirmedMilestone":{{"index":{confirmed_milestone_index}}},"pruningIndex":{pruning_index}}},"supportedProtocolVersions":[2],"protocol":{{"version":2,"networkName":"shimmer","bech32Hrp":"smr","minPowScore":1500,"belowMaxDepth":15,"rentStructure":{{"vByteCost":100,"vByteFactorKey":10,"vByteFactorData":1}},"tokenSupply":"1813620509061365"}},"pendingProtocolParameters":[],"baseToken":{{"name":"Shimmer","tickerSymbol":"SMR","unit":"SMR","subunit":"glow","decimals":6,"useMetricPrefix":false}},"metrics":{{"blocksPerSecond":1.0,"referencedBlocksPerSecond":1.0,"referencedRate":100.0}},"features":[]}}"#
+    )
+}
+
+/// Returns an indexer response with the output ids and, if there's another page, its cursor.
+pub fn output_ids_json(ledger_index: u32, output_ids: &[String], cursor: Option<&str>) -> String {
+    let items = output_ids
+        .iter()
+        .map(|output_id| format!(r#""{output_id}""#))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    match cursor {
+        Some(cursor) => format!(r#"{{"ledgerIndex":{ledger_index},"cursor":"{cursor}","items":[{items}]}}"#),
+        None => format!(r#"{{"ledgerIndex":{ledger_index},"items":[{items}]}}"#),
+    }
+}
+
+/// Returns the metadata of an output booked at `milestone_index_booked`.
+pub fn output_metadata_json(is_spent: bool, milestone_index_booked: u32, ledger_index: u32) -> String {
+    let zero = block_id(0);
+
+    format!(
+        r#"{{"blockId":"{zero}","transactionId":"{zero}","outputIndex":0,"isSpent":{is_spent},"milestoneIndexBooked":{milestone_index_booked},"milestoneTimestampBooked":1,"ledgerIndex":{ledger_index}}}"#
+    )
+}
+
+/// Returns the metadata of an output like [`output_metadata_json()`], spent by the transaction with all bytes set to
+/// `byte` at milestone 2.
+pub fn spent_output_metadata_json(byte: u8) -> String {
+    let zero = block_id(0);
+    let transaction_id_spent = block_id(byte);
+
+    format!(
+        r#"{{"blockId":"{zero}","transactionId":"{zero}","outputIndex":0,"isSpent":true,"milestoneIndexSpent":2,"milestoneTimestampSpent":2,"transactionIdSpent":"{transaction_id_spent}","milestoneIndexBooked":1,"milestoneTimestampBooked":1,"ledgerIndex":2}}"#
+    )
+}
+
+/// Returns an output response with a basic output holding `amount` for the zero Ed25519 address, booked at
+/// `milestone_index_booked`.
+pub fn basic_output_json(amount: u64, is_spent: bool, milestone_index_booked: u32, ledger_index: u32) -> String {
+    let metadata = output_metadata_json(is_spent, milestone_index_booked, ledger_index);
+    let zero = block_id(0);
+
+    format!(
+        r#"{{"metadata":{metadata},"output":{{"type":3,"amount":"{amount}","unlockConditions":[{{"type":0,"address":{{"type":0,"pubKeyHash":"{zero}"}}}}]}}}}"#
+    )
+}
+
+/// Returns a block response, with a tagged data payload if `tagged_data` is set.
+pub fn block_dto_json(tagged_data: bool) -> String {
+    let payload = if tagged_data {
+        r#","payload":{"type":5,"tag":"0x01","data":"0x02"}"#
+    } else {
+        ""
+    };
+
+    format!(
+        r#"{{"protocolVersion":2,"parents":["{}"]{payload},"nonce":"0"}}"#,
+        block_id(0x33)
+    )
+}
+
+/// Returns an error response of the node.
+pub fn error_json(code: u16, message: &str) -> String {
+    format!(r#"{{"error":{{"code":"{code}","message":"{message}"}}}}"#)
+}
@@ -4,6 +4,7 @@
 #![allow(clippy::redundant_pub_crate, dead_code)]
 
 mod constants;
+pub mod mock_node;
 
 use iota_client::{
     constants::SHIMMER_COIN_TYPE, node_api::indexer::query_parameters::QueryParameter, request_funds_from_faucet,
@@ -1,7 +1,11 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::{api::input_selection::Error as IsaError, block::Error as BlockError, Error};
+use iota_client::{
+    api::input_selection::Error as IsaError,
+    block::{BlockId, Error as BlockError},
+    Error,
+};
 
 #[test]
 fn stringified_error() {
@@ -17,7 +21,7 @@ fn stringified_error() {
     };
     assert_eq!(
         &serde_json::to_string(&error).unwrap(),
-        "{\"type\":\"timeNotSynced\",\"error\":\"local time 0 doesn't match the time of the latest milestone timestamp: 10000\"}"
+        "{\"type\":\"timeNotSynced\",\"error\":\"local time 0 doesn't match the time of the latest milestone timestamp: 10000\",\"code\":\"timeNotSynced\"}"
     );
 
     let error = Error::InvalidBIP32ChainData;
@@ -32,7 +36,7 @@ fn stringified_error() {
     });
     assert_eq!(
         &serde_json::to_string(&error).unwrap(),
-        "{\"type\":\"inputSelection\",\"error\":\"insufficient amount: found 0, required 100\"}"
+        "{\"type\":\"inputSelection\",\"error\":\"insufficient amount: found 0, required 100\",\"code\":\"insufficientFunds\"}"
     );
 
     let error = Error::InputSelection(IsaError::Block(BlockError::InvalidAddress));
@@ -41,3 +45,47 @@ fn stringified_error() {
         "{\"type\":\"inputSelection\",\"error\":\"invalid address provided\"}"
     );
 }
+
+#[test]
+fn error_code() {
+    let error = Error::ResponseError {
+        code: 503,
+        text: "service unavailable".into(),
+        url: "http://localhost:14265/api/core/v2/tips".into(),
+    };
+    assert_eq!(error.code(), Some("nodeUnavailable"));
+
+    // Mapped errors keep the code of the original error
+    let error = Error::Mapped {
+        error: Box::new(Error::HealthyNodePoolEmpty),
+        message: "all nodes are down, try again later".into(),
+    };
+    assert_eq!(error.code(), Some("noHealthyNode"));
+
+    // Errors without a code don't have the field
+    let error = Error::InvalidAmount("0".into());
+    assert_eq!(error.code(), None);
+    assert!(serde_json::to_value(&error).unwrap().get("code").is_none());
+}
+
+#[test]
+fn partially_sent_error() {
+    let block_id = BlockId::new([1; 32]);
+    let error = Error::PartiallySent {
+        block_ids: vec![block_id],
+        error: Box::new(Error::InputSelection(IsaError::InsufficientAmount {
+            found: 0,
+            required: 100,
+        })),
+    };
+
+    // The code of the original error is kept and the sent blocks are listed
+    let json = serde_json::to_value(&error).unwrap();
+    assert_eq!(json["type"], "partiallySent");
+    assert_eq!(json["code"], "insufficientFunds");
+    assert_eq!(json["blockIds"], serde_json::json!([block_id.to_string()]));
+    assert_eq!(
+        json["error"],
+        format!("insufficient amount: found 0, required 100, after sending the block(s) {block_id}")
+    );
+}
@@ -330,6 +330,63 @@ fn two_sdrucs_to_different_addresses_both_needed() {
     );
 }
 
+#[test]
+fn two_sdrucs_to_different_addresses_deterministic() {
+    let protocol_parameters = protocol_parameters();
+
+    let inputs = build_inputs(vec![
+        Basic(
+            2_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            Some((BECH32_ADDRESS_ED25519_1, 1_000_000)),
+            None,
+            None,
+            None,
+        ),
+        Basic(
+            2_000_000,
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            None,
+            Some((BECH32_ADDRESS_ED25519_2, 1_000_000)),
+            None,
+            None,
+            None,
+        ),
+    ]);
+    let outputs = build_outputs(vec![Basic(
+        2_000_000,
+        BECH32_ADDRESS_ED25519_0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )]);
+
+    let select = || {
+        InputSelection::new(
+            inputs.clone(),
+            outputs.clone(),
+            addresses(vec![BECH32_ADDRESS_ED25519_0]),
+            protocol_parameters.clone(),
+        )
+        .select()
+        .unwrap()
+    };
+    let selected = select();
+
+    // The same inputs always result in the same transaction, so the essence hash can be reproduced offline
+    for _ in 0..10 {
+        let reselected = select();
+        assert_eq!(reselected.inputs, selected.inputs);
+        assert_eq!(reselected.outputs, selected.outputs);
+    }
+}
+
 #[test]
 fn two_sdrucs_to_different_addresses_one_needed() {
     let protocol_parameters = protocol_parameters();
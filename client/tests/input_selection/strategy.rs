@@ -0,0 +1,63 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{
+    api::input_selection::{InputSelection, InputSelectionStrategy},
+    block::protocol::protocol_parameters,
+};
+
+use crate::{
+    addresses, build_inputs, build_outputs,
+    Build::{self, Basic},
+    BECH32_ADDRESS_ED25519_0,
+};
+
+fn basic(amount: u64) -> Build<'static> {
+    Basic(amount, BECH32_ADDRESS_ED25519_0, None, None, None, None, None, None)
+}
+
+fn selected_amounts(strategy: InputSelectionStrategy) -> Vec<u64> {
+    let inputs = build_inputs(vec![
+        basic(1_000_000),
+        basic(2_000_000),
+        basic(3_000_000),
+        basic(5_000_000),
+    ]);
+    let outputs = build_outputs(vec![basic(2_500_000)]);
+
+    let selected = InputSelection::new(
+        inputs,
+        outputs,
+        addresses(vec![BECH32_ADDRESS_ED25519_0]),
+        protocol_parameters(),
+    )
+    .strategy(strategy)
+    .select()
+    .unwrap();
+
+    let mut amounts = selected
+        .inputs
+        .iter()
+        .map(|input| input.output.amount())
+        .collect::<Vec<_>>();
+    amounts.sort_unstable();
+    amounts
+}
+
+#[test]
+fn smallest_first() {
+    assert_eq!(
+        selected_amounts(InputSelectionStrategy::SmallestFirst),
+        [1_000_000, 2_000_000]
+    );
+}
+
+#[test]
+fn largest_first() {
+    assert_eq!(selected_amounts(InputSelectionStrategy::LargestFirst), [5_000_000]);
+}
+
+#[test]
+fn minimize_inputs() {
+    assert_eq!(selected_amounts(InputSelectionStrategy::MinimizeInputs), [3_000_000]);
+}
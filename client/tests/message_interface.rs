@@ -3,15 +3,37 @@
 
 #![cfg(feature = "message_interface")]
 
-use std::{env, str::FromStr};
+mod common;
 
+use std::{env, str::FromStr, sync::Mutex, time::Duration};
+
+use common::mock_node::{
+    basic_output_json, block_dto_json, block_id, block_id_json, block_metadata_json, error_json, mock_client,
+    mock_client_builder, output_id, output_ids_json, output_metadata_json, serve, serve_json,
+    spent_output_metadata_json, tips_json,
+};
 use dotenv::dotenv;
 use iota_client::{
-    api::GetAddressesBuilderOptions as GenerateAddressesOptions,
-    block::{block::dto::BlockDto, payload::transaction::dto::TransactionEssenceDto, BlockId},
-    message_interface::{self, Message, Response},
+    api::{
+        ConsolidationCheckpoint, FundedAddress, GetAddressesBuilderOptions as GenerateAddressesOptions, PayloadType,
+        TypedBlockDto,
+    },
+    block::{
+        address::Address,
+        block::dto::BlockDto,
+        output::OutputId,
+        parent::Parents,
+        payload::{
+            transaction::{dto::TransactionEssenceDto, TransactionEssence, TransactionId},
+            Payload, TaggedDataPayload,
+        },
+        Block, BlockBuilder, BlockId,
+    },
+    message_interface::{self, ClientMessageHandler, Message, Response},
     secret::SecretManagerDto,
+    AddressFormat, ClientBuilder,
 };
+use packable::PackableExt;
 
 #[tokio::test]
 async fn generate_addresses() {
@@ -47,6 +69,45 @@ async fn generate_addresses() {
     };
 }
 
+#[tokio::test]
+async fn generate_all_addresses() {
+    let message_handler = message_interface::create_message_handler(Some(r#"{"nodes":[]}"#.to_string())).unwrap();
+    let secret_manager = r#"{"hexSeed":"0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2"}"#;
+    let options = GenerateAddressesOptions {
+        coin_type: Some(4218),
+        account_index: Some(0),
+        range: Some(0..2),
+        internal: None,
+        bech32_hrp: Some("atoi".to_string()),
+        options: None,
+    };
+    let message = Message::GenerateAllAddresses {
+        secret_manager: serde_json::from_str::<SecretManagerDto>(secret_manager).unwrap(),
+        options,
+    };
+
+    let response = message_handler.send_message(message).await;
+    match response {
+        Response::GeneratedAllAddresses(addresses) => {
+            assert_eq!(addresses.len(), 4);
+            // Interleaved by index, the public address first
+            assert_eq!(
+                addresses.iter().map(|address| address.internal).collect::<Vec<_>>(),
+                [false, true, false, true]
+            );
+            assert_eq!(
+                addresses[0].address,
+                "atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r"
+            );
+            assert_eq!(
+                addresses[1].address,
+                "atoi1qprxpfvaz2peggq6f8k9cj8zfsxuw69e4nszjyv5kuf8yt70t2847shpjak"
+            );
+        }
+        _ => panic!("Unexpected response type"),
+    };
+}
+
 #[tokio::test]
 #[should_panic]
 async fn build_and_post_block() {
@@ -216,6 +277,50 @@ async fn stronghold() {
     std::fs::remove_file("teststronghold.stronghold").unwrap_or(());
 }
 
+#[tokio::test]
+async fn hash_transaction_essence_bytes() {
+    let message_handler = message_interface::create_message_handler(None).unwrap();
+
+    let essence_dto: TransactionEssenceDto = serde_json::from_str(
+        r#"{
+            "type": 1,
+            "networkId": "8453507715857476362",
+            "inputs": [{ "type": 0, "transactionId": "0x6cb5226d9390afa41ee02306d429e1db532c617f86679a094519e8935571980f", "transactionOutputIndex": 0 }],
+            "inputsCommitment": "0x2b9db8d620137f02061d207310ef8876cc43b78c2cb826936e0d64e13531bf85",
+            "outputs": [{
+                "type": 3,
+                "amount": "1000000",
+                "unlockConditions": [{ "type": 0, "address": { "type": 0, "pubKeyHash": "0x00b35b7176c3db9cb4856df8703576ae19a563b44ea9bed069646cc6aa10d11f" } }]
+            }]
+        }"#,
+    )
+    .unwrap();
+    let essence_bytes = TransactionEssence::try_from_dto_unverified(&essence_dto)
+        .unwrap()
+        .pack_to_vec();
+
+    let message = Message::HashTransactionEssenceBytes {
+        essence_bytes: prefix_hex::encode(essence_bytes),
+    };
+    match message_handler.send_message(message).await {
+        // Same hash as for the DTO
+        Response::TransactionEssenceHash(essence_hash) => assert_eq!(
+            essence_hash,
+            "0x4624e6735cadee3d6ee5d83f9f8848d454a434ddf2b891b885f664de87eee044"
+        ),
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    // Malformed bytes return an error instead of panicking
+    let message = Message::HashTransactionEssenceBytes {
+        essence_bytes: "0x0102".to_string(),
+    };
+    match message_handler.send_message(message).await {
+        Response::Error(iota_client::Error::Unpack(_)) => {}
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
 #[tokio::test]
 async fn hash_transaction_essence() {
     let message_handler = message_interface::create_message_handler(None).unwrap();
@@ -263,3 +368,588 @@ async fn hash_transaction_essence() {
         response_type => panic!("Unexpected response type: {response_type:?}"),
     }
 }
+
+#[tokio::test]
+async fn verify_pow() {
+    let message_handler = message_interface::create_message_handler(None).unwrap();
+
+    // Example from https://github.com/iotaledger/tips/blob/main/tips/TIP-0012/tip-0012.md#example with a score of
+    // 937.29
+    let block = "0x48656c6c6f2c20576f726c64215ee6aaaaaaaaaaaa";
+
+    for (min_pow_score, expected_valid) in [(900, true), (1000, false)] {
+        let message = Message::VerifyPow {
+            block: block.to_string(),
+            min_pow_score: Some(min_pow_score),
+        };
+
+        match message_handler.send_message(message).await {
+            Response::PowVerification(verification) => {
+                assert_eq!(verification.valid, expected_valid);
+                assert_eq!(verification.min_pow_score, min_pow_score);
+                assert!((verification.score - 937.2857142857143).abs() < f64::EPSILON);
+            }
+            response_type => panic!("Unexpected response type: {response_type:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn mapped_error_message() {
+    let client = ClientBuilder::new()
+        .with_error_message_mapper(|_error, kind| {
+            (kind == "block").then(|| "the provided address isn't a valid bech32 address".to_string())
+        })
+        .finish()
+        .unwrap();
+    let message_handler = ClientMessageHandler::with_client(client);
+
+    let response = message_handler
+        .send_message(Message::Bech32ToHex {
+            bech32: "invalid".to_string(),
+        })
+        .await;
+
+    match response {
+        Response::Error(error) => {
+            assert!(matches!(&error, iota_client::Error::Mapped { error, .. } if error.kind() == "block"));
+            assert_eq!(
+                serde_json::to_string(&error).unwrap(),
+                "{\"type\":\"block\",\"error\":\"the provided address isn't a valid bech32 address\"}"
+            );
+        }
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    // Messages can also be configured in the client options, like by the bindings
+    let message_handler =
+        message_interface::create_message_handler(Some(r#"{"errorMessages":{"block":"invalid address"}}"#.to_string()))
+            .unwrap();
+
+    let response = message_handler
+        .send_message(Message::Bech32ToHex {
+            bech32: "invalid".to_string(),
+        })
+        .await;
+
+    match response {
+        Response::Error(error) => assert_eq!(error.to_string(), "invalid address"),
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
+#[tokio::test]
+async fn post_block_json_validates_structure() {
+    let client_config = r#"{
+            "nodes":[],
+            "localPow":true
+    }"#
+    .to_string();
+    let message_handler = message_interface::create_message_handler(Some(client_config)).unwrap();
+
+    let message = Message::PostBlockJson {
+        block: serde_json::json!({ "protocolVersion": 2, "parents": "invalid" }),
+    };
+
+    match message_handler.send_message(message).await {
+        Response::Error(iota_client::Error::Json(_)) => {}
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
+#[tokio::test]
+async fn emit_nulls() {
+    let client_config = r#"{
+            "nodes":["http://localhost:14265"],
+            "localPow":true
+    }"#;
+
+    let message_handler = message_interface::create_message_handler(Some(client_config.to_string())).unwrap();
+    let response = message_handler.send_message(Message::GetNode).await;
+    assert_eq!(
+        message_handler.response_to_json(&response).unwrap(),
+        "{\"type\":\"node\",\"payload\":{\"url\":\"http://localhost:14265/\",\"auth\":null,\"disabled\":false}}"
+    );
+
+    let client = ClientBuilder::new()
+        .from_json(client_config)
+        .unwrap()
+        .with_emit_nulls(false)
+        .finish()
+        .unwrap();
+    let message_handler = ClientMessageHandler::with_client(client);
+    let response = message_handler.send_message(Message::GetNode).await;
+    // The order of the fields isn't kept
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap(),
+        serde_json::json!({ "type": "node", "payload": { "url": "http://localhost:14265/", "disabled": false } })
+    );
+}
+
+#[tokio::test]
+async fn emit_nulls_nested() {
+    let node = serve_json([("/api/core/v2/blocks/", block_metadata_json(0x01, None))]);
+    let client = mock_client_builder(&node).with_emit_nulls(false).finish().unwrap();
+    let message_handler = ClientMessageHandler::with_client(client);
+
+    let response = message_handler
+        .send_message(Message::GetInclusionStates {
+            block_ids: vec![BlockId::from_str(&block_id(0x01)).unwrap()],
+        })
+        .await;
+    let response =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+
+    // The missing inclusion state of the nested item is omitted instead of being `null`
+    assert_eq!(
+        response,
+        serde_json::json!({
+            "type": "inclusionStates",
+            "payload": { "items": [{ "index": 0, "item": { "blockId": block_id(0x01) } }], "errors": [] }
+        })
+    );
+}
+
+#[tokio::test]
+async fn double_spend_detected_event() {
+    let node = serve_json([("/api/core/v2/outputs/", spent_output_metadata_json(3))]);
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node));
+    let output_id = OutputId::from_str(&output_id(0, 0)).unwrap();
+    let events = Mutex::new(Vec::new());
+
+    message_handler
+        .watch_double_spends(
+            [(output_id, TransactionId::new([2; 32]))].into_iter().collect(),
+            Duration::from_millis(10),
+            |event| events.lock().unwrap().push(event),
+        )
+        .await
+        .unwrap();
+
+    let events = events.into_inner().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&events[0]).unwrap(),
+        serde_json::json!({
+            "type": "doubleSpendDetected",
+            "payload": {
+                "outputId": output_id.to_string(),
+                "transactionId": TransactionId::new([2; 32]).to_string(),
+                "conflictingTransactionId": TransactionId::new([3; 32]).to_string(),
+            }
+        })
+    );
+}
+
+fn address_format_json(address_format: AddressFormat, response: &Response) -> serde_json::Value {
+    let client = ClientBuilder::new()
+        .with_address_format(address_format)
+        .finish()
+        .unwrap();
+    let message_handler = ClientMessageHandler::with_client(client);
+    serde_json::from_str(&message_handler.response_to_json(response).unwrap()).unwrap()
+}
+
+fn packed_address_hex(bech32: &str) -> String {
+    prefix_hex::encode(Address::try_from_bech32(bech32).unwrap().1.pack_to_vec())
+}
+
+#[tokio::test]
+async fn address_format() {
+    let secret_manager = r#"{"mnemonic":"endorse answer radar about source reunion marriage tag sausage weekend frost daring base attack because joke dream slender leisure group reason prepare broken river"}"#;
+    let message = Message::GenerateAddresses {
+        secret_manager: serde_json::from_str::<SecretManagerDto>(secret_manager).unwrap(),
+        options: GenerateAddressesOptions {
+            range: Some(0..2),
+            bech32_hrp: Some("atoi".to_string()),
+            ..Default::default()
+        },
+    };
+    let response = message_interface::create_message_handler(None)
+        .unwrap()
+        .send_message(message)
+        .await;
+
+    let bech32 = address_format_json(AddressFormat::Bech32, &response);
+    let bech32_addresses = bech32["payload"].as_array().unwrap();
+    let hex_addresses = bech32_addresses
+        .iter()
+        .map(|address| packed_address_hex(address.as_str().unwrap()))
+        .collect::<Vec<_>>();
+    assert!(bech32_addresses[0].as_str().unwrap().starts_with("atoi1"));
+    // The packed address starts with the Ed25519 address kind.
+    assert!(hex_addresses[0].starts_with("0x00"));
+    assert_eq!(hex_addresses[0].len(), 2 + 2 * 33);
+
+    let hex = address_format_json(AddressFormat::Hex, &response);
+    assert_eq!(hex["payload"], serde_json::json!(hex_addresses));
+
+    let both = address_format_json(AddressFormat::Both, &response);
+    assert_eq!(both["payload"], bech32["payload"]);
+}
+
+#[test]
+fn address_format_balance() {
+    let address = "atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r";
+    let response = Response::FirstFundedAddress(Some(FundedAddress {
+        address: address.to_string(),
+        index: 0,
+        internal: false,
+        amount: "1000000".to_string(),
+    }));
+    let hex = packed_address_hex(address);
+
+    let bech32 = address_format_json(AddressFormat::Bech32, &response);
+    assert_eq!(bech32["payload"]["address"], address);
+    assert!(bech32["payload"].get("addressHex").is_none());
+
+    let hex_response = address_format_json(AddressFormat::Hex, &response);
+    assert_eq!(hex_response["payload"]["address"], hex);
+    assert_eq!(hex_response["payload"]["amount"], "1000000");
+
+    let both = address_format_json(AddressFormat::Both, &response);
+    assert_eq!(both["payload"]["address"], address);
+    assert_eq!(both["payload"]["addressHex"], hex);
+    assert_eq!(both["payload"]["amount"], "1000000");
+}
+
+#[test]
+fn address_format_output_metadata() {
+    let response = Response::OutputMetadata(serde_json::from_str(&output_metadata_json(false, 1, 2)).unwrap());
+    let expected = address_format_json(AddressFormat::Bech32, &response);
+
+    // Output metadata has no address fields, the IDs must not be changed.
+    for address_format in [AddressFormat::Hex, AddressFormat::Both] {
+        assert_eq!(address_format_json(address_format, &response), expected);
+    }
+}
+
+#[test]
+fn address_format_keeps_converted_bech32() {
+    let address = "atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r";
+    let response = Response::Bech32Address(address.to_string());
+
+    for address_format in [AddressFormat::Bech32, AddressFormat::Hex, AddressFormat::Both] {
+        assert_eq!(address_format_json(address_format, &response)["payload"], address);
+    }
+}
+
+#[test]
+fn idempotent_messages() {
+    let output_id = OutputId::from_str(&output_id(1, 0)).unwrap();
+
+    assert!(Message::GetInfo.is_idempotent());
+    assert!(Message::GetTips.is_idempotent());
+    assert!(Message::GetOutput { output_id }.is_idempotent());
+    assert!(!Message::PostTaggedData {
+        tag: "0x01".to_string(),
+        data: "Hello".to_string(),
+    }
+    .is_idempotent());
+    assert!(!Message::PostBlockRaw {
+        block_bytes: Vec::new()
+    }
+    .is_idempotent());
+    assert!(!Message::Reattach {
+        block_id: BlockId::new([1; 32])
+    }
+    .is_idempotent());
+}
+
+#[tokio::test]
+async fn post_tagged_data_requires_tag() {
+    let message_handler = message_interface::create_message_handler(None).unwrap();
+
+    for tag in ["", "0x"] {
+        let message = Message::PostTaggedData {
+            tag: tag.to_string(),
+            data: "Hello".to_string(),
+        };
+
+        match message_handler.send_message(message).await {
+            Response::Error(iota_client::Error::TaggedData(_)) => {}
+            response_type => panic!("Unexpected response type: {response_type:?}"),
+        }
+    }
+
+    let message = Message::PostTaggedData {
+        tag: "0xinvalid".to_string(),
+        data: "Hello".to_string(),
+    };
+
+    match message_handler.send_message(message).await {
+        Response::Error(iota_client::Error::PrefixHex(_)) => {}
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
+#[tokio::test]
+async fn decode_tagged_data_utf8() {
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::new([0; 32])]).unwrap())
+        .with_payload(Payload::from(
+            TaggedDataPayload::new(b"Hello".to_vec(), vec![0xff, 0xfe]).unwrap(),
+        ))
+        .finish()
+        .unwrap();
+    let response = Response::Block(TypedBlockDto::from(&block));
+
+    let client = ClientBuilder::new()
+        .with_decode_tagged_data_utf8(true)
+        .finish()
+        .unwrap();
+    let message_handler = ClientMessageHandler::with_client(client);
+    let json =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+    let payload = &json["payload"]["payload"];
+
+    assert_eq!(payload["tag"], "0x48656c6c6f");
+    assert_eq!(payload["tagUtf8"], "Hello");
+    // Invalid UTF-8 is only kept hex encoded.
+    assert_eq!(payload["data"], "0xfffe");
+    assert!(payload.get("dataUtf8").is_none());
+}
+
+#[test]
+fn typed_block_dto() {
+    let block = BlockBuilder::new(Parents::new(vec![BlockId::new([0; 32])]).unwrap())
+        .with_payload(Payload::from(
+            TaggedDataPayload::new(b"Hello".to_vec(), Vec::new()).unwrap(),
+        ))
+        .finish()
+        .unwrap();
+
+    let json = serde_json::to_value(TypedBlockDto::from(&block)).unwrap();
+    assert_eq!(json["payloadType"], "taggedData");
+    assert_eq!(json["payload"]["type"], 5);
+
+    // The typed DTO can be posted as block again.
+    let block_dto = serde_json::from_value::<BlockDto>(json).unwrap();
+    assert_eq!(block_dto, BlockDto::from(&block));
+}
+
+#[tokio::test]
+async fn build_and_post_block_returns_block() {
+    // With remote PoW the block is requested from the node after posting it
+    let node = serve(|path| match path {
+        "/api/core/v2/tips" => (200, tips_json()),
+        "/api/core/v2/blocks" => (201, block_id_json(1)),
+        _ if path.starts_with("/api/core/v2/blocks/") => (200, block_dto_json(true)),
+        _ => (404, "{}".to_string()),
+    });
+    let client = mock_client_builder(&node).with_local_pow(false).finish().unwrap();
+    let message_handler = ClientMessageHandler::with_client(client);
+
+    let response = message_handler
+        .send_message(Message::BuildAndPostBlock {
+            secret_manager: None,
+            options: None,
+        })
+        .await;
+    let json =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+
+    match response {
+        Response::BlockIdWithBlock(block_id, block_dto) => {
+            let block = Block::try_from_dto_unverified(&block_dto).unwrap();
+            assert_eq!(block_id, block.id());
+            assert!(matches!(block.payload(), Some(Payload::TaggedData(_))));
+        }
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+    assert_eq!(json["type"], "blockIdWithBlock");
+    assert_eq!(json["payload"][1]["payload"]["type"], 5);
+}
+
+#[tokio::test]
+async fn get_network_info_without_node() {
+    // The network info comes from the client's configuration, so clients without a node can answer it
+    let client = ClientBuilder::new().with_local_pow(false).finish().unwrap();
+    let message_handler = ClientMessageHandler::with_client(client);
+
+    let response = message_handler.send_message(Message::GetNetworkInfo).await;
+    let json =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+
+    assert_eq!(json["type"], "networkInfo");
+    assert_eq!(json["payload"]["protocolParameters"]["bech32Hrp"], "smr");
+    assert_eq!(json["payload"]["protocolParameters"]["minPowScore"], 1500);
+    assert_eq!(json["payload"]["localPow"], false);
+}
+
+#[tokio::test]
+async fn retry_until_included() {
+    // Serves a block whose metadata has the inclusion state, without promotion or reattachment being needed
+    let node = |ledger_inclusion_state: &'static str| {
+        serve(move |path| {
+            if path.ends_with("/metadata") {
+                (
+                    200,
+                    format!(
+                        r#"{{"blockId":"{}","parents":["{}"],"isSolid":true{ledger_inclusion_state}}}"#,
+                        block_id(1),
+                        block_id(0x33)
+                    ),
+                )
+            } else if path.starts_with("/api/core/v2/blocks/") {
+                (200, block_dto_json(true))
+            } else {
+                (404, "{}".to_string())
+            }
+        })
+    };
+    let message = || Message::RetryUntilIncluded {
+        block_id: BlockId::new([1; 32]),
+        interval: Some(0),
+        max_attempts: Some(2),
+        strategy: Default::default(),
+    };
+
+    let message_handler =
+        ClientMessageHandler::with_client(mock_client(&node(r#","ledgerInclusionState":"included""#)));
+    match message_handler.send_message(message()).await {
+        Response::RetryUntilIncludedSuccessful(blocks) => {
+            assert_eq!(blocks.len(), 1);
+            assert_eq!(blocks[0].0, BlockId::new([1; 32]));
+        }
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    // Without inclusion it stops after the maximum attempts
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node("")));
+    match message_handler.send_message(message()).await {
+        Response::Error(iota_client::Error::TangleInclusion(_)) => {}
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
+#[tokio::test]
+async fn get_treasury_amount_as_string() {
+    // The amount exceeds the safe integer range of JS numbers
+    let node = serve_json([(
+        "/api/core/v2/treasury",
+        format!(r#"{{"milestoneId":"{}","amount":"9007199254740993"}}"#, block_id(1)),
+    )]);
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node));
+
+    let response = message_handler.send_message(Message::GetTreasury).await;
+    let json =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+
+    assert_eq!(json["type"], "treasury");
+    assert_eq!(json["payload"]["milestoneId"], block_id(1));
+    assert_eq!(json["payload"]["amount"], "9007199254740993");
+}
+
+#[tokio::test]
+async fn get_included_block() {
+    let message = || Message::GetIncludedBlock {
+        transaction_id: TransactionId::new([1; 32]),
+    };
+
+    let node = serve_json([("/api/core/v2/transactions/", block_dto_json(true))]);
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node));
+    match message_handler.send_message(message()).await {
+        Response::Block(block) => assert_eq!(block.payload_type, PayloadType::TaggedData),
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+
+    // A transaction without included block isn't found instead of returning an empty response
+    let node = serve(|_| (404, error_json(404, "transaction not found")));
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node));
+    match message_handler.send_message(message()).await {
+        Response::Error(iota_client::Error::NotFound(_)) => {}
+        response_type => panic!("Unexpected response type: {response_type:?}"),
+    }
+}
+
+#[tokio::test]
+async fn get_utxo_changes_by_index() {
+    let node = serve_json([(
+        "/api/core/v2/milestones/by-index/7/utxo-changes",
+        format!(
+            r#"{{"index":7,"createdOutputs":["{}","{}"],"consumedOutputs":["{}"]}}"#,
+            output_id(1, 0),
+            output_id(1, 1),
+            output_id(2, 0)
+        ),
+    )]);
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node));
+
+    let response = message_handler
+        .send_message(Message::GetUtxoChangesByIndex { index: 7 })
+        .await;
+    let json =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+
+    // The output IDs keep the hex encoding of the node, the transaction ID followed by the output index
+    assert_eq!(json["type"], "milestoneUtxoChanges");
+    assert_eq!(
+        json["payload"],
+        serde_json::json!({
+            "index": 7,
+            "createdOutputs": [output_id(1, 0), output_id(1, 1)],
+            "consumedOutputs": [output_id(2, 0)],
+        })
+    );
+}
+
+#[tokio::test]
+async fn get_outputs_returns_failed_items() {
+    let unknown_output_id = output_id(0x11, 0);
+    let node = {
+        let unknown_output_id = unknown_output_id.clone();
+        serve(move |path| {
+            if path.ends_with(&unknown_output_id) {
+                (404, error_json(404, "output not found"))
+            } else {
+                (200, basic_output_json(1_000_000, false, 1, 1))
+            }
+        })
+    };
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node));
+
+    let output_ids = [output_id(0x00, 0), unknown_output_id, output_id(0x22, 0)]
+        .iter()
+        .map(|output_id| OutputId::from_str(output_id).unwrap())
+        .collect();
+    let response = message_handler.send_message(Message::GetOutputs { output_ids }).await;
+    let json =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+
+    assert_eq!(json["type"], "outputsBatch");
+    assert_eq!(json["payload"]["items"].as_array().unwrap().len(), 2);
+    assert_eq!(json["payload"]["items"][1]["index"], 2);
+    assert_eq!(json["payload"]["errors"][0]["index"], 1);
+    assert_eq!(json["payload"]["errors"][0]["error"]["type"], "notFound");
+}
+
+#[tokio::test]
+async fn consolidation_checkpoint() {
+    // Every address owns the same two outputs
+    let output_ids = vec![output_id(0x11, 0), output_id(0x22, 0)];
+    let node = serve_json([("/api/indexer/v1/outputs/basic", output_ids_json(1, &output_ids, None))]);
+    let message_handler = ClientMessageHandler::with_client(mock_client(&node));
+    let secret_manager = r#"{"hexSeed":"0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2"}"#;
+
+    let response = message_handler
+        .send_message(Message::ConsolidationCheckpoint {
+            secret_manager: serde_json::from_str(secret_manager).unwrap(),
+            generate_addresses_options: GenerateAddressesOptions {
+                range: Some(0..2),
+                ..Default::default()
+            },
+        })
+        .await;
+    let json =
+        serde_json::from_str::<serde_json::Value>(&message_handler.response_to_json(&response).unwrap()).unwrap();
+
+    assert_eq!(json["type"], "consolidationCheckpoint");
+    let remaining_outputs = &json["payload"]["remainingOutputs"];
+    assert_eq!(remaining_outputs[0]["addressIndex"], 1);
+    assert_eq!(remaining_outputs[1]["addressIndex"], 0);
+    assert_eq!(remaining_outputs[1]["outputIds"], serde_json::json!(output_ids));
+
+    // The checkpoint can be passed back to resume the consolidation
+    let checkpoint = serde_json::from_value::<ConsolidationCheckpoint>(json["payload"].clone()).unwrap();
+    assert_eq!(checkpoint.remaining_outputs.len(), 2);
+}
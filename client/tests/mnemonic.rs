@@ -1,7 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use iota_client::{Client, Result};
+use iota_client::{Client, Error, Result};
 
 #[tokio::test]
 async fn mnemonic() -> Result<()> {
@@ -19,3 +19,38 @@ async fn mnemonic() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn mnemonic_word_count() {
+    for word_count in [12, 15, 18, 21, 24] {
+        let mnemonic = Client::generate_mnemonic_with_word_count(word_count).unwrap();
+        assert_eq!(mnemonic.split_whitespace().count(), word_count);
+        assert!(Client::mnemonic_to_hex_seed(&mnemonic).is_ok());
+    }
+    assert_eq!(Client::generate_mnemonic().unwrap().split_whitespace().count(), 24);
+
+    assert!(matches!(
+        Client::generate_mnemonic_with_word_count(13),
+        Err(Error::InvalidMnemonic(_))
+    ));
+}
+
+#[test]
+fn mnemonic_with_passphrase() {
+    // BIP39 test vector
+    let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    assert_eq!(
+        Client::mnemonic_to_hex_seed_with_passphrase(mnemonic, "TREZOR").unwrap(),
+        "0xc55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+    );
+    assert_eq!(
+        Client::mnemonic_to_hex_seed_with_passphrase(mnemonic, "").unwrap(),
+        Client::mnemonic_to_hex_seed(mnemonic).unwrap()
+    );
+    // Wrong checksum
+    assert!(matches!(
+        Client::mnemonic_to_hex_seed_with_passphrase(&mnemonic.replace("about", "abandon"), "TREZOR"),
+        Err(Error::InvalidMnemonic(_))
+    ));
+}
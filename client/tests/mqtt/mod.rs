@@ -1,4 +1,5 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod subscription;
 mod topic;
@@ -0,0 +1,23 @@
+// Copyright 2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_client::{mqtt::Topic, Client};
+
+#[tokio::test]
+async fn unsubscribe_unknown_topic() {
+    let client = Client::builder()
+        .with_node("http://127.0.0.1:1")
+        .unwrap()
+        .with_ignore_node_health()
+        .finish()
+        .unwrap();
+
+    assert!(client.subscribed_topics().await.is_empty());
+    // Topics that aren't subscribed are ignored, without connecting to the broker.
+    client
+        .unsubscribe(vec![Topic::try_new("milestones").unwrap()])
+        .await
+        .unwrap();
+    client.unsubscribe(Vec::new()).await.unwrap();
+    assert!(client.subscribed_topics().await.is_empty());
+}
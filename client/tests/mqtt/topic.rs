@@ -87,3 +87,35 @@ fn invalid_tags() {
         Err(Error::InvalidTopic(_))
     ));
 }
+
+#[test]
+fn unknown_topics() {
+    // Milestone topics of the legacy event API, milestones are published on `milestone-info/*` instead.
+    assert!(matches!(
+        Topic::try_new("milestones/latest"),
+        Err(Error::InvalidTopic(topic)) if topic == "milestones/latest"
+    ));
+    assert!(matches!(
+        Topic::try_new("milestones/confirmed"),
+        Err(Error::InvalidTopic(_))
+    ));
+    assert!(matches!(Topic::try_new(""), Err(Error::InvalidTopic(_))));
+}
+
+#[test]
+fn invalid_unlock_addresses() {
+    // Invalid checksum.
+    assert!(matches!(
+        Topic::try_new("outputs/unlock/address/iota1qrwfnskm4f7utdrxqnkfntfqxehtpj8s0kf68zkcwm0yrhuemzjp5sjfw5w"),
+        Err(Error::InvalidTopic(_))
+    ));
+    assert!(matches!(
+        Topic::try_new("outputs/unlock/+/iota1qrwfnskm4f7utdrxqnkfntfqxehtpj8s0kf68zkcwm0yrhuemzjp5sjfw5w/spent"),
+        Err(Error::InvalidTopic(_))
+    ));
+    // Truncated.
+    assert!(matches!(
+        Topic::try_new("outputs/unlock/address/iota1qrwfnskm4f7utdrxqnkfntfqxehtpj8s0kf68zkcwm0yrhuemzjp5s"),
+        Err(Error::InvalidTopic(_))
+    ));
+}
@@ -5,16 +5,47 @@
 
 mod common;
 
-use common::{setup_client_with_node_health_ignored, FAUCET_URL, NODE_LOCAL};
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use common::{
+    mock_node::{
+        basic_output_json, block_dto_json, block_id, block_id_json, block_metadata_json, error_json, info_json,
+        mock_client, mock_client_builder, output_id, output_ids_json, output_metadata_json, serve, serve_json,
+        serve_sequence, spent_output_metadata_json, tips_json,
+    },
+    setup_client_with_node_health_ignored, FAUCET_URL, NODE_LOCAL,
+};
 use iota_client::{
-    bech32_to_hex, node_api::indexer::query_parameters::QueryParameter, request_funds_from_faucet,
-    secret::SecretManager, Client,
+    api::{BatchErrorMode, DoubleSpend, GetAddressesBuilderOptions, PayloadType, PendingBlock, PendingState},
+    bech32_to_hex,
+    constants::{SHIMMER_BECH32_HRP, SHIMMER_COIN_TYPE},
+    node_api::indexer::query_parameters::QueryParameter,
+    request_funds_from_faucet,
+    secret::SecretManager,
+    ApiTimeouts, Client, Error,
 };
-use iota_types::block::{
-    output::OutputId,
-    payload::{transaction::TransactionId, Payload},
-    BlockId,
+use iota_types::{
+    api::core::dto::{LedgerInclusionStateDto, PeerDto, RelationDto},
+    block::{
+        address::{Address, Ed25519Address},
+        input::UtxoInput,
+        output::{
+            unlock_condition::{AddressUnlockCondition, UnlockCondition},
+            BasicOutputBuilder, NftOutput, OutputId, TreasuryOutput,
+        },
+        parent::Parents,
+        payload::{milestone::MilestoneId, transaction::TransactionId, MilestonePayload, Payload, TaggedDataPayload},
+        Block, BlockDto, BlockId,
+    },
 };
+use packable::PackableExt;
 
 // THIS SEED SERVES FOR TESTING PURPOSES! DON'T USE THIS SEED IN PRODUCTION!
 const DEFAULT_DEVELOPMENT_SEED: &str = "0x256a818b2aac458941f7274985a410e57fb750f3a3a67969ece5bd9ae7eef5b2";
@@ -221,6 +252,62 @@ async fn test_get_peers() {
     println!("{r:#?}");
 }
 
+#[ignore]
+#[tokio::test]
+async fn test_get_peer() {
+    let client = setup_client_with_node_health_ignored();
+
+    if let Some(peer) = client.get_peers().await.unwrap().first() {
+        assert_eq!(client.get_peer(&peer.id).await.unwrap().id, peer.id);
+    }
+
+    assert!(matches!(
+        client.get_peer("12D3KooWNotExisting").await,
+        Err(Error::PeerNotFound(peer_id)) if peer_id == "12D3KooWNotExisting"
+    ));
+}
+
+#[test]
+fn peer_response() {
+    let peer: PeerDto = serde_json::from_value(serde_json::json!({
+        "id": "12D3KooWRVt4Engu27jHnF2RjfX48EqiAqJbgLfFdHNt3Vn6BtJK",
+        "multiAddresses": ["/ip4/127.0.0.1/tcp/15600"],
+        "alias": "node-2",
+        "relation": "known",
+        "connected": true,
+        "gossip": {
+            "heartbeat": {
+                "solidMilestoneIndex": 120,
+                "prunedMilestoneIndex": 0,
+                "latestMilestoneIndex": 121,
+                "connectedPeers": 2,
+                "syncedPeers": 2
+            },
+            "metrics": {
+                "newBlocks": 10,
+                "receivedBlocks": 25,
+                "knownBlocks": 15,
+                "receivedBlockRequests": 1,
+                "receivedMilestoneRequests": 2,
+                "receivedHeartbeats": 30,
+                "sentBlocks": 12,
+                "sentBlockRequests": 3,
+                "sentMilestoneRequests": 4,
+                "sentHeartbeats": 31,
+                "droppedPackets": 0
+            }
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(peer.relation, RelationDto::Known);
+    let gossip = peer.gossip.unwrap();
+    assert_eq!(gossip.heartbeat.latest_milestone_index, 121);
+    assert_eq!(gossip.metrics.new_blocks, 10);
+    assert_eq!(gossip.metrics.received_blocks, 25);
+    assert_eq!(gossip.metrics.known_blocks, 15);
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_get_milestone_by_id() {
@@ -245,6 +332,26 @@ async fn test_get_milestone_by_id() {
     println!("{r:#?}");
 }
 
+#[ignore]
+#[tokio::test]
+async fn test_get_confirming_milestone() {
+    let client = setup_client_with_node_health_ignored();
+    let block_id = setup_tagged_data_block().await;
+
+    client.retry_until_included(&block_id, None, None).await.unwrap();
+
+    let referenced_by_milestone_index = client
+        .get_block_metadata(&block_id)
+        .await
+        .unwrap()
+        .referenced_by_milestone_index
+        .unwrap();
+    let milestone = client.get_confirming_milestone(&block_id).await.unwrap();
+
+    assert_eq!(milestone.essence().index().0, referenced_by_milestone_index);
+    println!("{}", milestone.essence().timestamp());
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_get_milestone_by_index() {
@@ -260,6 +367,21 @@ async fn test_get_milestone_by_index() {
     println!("{r:#?}");
 }
 
+#[ignore]
+#[tokio::test]
+async fn test_get_milestone_by_index_raw() {
+    let client = setup_client_with_node_health_ignored();
+
+    let index = client.get_info().await.unwrap().node_info.status.latest_milestone.index;
+
+    let milestone = client.get_milestone_by_index(index).await.unwrap();
+    let milestone_raw = client.get_milestone_by_index_raw(index).await.unwrap();
+
+    let milestone_from_raw =
+        MilestonePayload::unpack_verified(milestone_raw, &client.get_protocol_parameters().await.unwrap()).unwrap();
+    assert_eq!(milestone_from_raw, milestone);
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_get_utxo_changes_by_id() {
@@ -338,3 +460,1118 @@ async fn test_get_included_block() {
 
     println!("{r:#?}");
 }
+
+#[ignore]
+#[tokio::test]
+async fn test_get_latest_milestone_timestamp() {
+    let client = setup_client_with_node_health_ignored();
+
+    let milestone_timestamp = client.get_latest_milestone_timestamp().await.unwrap();
+    let milestone = client
+        .get_milestone_by_index(milestone_timestamp.milestone_index)
+        .await
+        .unwrap();
+
+    assert_eq!(milestone_timestamp.milestone_timestamp, milestone.essence().timestamp());
+    println!("{milestone_timestamp:#?}");
+}
+
+#[ignore]
+#[tokio::test]
+async fn test_post_block_json() {
+    let client = setup_client_with_node_health_ignored();
+
+    let block = client
+        .finish_block_builder(
+            None,
+            Some(Payload::TaggedData(Box::new(
+                TaggedDataPayload::new(b"Hello".to_vec(), b"Tangle".to_vec()).unwrap(),
+            ))),
+        )
+        .await
+        .unwrap();
+    let block_json = serde_json::to_value(BlockDto::from(&block)).unwrap();
+
+    let block_id = client.post_block_json(block_json).await.unwrap();
+
+    // The block ID is the same as the one of the packed block
+    assert_eq!(block_id, block.id());
+}
+
+#[ignore]
+#[tokio::test]
+async fn test_get_inclusion_states() {
+    let client = setup_client_with_node_health_ignored();
+
+    let confirmed_block_id = setup_tagged_data_block().await;
+    client
+        .retry_until_included(&confirmed_block_id, None, None)
+        .await
+        .unwrap();
+    let pending_block_id = setup_tagged_data_block().await;
+    let unknown_block_id = BlockId::new([1; 32]);
+
+    let inclusion_states = client
+        .get_inclusion_states(vec![confirmed_block_id, pending_block_id, unknown_block_id])
+        .await
+        .unwrap();
+
+    assert_eq!(inclusion_states.items.len(), 2);
+    assert_eq!(inclusion_states.items[0].0, 0);
+    assert_eq!(inclusion_states.items[0].1.block_id, confirmed_block_id);
+    assert_eq!(
+        inclusion_states.items[0].1.ledger_inclusion_state,
+        Some(LedgerInclusionStateDto::NoTransaction)
+    );
+    assert_eq!(inclusion_states.items[1].1.block_id, pending_block_id);
+    assert_eq!(inclusion_states.errors.len(), 1);
+    assert_eq!(inclusion_states.errors[0].0, 2);
+}
+
+#[tokio::test]
+async fn test_retry_on_parse_error() {
+    // A node with a different API version returns tips in another format
+    let outdated_node = serve_json([("/", r#"{"tipMessageIds":["0x01"]}"#.to_string())]);
+    let node = serve_json([("/", tips_json())]);
+
+    let client = mock_client_builder(&node)
+        .with_primary_node(&outdated_node, None)
+        .unwrap()
+        .finish()
+        .unwrap();
+    let tips = client.get_tips().await.unwrap();
+    assert_eq!(tips.len(), 1);
+
+    let client = mock_client_builder(&node)
+        .with_primary_node(&outdated_node, None)
+        .unwrap()
+        .with_retry_on_parse_error(false)
+        .finish()
+        .unwrap();
+    let error = client.get_tips().await.unwrap_err();
+    assert!(matches!(error, Error::ResponseParse { body, .. } if body == r#"{"tipMessageIds":["0x01"]}"#));
+}
+
+#[tokio::test]
+async fn test_post_block_health_check() {
+    // The primary node is healthy, but its confirmed milestone lags behind
+    let lagging_node = serve_json([
+        ("/api/core/v2/info", info_json(100, 80, 0)),
+        ("/api/core/v2/blocks", block_id_json(1)),
+    ]);
+    let node = serve_json([
+        ("/api/core/v2/info", info_json(100, 99, 0)),
+        ("/api/core/v2/blocks", block_id_json(2)),
+    ]);
+    let block = Block::build(Parents::new(vec![BlockId::new([3; 32])]).unwrap())
+        .finish()
+        .unwrap();
+
+    let client = mock_client_builder(&node)
+        .with_primary_node(&lagging_node, None)
+        .unwrap()
+        .with_local_pow(true)
+        .finish()
+        .unwrap();
+    assert_eq!(client.post_block(&block).await.unwrap(), BlockId::new([1; 32]));
+
+    let client = mock_client_builder(&node)
+        .with_primary_node(&lagging_node, None)
+        .unwrap()
+        .with_local_pow(true)
+        .with_post_health_check(3)
+        .finish()
+        .unwrap();
+    assert_eq!(client.post_block(&block).await.unwrap(), BlockId::new([2; 32]));
+}
+
+#[tokio::test]
+async fn test_read_retry() {
+    let (node, requests) = serve_sequence(
+        "/api/core/v2/tips",
+        [(503, "{}".to_string()), (503, "{}".to_string()), (200, tips_json())],
+    );
+
+    let client = mock_client_builder(&node)
+        .with_read_retry(3, Duration::from_millis(10))
+        .finish()
+        .unwrap();
+    // The read request recovers after two transient failures
+    assert_eq!(client.get_tips().await.unwrap().len(), 1);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    let (node, requests) = serve_sequence(
+        "/api/core/v2/blocks",
+        [(503, "{}".to_string()), (201, block_id_json(1))],
+    );
+    let block = Block::build(Parents::new(vec![BlockId::new([3; 32])]).unwrap())
+        .finish()
+        .unwrap();
+
+    let client = mock_client_builder(&node)
+        .with_local_pow(true)
+        .with_read_retry(3, Duration::from_millis(10))
+        .finish()
+        .unwrap();
+    // Posting isn't idempotent, so it's not retried
+    assert!(client.post_block(&block).await.is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_post_block_json_bytes_fallback() {
+    // The node doesn't accept JSON, so the block is posted as bytes
+    let (node, requests) = serve_sequence(
+        "/api/core/v2/blocks",
+        [(415, "{}".to_string()), (201, block_id_json(1))],
+    );
+    let block = Block::build(Parents::new(vec![BlockId::new([3; 32])]).unwrap())
+        .finish()
+        .unwrap();
+
+    let client = mock_client_builder(&node).with_local_pow(true).finish().unwrap();
+    let block_json = serde_json::to_value(BlockDto::from(&block)).unwrap();
+    assert_eq!(client.post_block_json(block_json).await.unwrap(), BlockId::new([1; 32]));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_consolidate_funds_without_outputs() {
+    let node = serve_json([("/api/indexer/v1/outputs/basic", output_ids_json(1, &[], None))]);
+    let client = mock_client(&node);
+    let secret_manager = SecretManager::try_from_hex_seed(DEFAULT_DEVELOPMENT_SEED).unwrap();
+
+    let block_ids = client
+        .consolidate_funds_with_block_ids(
+            &secret_manager,
+            GetAddressesBuilderOptions {
+                range: Some(0..5),
+                bech32_hrp: Some("rms".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert!(block_ids.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_nodes_health() {
+    let healthy_node = serve(|_| (200, "{}"));
+    let unhealthy_node = serve(|_| (503, "{}"));
+
+    let client = Client::builder()
+        .with_nodes(&[&healthy_node, &unhealthy_node])
+        .unwrap()
+        .with_ignore_node_health()
+        .finish()
+        .unwrap();
+
+    let health = client.get_nodes_health().await.unwrap();
+    assert_eq!(health.len(), 2);
+    for node in health {
+        assert_eq!(node.healthy, node.url.trim_end_matches('/') == healthy_node);
+    }
+}
+
+#[tokio::test]
+async fn test_get_peers_unauthorized() {
+    let node = serve(|path| {
+        if path.starts_with("/api/core/v2/peers") {
+            (401, error_json(401, "missing or invalid jwt"))
+        } else {
+            (200, "{}".to_string())
+        }
+    });
+    let client = mock_client(&node);
+
+    let error = client.get_peers().await.unwrap_err();
+    assert!(matches!(error, Error::Unauthorized(_)));
+    assert_eq!(error.code(), Some("unauthorized"));
+}
+
+#[tokio::test]
+async fn test_add_and_remove_peer() {
+    let node = serve(|path| match path {
+        "/api/core/v2/peers" => (
+            201,
+            r#"{"id":"12D3KooWRVt4Engu27jHnF2RjfX48EqiAqJbgLfFdHNt3Vn6BtJK","multiAddresses":["/ip4/127.0.0.1/tcp/15600"],"alias":"node-2","relation":"known","connected":false}"#,
+        ),
+        "/api/core/v2/peers/12D3KooWRVt4Engu27jHnF2RjfX48EqiAqJbgLfFdHNt3Vn6BtJK" => (200, "{}"),
+        _ => (404, "{}"),
+    });
+    let client = mock_client(&node);
+
+    let peer = client
+        .add_peer(
+            "/ip4/127.0.0.1/tcp/15600/p2p/12D3KooWRVt4Engu27jHnF2RjfX48EqiAqJbgLfFdHNt3Vn6BtJK",
+            Some("node-2"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(peer.alias.as_deref(), Some("node-2"));
+
+    client.remove_peer(&peer.id).await.unwrap();
+    assert!(matches!(
+        client.remove_peer("12D3KooWNotExisting").await,
+        Err(Error::PeerNotFound(peer_id)) if peer_id == "12D3KooWNotExisting"
+    ));
+}
+
+#[tokio::test]
+async fn test_api_timeouts() {
+    let node = serve(|path| {
+        if path.starts_with("/api/core/v2/tips") {
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        (200, "{}")
+    });
+
+    let client = mock_client_builder(&node)
+        .with_api_timeouts(ApiTimeouts {
+            tips: Some(Duration::from_millis(100)),
+            ..Default::default()
+        })
+        .finish()
+        .unwrap();
+
+    let error = client.get_tips().await.unwrap_err();
+    assert!(matches!(error, Error::Timeout(_)), "{error:?}");
+    assert_eq!(error.code(), Some("timeout"));
+}
+
+#[tokio::test]
+async fn test_node_sync_disabled() {
+    let info = info_json(10, 10, 0).replace(r#""bech32Hrp":"smr""#, r#""bech32Hrp":"rms""#);
+    let (node, info_requests) = serve_sequence("/api/core/v2/info", [(200, info)]);
+
+    let client = Client::builder()
+        .with_node(&node)
+        .unwrap()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap();
+
+    // The network info is fetched once and the configured node is used without syncing it
+    assert_eq!(info_requests.load(Ordering::SeqCst), 1);
+    assert_eq!(client.get_bech32_hrp().await.unwrap(), "rms");
+    assert_eq!(client.get_node().unwrap().url.as_str(), format!("{node}/"));
+
+    let status = client.node_sync_status();
+    assert!(!status.enabled);
+    assert!(status.synced_nodes.is_empty());
+    assert_eq!(info_requests.load(Ordering::SeqCst), 1);
+
+    // Without a responding node the configured network info is kept
+    let (node, _) = serve_sequence("/api/core/v2/info", [(500, "{}".to_string())]);
+    let client = Client::builder()
+        .with_node(&node)
+        .unwrap()
+        .with_node_sync_disabled()
+        .finish()
+        .unwrap();
+    assert_eq!(client.get_bech32_hrp().await.unwrap(), "smr");
+}
+
+#[tokio::test]
+async fn test_quorum_threshold_error() {
+    let node_a = serve_json([("/api/core/v2/blocks", r#"{"isSolid":true}"#)]);
+    let node_b = serve_json([("/api/core/v2/blocks", r#"{"isSolid":false}"#)]);
+
+    let client = Client::builder()
+        .with_nodes(&[&node_a, &node_b])
+        .unwrap()
+        .with_ignore_node_health()
+        .with_quorum(true)
+        .with_min_quorum_size(2)
+        .with_quorum_threshold(100)
+        .finish()
+        .unwrap();
+
+    match client.get_block_metadata(&BlockId::new([1; 32])).await {
+        Err(Error::QuorumThresholdError {
+            quorum_size, responses, ..
+        }) => {
+            assert_eq!(quorum_size, 1);
+            // Both divergent responses are returned
+            let mut responses = responses.into_iter().map(|(response, _)| response).collect::<Vec<_>>();
+            responses.sort();
+            assert_eq!(responses, [r#"{"isSolid":false}"#, r#"{"isSolid":true}"#]);
+        }
+        res => panic!("unexpected result: {res:?}"),
+    }
+}
+
+// Serves the outputs of an address, with the given ledger index and amount for every output
+fn serve_quorum_node(output_ids: Vec<String>, ledger_index: u32, amount: u64) -> String {
+    serve(move |path| {
+        if path.starts_with("/api/indexer/v1/outputs/basic") {
+            (200, output_ids_json(ledger_index, &output_ids, None))
+        } else if path.starts_with("/api/indexer/v1/outputs/nft") {
+            (200, output_ids_json(ledger_index, &[], None))
+        } else if path.starts_with("/api/core/v2/outputs/") {
+            (200, basic_output_json(amount, false, 1, ledger_index))
+        } else {
+            (404, error_json(404, "not found"))
+        }
+    })
+}
+
+fn quorum_client(node_a: &str, node_b: &str) -> Client {
+    Client::builder()
+        .with_nodes(&[node_a, node_b])
+        .unwrap()
+        .with_ignore_node_health()
+        .with_quorum(true)
+        .with_min_quorum_size(2)
+        .with_quorum_threshold(100)
+        .finish()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_quorum_address_outputs() {
+    let address = Address::Ed25519(Ed25519Address::new([0; 32])).to_bech32("rms");
+    let query_parameters = || vec![QueryParameter::Address(address.clone())];
+
+    // Nodes at different ledger indexes agree if they return the same outputs
+    let client = quorum_client(
+        &serve_quorum_node(vec![output_id(0, 0)], 7, 1_000_000),
+        &serve_quorum_node(vec![output_id(0, 0)], 8, 1_000_000),
+    );
+    let output_ids = client.basic_output_ids(query_parameters()).await.unwrap();
+    assert_eq!(output_ids.items, [OutputId::from_str(&output_id(0, 0)).unwrap()]);
+    assert_eq!(client.get_address_total(&address).await.unwrap().balance, "1000000");
+
+    // Nodes that return different outputs for the address don't reach the quorum
+    let client = quorum_client(
+        &serve_quorum_node(vec![output_id(0, 0)], 7, 1_000_000),
+        &serve_quorum_node(vec![output_id(0, 1)], 7, 1_000_000),
+    );
+    match client.basic_output_ids(query_parameters()).await {
+        Err(Error::QuorumThresholdError {
+            quorum_size, responses, ..
+        }) => {
+            assert_eq!(quorum_size, 1);
+            assert_eq!(responses.len(), 2);
+        }
+        res => panic!("unexpected result: {res:?}"),
+    }
+
+    // Nodes that return different amounts for the outputs don't reach the quorum for the balance
+    let client = quorum_client(
+        &serve_quorum_node(vec![output_id(0, 0)], 7, 1_000_000),
+        &serve_quorum_node(vec![output_id(0, 0)], 7, 2_000_000),
+    );
+    match client.get_address_total(&address).await {
+        Err(Error::Batch(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert!(matches!(
+                errors[0].1,
+                Error::QuorumThresholdError { quorum_size: 1, .. }
+            ));
+        }
+        res => panic!("unexpected result: {res:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_primary_node_fallback() {
+    let (primary_node, primary_requests) = serve_sequence("/api/core/v2/tips", [(503, "{}".to_string())]);
+    let (node, requests) = serve_sequence("/api/core/v2/tips", [(200, tips_json())]);
+
+    let client = mock_client_builder(&node)
+        .with_primary_node(&primary_node, None)
+        .unwrap()
+        .finish()
+        .unwrap();
+
+    // The primary node is tried first and the other node is used when it fails
+    assert_eq!(client.get_tips().await.unwrap().len(), 1);
+    assert_eq!(primary_requests.load(Ordering::SeqCst), 1);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_address_output_ids() {
+    let basic_output_id = output_id(0x00, 0);
+    let basic_output_id_2 = output_id(0x00, 16);
+    let alias_output_id = output_id(0x11, 0);
+    let nft_output_id = output_id(0x22, 0);
+
+    let node = {
+        let (basic_output_id, basic_output_id_2, alias_output_id, nft_output_id) = (
+            basic_output_id.clone(),
+            basic_output_id_2.clone(),
+            alias_output_id.clone(),
+            nft_output_id.clone(),
+        );
+        serve(move |path| {
+            let body = if path.starts_with("/api/indexer/v1/outputs/basic") {
+                // The second page is only returned for the cursor of the first one
+                if path.contains("cursor=next") {
+                    output_ids_json(1, &[basic_output_id_2.clone()], None)
+                } else {
+                    output_ids_json(1, &[basic_output_id.clone()], Some("next"))
+                }
+            } else if path.starts_with("/api/indexer/v1/outputs/alias") {
+                // The address is state controller and governor of the same alias output
+                output_ids_json(1, &[alias_output_id.clone()], None)
+            } else if path.starts_with("/api/indexer/v1/outputs/nft") {
+                output_ids_json(1, &[nft_output_id.clone()], None)
+            } else {
+                "{}".to_string()
+            };
+            (200, body)
+        })
+    };
+    let client = mock_client(&node);
+    let address = Address::Ed25519(Ed25519Address::new([0; 32])).to_bech32("rms");
+
+    let output_ids = client.address_output_ids(&address, None).await.unwrap();
+    let expected = [
+        basic_output_id,
+        basic_output_id_2,
+        alias_output_id,
+        nft_output_id.clone(),
+    ]
+    .map(|output_id| OutputId::from_str(&output_id).unwrap());
+    assert_eq!(output_ids, expected);
+
+    let output_ids = client
+        .address_output_ids(&address, Some(NftOutput::KIND))
+        .await
+        .unwrap();
+    assert_eq!(output_ids, [OutputId::from_str(&nft_output_id).unwrap()]);
+
+    assert!(matches!(
+        client.address_output_ids(&address, Some(TreasuryOutput::KIND)).await,
+        Err(Error::Block(iota_types::block::Error::UnsupportedOutputKind(
+            TreasuryOutput::KIND
+        )))
+    ));
+}
+
+#[tokio::test]
+async fn test_find_blocks_with_payload_types() {
+    let tagged_data_block_id = block_id(0x11);
+    let no_payload_block_id = block_id(0x22);
+
+    let node = {
+        let tagged_data_block_id = tagged_data_block_id.clone();
+        serve(move |path| (200, block_dto_json(path.ends_with(&tagged_data_block_id))))
+    };
+    let client = mock_client(&node);
+    let block_ids = [tagged_data_block_id, no_payload_block_id].map(|block_id| BlockId::from_str(&block_id).unwrap());
+
+    let blocks = client
+        .find_blocks_with_payload_types(&block_ids, &[PayloadType::TaggedData])
+        .await
+        .unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert!(matches!(blocks[0].payload(), Some(Payload::TaggedData(_))));
+
+    let blocks = client
+        .find_blocks_with_payload_types(&block_ids, &[PayloadType::None])
+        .await
+        .unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert!(blocks[0].payload().is_none());
+
+    // No payload types return all blocks
+    let blocks = client.find_blocks_with_payload_types(&block_ids, &[]).await.unwrap();
+    assert_eq!(blocks.len(), 2);
+}
+
+#[tokio::test]
+async fn test_batch_error_mode() {
+    let unknown_output_id = output_id(0x11, 0);
+    let node = {
+        let unknown_output_id = unknown_output_id.clone();
+        serve(move |path| {
+            if path.ends_with(&unknown_output_id) {
+                (404, error_json(404, "output not found"))
+            } else {
+                (200, basic_output_json(1_000_000, false, 1, 1))
+            }
+        })
+    };
+    let output_ids = [output_id(0x00, 0), unknown_output_id, output_id(0x22, 0)]
+        .map(|output_id| OutputId::from_str(&output_id).unwrap())
+        .to_vec();
+
+    // The default mode requests every output and returns the failed one with its index
+    let client = mock_client(&node);
+    let outputs = client.get_outputs_batch(output_ids.clone()).await.unwrap();
+    assert_eq!(
+        outputs.items.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+        [0, 2]
+    );
+    assert_eq!(outputs.errors.len(), 1);
+    assert!(matches!(outputs.errors[0], (1, Error::NotFound(_))));
+    assert!(matches!(client.get_outputs(output_ids.clone()).await, Err(Error::Batch(errors)) if errors.len() == 1));
+
+    let client = mock_client_builder(&node)
+        .with_batch_error_mode(BatchErrorMode::FailFast)
+        .finish()
+        .unwrap();
+    assert!(matches!(
+        client.get_outputs(output_ids.clone()).await,
+        Err(Error::NotFound(_))
+    ));
+    assert!(matches!(
+        client.get_outputs_batch(output_ids).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_remote_pow_not_supported() {
+    let node = serve(|_| (403, r#"{"error":{"message":"proof of work is not enabled"}}"#));
+    let block = Block::build(Parents::new(vec![BlockId::new([3; 32])]).unwrap())
+        .finish()
+        .unwrap();
+
+    let client = mock_client_builder(&node)
+        .with_local_pow(false)
+        .with_fallback_to_local_pow(false)
+        .finish()
+        .unwrap();
+    let error = client.post_block(&block).await.unwrap_err();
+
+    assert!(matches!(error, Error::RemotePowNotSupported(_)), "{error:?}");
+    assert_eq!(error.code(), Some("remotePowNotSupported"));
+}
+
+#[tokio::test]
+async fn test_next_unused_address_skips_spent_history() {
+    let secret_manager = SecretManager::try_from_hex_seed(DEFAULT_DEVELOPMENT_SEED).unwrap();
+    let client = Client::builder().with_ignore_node_health().finish().unwrap();
+    let addresses = client
+        .get_addresses(&secret_manager)
+        .with_range(0..2)
+        .finish()
+        .await
+        .unwrap();
+
+    // The first address has no unspent outputs anymore, but its outputs got spent
+    let spent_address = addresses[0].clone();
+    let node = serve(move |path| {
+        if path.starts_with("/api/indexer/v1/outputs/") {
+            (200, output_ids_json(1, &[], None))
+        } else if path.starts_with(&format!("/api/explorer/v2/ledger/updates/by-address/{spent_address}")) {
+            (
+                200,
+                format!(r#"{{"items":[{{"outputId":"{}","isSpent":true}}]}}"#, output_id(0, 0)),
+            )
+        } else if path.starts_with("/api/explorer/v2/ledger/updates/by-address/") {
+            (200, r#"{"items":[]}"#.to_string())
+        } else {
+            (404, "{}".to_string())
+        }
+    });
+    let client = mock_client(&node);
+
+    let address = client
+        .get_next_unused_address(&secret_manager, SHIMMER_COIN_TYPE, 0)
+        .await
+        .unwrap();
+    assert_eq!(address, addresses[1]);
+}
+
+// Prepares a transaction spending the basic output served by the node, with the address reuse check enabled, and
+// returns its reuse warning.
+async fn prepare_reuse_warning(node: &str, remainder_address: Option<&str>) -> bool {
+    let client = mock_client(node);
+    let token_supply = client.get_token_supply().await.unwrap();
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .unwrap()
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+            Ed25519Address::new([2; 32]),
+        ))))
+        .finish_output(token_supply)
+        .unwrap();
+
+    let mut block_builder = client
+        .block()
+        .with_input(UtxoInput::from_str(&output_id(0x00, 0)).unwrap())
+        .unwrap()
+        .with_outputs(vec![output])
+        .unwrap()
+        .with_address_reuse_check(true);
+    if let Some(remainder_address) = remainder_address {
+        block_builder = block_builder.with_custom_remainder_address(remainder_address).unwrap();
+    }
+
+    block_builder.prepare_transaction().await.unwrap().reuse_warning
+}
+
+// Serves a basic output of the zero address, the unspent output IDs and the explorer ledger updates of `address`
+// and nothing for other addresses.
+fn serve_address_outputs(address: String, unspent_output_ids: Vec<String>, ledger_updates: Option<String>) -> String {
+    serve(move |path| {
+        if path.starts_with("/api/core/v2/outputs/") {
+            (200, basic_output_json(2_000_000, false, 1, 1))
+        } else if path.starts_with("/api/indexer/v1/outputs/basic") && path.contains(&address) {
+            (200, output_ids_json(1, &unspent_output_ids, None))
+        } else if path.starts_with("/api/indexer/v1/outputs/") {
+            (200, output_ids_json(1, &[], None))
+        } else if path.starts_with("/api/explorer/v2/ledger/updates/by-address/") {
+            match &ledger_updates {
+                Some(ledger_updates) if path.contains(&address) => (200, ledger_updates.clone()),
+                Some(_) => (200, r#"{"items":[]}"#.to_string()),
+                // The node doesn't have the explorer plugin
+                None => (404, "{}".to_string()),
+            }
+        } else {
+            (404, "{}".to_string())
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_address_reuse_warning() {
+    let input_address = Address::Ed25519(Ed25519Address::new([0; 32])).to_bech32(SHIMMER_BECH32_HRP);
+    let input_id = output_id(0x00, 0);
+
+    // The remainder goes back to the input address, which only owns the input that is spent
+    let node = serve_address_outputs(input_address.clone(), vec![input_id.clone()], None);
+    assert!(!prepare_reuse_warning(&node, None).await);
+
+    // The input address owns another unspent output
+    let node = serve_address_outputs(input_address.clone(), vec![input_id.clone(), output_id(0x11, 0)], None);
+    assert!(prepare_reuse_warning(&node, None).await);
+
+    // The input address owned an output that was already spent
+    let ledger_updates = format!(
+        r#"{{"items":[{{"outputId":"{input_id}","isSpent":false}},{{"outputId":"{}","isSpent":true}}]}}"#,
+        output_id(0x11, 0)
+    );
+    let node = serve_address_outputs(input_address.clone(), vec![input_id.clone()], Some(ledger_updates));
+    assert!(prepare_reuse_warning(&node, None).await);
+
+    // The remainder goes to a newly generated receive address, while the input address has a history
+    let receive_address = Address::Ed25519(Ed25519Address::new([1; 32])).to_bech32(SHIMMER_BECH32_HRP);
+    let ledger_updates = format!(
+        r#"{{"items":[{{"outputId":"{}","isSpent":true}}]}}"#,
+        output_id(0x11, 0)
+    );
+    let node = serve_address_outputs(input_address, vec![input_id, output_id(0x22, 0)], Some(ledger_updates));
+    assert!(!prepare_reuse_warning(&node, Some(&receive_address)).await);
+
+    // The address of the output already owns an output
+    let output_address = Address::Ed25519(Ed25519Address::new([2; 32])).to_bech32(SHIMMER_BECH32_HRP);
+    let node = serve_address_outputs(output_address, vec![output_id(0x33, 0)], None);
+    assert!(prepare_reuse_warning(&node, Some(&receive_address)).await);
+}
+
+#[tokio::test]
+async fn test_get_raw_milestone_pruned() {
+    let milestone_id = MilestoneId::new([1; 32]);
+
+    // The node pruned the milestones up to index 10
+    let node = serve(|path| {
+        if path.starts_with("/api/core/v2/info") {
+            (200, info_json(20, 20, 10))
+        } else {
+            (404, error_json(404, "milestone not found"))
+        }
+    });
+    let client = mock_client(&node);
+    assert!(matches!(
+        client.get_milestone_by_index_raw(5).await,
+        Err(Error::Pruned(milestone)) if milestone == "milestone 5"
+    ));
+    assert!(matches!(
+        client.get_milestone_by_index_raw(15).await,
+        Err(Error::NotFound(_))
+    ));
+    // The index of an unknown milestone ID can't be compared with the pruning index
+    assert!(matches!(
+        client.get_milestone_by_id_raw(&milestone_id).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_get_creating_block_pruned_or_unknown() {
+    let output_id = OutputId::from_str(&output_id(1, 0)).unwrap();
+    let node = |milestone_index_booked| {
+        serve(move |path| {
+            if path.starts_with("/api/core/v2/info") {
+                (200, info_json(20, 20, 10))
+            } else if path.ends_with("/metadata") && milestone_index_booked > 0 {
+                (200, output_metadata_json(false, milestone_index_booked, 20))
+            } else {
+                (404, error_json(404, "not found"))
+            }
+        })
+    };
+
+    // The output was booked at or below the pruning index
+    let client = mock_client(&node(10));
+    assert!(matches!(
+        client.get_creating_block(&output_id).await,
+        Err(Error::Pruned(_))
+    ));
+
+    // The output was booked after the pruning index, so the block isn't pruned
+    let client = mock_client(&node(15));
+    assert!(matches!(
+        client.get_creating_block(&output_id).await,
+        Err(Error::NotFound(_))
+    ));
+
+    // The node doesn't know the output
+    let client = mock_client(&node(0));
+    assert!(matches!(
+        client.get_creating_block(&output_id).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_watch_double_spends_unknown_output() {
+    let node = serve(|_| (404, error_json(404, "output not found")));
+    let watched_outputs = [(
+        OutputId::from_str(&output_id(1, 0)).unwrap(),
+        TransactionId::new([2; 32]),
+    )]
+    .into_iter()
+    .collect();
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(10),
+        mock_client(&node).watch_double_spends(watched_outputs, Duration::from_millis(10), |_| {}),
+    )
+    .await
+    .expect("watching an unknown output didn't return");
+    assert!(matches!(result, Err(Error::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_watch_double_spends_conflicting_spend() {
+    let polls = Arc::new(AtomicUsize::new(0));
+    let polls_ = polls.clone();
+    // The node is unavailable first, then the output is unspent and afterwards spent by another transaction
+    let node = serve(move |path| {
+        if !path.ends_with("/metadata") {
+            return (200, "{}".to_string());
+        }
+        match polls_.fetch_add(1, Ordering::SeqCst) {
+            0 => (503, error_json(503, "service unavailable")),
+            1 => (200, output_metadata_json(false, 1, 1)),
+            _ => (200, spent_output_metadata_json(3)),
+        }
+    });
+    let output_id = OutputId::from_str(&output_id(0, 0)).unwrap();
+    let transaction_id = TransactionId::new([2; 32]);
+
+    let mut double_spends = Vec::new();
+    tokio::time::timeout(
+        Duration::from_secs(10),
+        mock_client(&node).watch_double_spends(
+            [(output_id, transaction_id)].into_iter().collect(),
+            Duration::from_millis(10),
+            |double_spend| double_spends.push(double_spend),
+        ),
+    )
+    .await
+    .expect("watching the output didn't return")
+    .unwrap();
+
+    assert!(polls.load(Ordering::SeqCst) >= 3);
+    assert_eq!(
+        double_spends,
+        [DoubleSpend {
+            output_id,
+            transaction_id,
+            conflicting_transaction_id: TransactionId::new([3; 32]),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_get_address_total() {
+    let address = Address::Ed25519(Ed25519Address::new([0; 32])).to_bech32("rms");
+    let output_requests = Arc::new(AtomicUsize::new(0));
+    let output_counter = output_requests.clone();
+
+    let node = serve(move |path| {
+        if path.starts_with("/api/indexer/v1/outputs/basic") {
+            (200, output_ids_json(7, &[output_id(0, 0), output_id(0, 1)], None))
+        } else if path.starts_with("/api/indexer/v1/outputs/nft") {
+            (200, output_ids_json(7, &[], None))
+        } else if path.starts_with("/api/core/v2/outputs/") {
+            output_counter.fetch_add(1, Ordering::SeqCst);
+            (200, basic_output_json(1_000_000, false, 1, 7))
+        } else {
+            (404, error_json(404, "not found"))
+        }
+    });
+
+    // The balance, count and storage deposit are all derived from the outputs of the indexer
+    let address_total = mock_client(&node).get_address_total(&address).await.unwrap();
+    assert_eq!(address_total.balance, "2000000");
+    assert_eq!(address_total.output_count, 2);
+    assert_ne!(address_total.storage_deposit, "0");
+    assert_eq!(address_total.ledger_index, 7);
+    assert_eq!(output_requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_check_pending_blocks_releases_unknown_and_orphaned_blocks() {
+    let node = serve(|path| match path.strip_prefix("/api/core/v2/blocks/") {
+        // Pruned
+        Some(path) if path.starts_with(&block_id(1)) => (404, error_json(404, "block not found")),
+        // Orphaned
+        Some(path) if path.starts_with(&block_id(2)) => (
+            200,
+            format!(
+                r#"{{"blockId":"{}","parents":["{}"],"isSolid":true,"shouldPromote":false,"shouldReattach":true}}"#,
+                block_id(2),
+                block_id(0x33)
+            ),
+        ),
+        Some(path) if path.starts_with(&block_id(3)) => (200, block_metadata_json(3, None)),
+        Some(path) if path.starts_with(&block_id(4)) => (200, block_metadata_json(4, Some("included"))),
+        _ => (404, error_json(404, "not found")),
+    });
+    let client = mock_client_builder(&node)
+        .with_output_reservation(true)
+        .finish()
+        .unwrap();
+    client
+        .import_pending_state(PendingState {
+            pending_blocks: (1..=4)
+                .map(|byte| PendingBlock {
+                    block_id: BlockId::new([byte; 32]),
+                    reserved_outputs: vec![OutputId::from_str(&output_id(byte, 0)).unwrap()],
+                })
+                .collect(),
+        })
+        .unwrap();
+
+    // One unknown block doesn't abort the sweep, only the block without inclusion state stays pending
+    let pending = client.check_pending_blocks().await.unwrap();
+    assert_eq!(pending, vec![BlockId::new([3; 32])]);
+    assert_eq!(
+        client.export_pending_state().pending_blocks,
+        vec![PendingBlock {
+            block_id: BlockId::new([3; 32]),
+            reserved_outputs: vec![OutputId::from_str(&output_id(3, 0)).unwrap()],
+        }]
+    );
+}
+
+#[tokio::test]
+async fn test_prepare_transaction_insufficient_storage_deposit() {
+    // Outputs are checked before any inputs are requested from the node
+    let node = serve(|_| (404, error_json(404, "not found")));
+    let client = mock_client(&node);
+    let output = BasicOutputBuilder::new_with_amount(1)
+        .unwrap()
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+            Ed25519Address::new([0; 32]),
+        ))))
+        .finish_output(client.get_token_supply().await.unwrap())
+        .unwrap();
+
+    let result = client
+        .block()
+        .with_outputs(vec![output])
+        .unwrap()
+        .prepare_transaction()
+        .await;
+    assert!(matches!(
+        result,
+        Err(Error::Block(
+            iota_types::block::Error::InsufficientStorageDepositAmount { amount: 1, .. }
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn test_spent_custom_input() {
+    let posted_blocks = Arc::new(AtomicUsize::new(0));
+    let post_counter = posted_blocks.clone();
+    let node = serve(move |path| {
+        if path.starts_with("/api/core/v2/outputs/") {
+            (200, basic_output_json(1_000_000, true, 1, 7))
+        } else if path.starts_with("/api/core/v2/blocks") {
+            post_counter.fetch_add(1, Ordering::SeqCst);
+            (201, block_id_json(1))
+        } else {
+            (404, error_json(404, "not found"))
+        }
+    });
+    let client = mock_client(&node);
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .unwrap()
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+            Ed25519Address::new([2; 32]),
+        ))))
+        .finish_output(client.get_token_supply().await.unwrap())
+        .unwrap();
+
+    // A pinned input that is already spent fails before anything is posted
+    let result = client
+        .block()
+        .with_input(UtxoInput::from_str(&output_id(0, 0)).unwrap())
+        .unwrap()
+        .with_outputs(vec![output])
+        .unwrap()
+        .finish()
+        .await;
+    match result {
+        Err(Error::InputAlreadySpent(spent_output_id)) => assert_eq!(spent_output_id, output_id(0, 0)),
+        res => panic!("unexpected result: {res:?}"),
+    }
+    assert_eq!(posted_blocks.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_find_outputs_deduplicates() {
+    let node = serve_json([
+        (
+            "/api/indexer/v1/outputs/basic",
+            output_ids_json(7, &[output_id(0, 0)], None),
+        ),
+        ("/api/core/v2/outputs/", basic_output_json(1_000_000, false, 1, 7)),
+    ]);
+    let client = mock_client(&node);
+    let address = Address::Ed25519(Ed25519Address::new([0; 32])).to_bech32("rms");
+    // The output is requested by its ID and also found via the address
+    let output_ids = [OutputId::from_str(&output_id(0, 0)).unwrap()];
+
+    let outputs = client.find_outputs(&output_ids, &[address.clone()]).await.unwrap();
+    assert_eq!(outputs.len(), 1);
+
+    let outputs = client
+        .find_outputs_with_duplicates(&output_ids, &[address])
+        .await
+        .unwrap();
+    assert_eq!(outputs.len(), 2);
+}
+
+#[tokio::test]
+async fn test_get_inclusion_states_error_mode() {
+    let node = serve(|path| {
+        if path == format!("/api/core/v2/blocks/{}/metadata", block_id(1)) {
+            (200, block_metadata_json(1, Some("included")))
+        } else if path == format!("/api/core/v2/blocks/{}/metadata", block_id(2)) {
+            (200, block_metadata_json(2, None))
+        } else {
+            (404, error_json(404, "block not found"))
+        }
+    });
+    let block_ids = vec![BlockId::new([1; 32]), BlockId::new([3; 32]), BlockId::new([2; 32])];
+
+    let inclusion_states = mock_client(&node)
+        .get_inclusion_states(block_ids.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        inclusion_states
+            .items
+            .iter()
+            .map(|(index, state)| (*index, state.ledger_inclusion_state.clone()))
+            .collect::<Vec<_>>(),
+        [(0, Some(LedgerInclusionStateDto::Included)), (2, None)]
+    );
+    assert!(matches!(inclusion_states.errors[..], [(1, Error::NotFound(_))]));
+
+    let client = mock_client_builder(&node)
+        .with_batch_error_mode(BatchErrorMode::FailFast)
+        .finish()
+        .unwrap();
+    assert!(matches!(
+        client.get_inclusion_states(block_ids).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_utxo_snapshot_deadline_cuts_slow_request() {
+    // The indexer only answers after the deadline
+    let node = serve(|path| {
+        if path.starts_with("/api/indexer") {
+            std::thread::sleep(Duration::from_secs(2));
+        }
+        (200, output_ids_json(1, &[], None))
+    });
+    let secret_manager = SecretManager::try_from_mnemonic(&Client::generate_mnemonic().unwrap()).unwrap();
+
+    let start = std::time::Instant::now();
+    let snapshot = mock_client(&node)
+        .export_utxo_snapshot_with_deadline(&secret_manager, SHIMMER_COIN_TYPE, 0, 20, Duration::from_millis(200))
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(!snapshot.complete);
+    assert!(snapshot.addresses.is_empty());
+}
+
+#[tokio::test]
+async fn test_can_afford_overflowing_outputs() {
+    let node = serve_json([("/api/indexer", output_ids_json(1, &[], None))]);
+    let secret_manager = SecretManager::try_from_mnemonic(&Client::generate_mnemonic().unwrap()).unwrap();
+    let output = BasicOutputBuilder::new_with_amount(u64::MAX)
+        .unwrap()
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(Address::Ed25519(
+            Ed25519Address::new([0; 32]),
+        ))))
+        .finish_output(u64::MAX)
+        .unwrap();
+
+    let affordability = mock_client(&node)
+        .can_afford(&secret_manager, SHIMMER_COIN_TYPE, 0, 1, &[output.clone(), output])
+        .await
+        .unwrap();
+
+    // The summed amount overflows a u64, so it can't be afforded
+    assert!(!affordability.affordable);
+    assert_eq!(affordability.required, (2 * u128::from(u64::MAX)).to_string());
+}
+
+#[tokio::test]
+async fn test_check_outputs_spent_error_mode() {
+    let node = serve(|path| {
+        if path == format!("/api/core/v2/outputs/{}/metadata", output_id(1, 0)) {
+            (200, output_metadata_json(true, 1, 2))
+        } else if path == format!("/api/core/v2/outputs/{}/metadata", output_id(2, 0)) {
+            (200, output_metadata_json(false, 1, 2))
+        } else {
+            (404, error_json(404, "output not found"))
+        }
+    });
+    let output_ids = [output_id(1, 0), output_id(3, 0), output_id(2, 0)]
+        .iter()
+        .map(|output_id| OutputId::from_str(output_id).unwrap())
+        .collect::<Vec<_>>();
+
+    let spent = mock_client(&node)
+        .check_outputs_spent(output_ids.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        spent
+            .items
+            .iter()
+            .map(|(index, state)| (*index, state.output_id, state.is_spent))
+            .collect::<Vec<_>>(),
+        [(0, output_ids[0], true), (2, output_ids[2], false)]
+    );
+    assert!(matches!(spent.errors[..], [(1, Error::NotFound(_))]));
+
+    let client = mock_client_builder(&node)
+        .with_batch_error_mode(BatchErrorMode::FailFast)
+        .finish()
+        .unwrap();
+    assert!(matches!(
+        client.check_outputs_spent(output_ids).await,
+        Err(Error::NotFound(_))
+    ));
+}
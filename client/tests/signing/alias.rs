@@ -95,6 +95,7 @@ async fn sign_alias_state_transition() -> Result<()> {
         essence,
         inputs_data: inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let unlocks = secret_manager
@@ -184,6 +185,7 @@ async fn sign_alias_governance_transition() -> Result<()> {
         essence,
         inputs_data: inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let unlocks = secret_manager
@@ -281,6 +283,7 @@ async fn alias_reference_unlocks() -> Result<()> {
         essence,
         inputs_data: inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let unlocks = secret_manager
@@ -86,6 +86,7 @@ async fn single_ed25519_unlock() -> Result<()> {
         essence,
         inputs_data: inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let unlocks = secret_manager
@@ -208,6 +209,7 @@ async fn ed25519_reference_unlocks() -> Result<()> {
         essence,
         inputs_data: inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let unlocks = secret_manager
@@ -330,6 +332,7 @@ async fn two_signature_unlocks() -> Result<()> {
         essence,
         inputs_data: inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let unlocks = secret_manager
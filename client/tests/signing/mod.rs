@@ -361,6 +361,7 @@ async fn all_combined() -> Result<()> {
         essence,
         inputs_data: selected.inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let unlocks = secret_manager
@@ -88,6 +88,7 @@ async fn nft_reference_unlocks() -> Result<()> {
         essence,
         inputs_data: inputs,
         remainder: None,
+        reuse_warning: false,
     };
 
     let current_time = 100;
@@ -5,17 +5,25 @@
 
 mod common;
 
+use std::time::Duration;
+
 use iota_client::{
     block::{
-        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, UnlockCondition},
+        input::Input,
+        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, Output, UnlockCondition},
         payload::transaction::TransactionEssence,
     },
+    constants::SHIMMER_COIN_TYPE,
     node_api::indexer::query_parameters::QueryParameter,
-    Result,
+    request_funds_from_faucet,
+    secret::SecretManager,
+    Client, Result,
 };
 use iota_types::block::{output::OutputId, payload::Payload};
 
-use self::common::create_client_and_secret_manager_with_funds;
+use self::common::{
+    create_client_and_secret_manager_with_funds, setup_client_with_node_health_ignored, FAUCET_URL, NODE_LOCAL,
+};
 
 #[ignore]
 #[tokio::test]
@@ -69,3 +77,356 @@ async fn send_basic_output() -> Result<()> {
 
     Ok(())
 }
+
+#[ignore]
+#[tokio::test]
+async fn remainder_address_reuse_warning() -> Result<()> {
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+
+    let token_supply = client.get_token_supply().await?;
+
+    let second_address = client.get_addresses(&secret_manager).with_range(1..2).get_raw().await?[0];
+
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)?
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(second_address)))
+        .finish_output(token_supply)?;
+
+    // The remainder is sent back to the first address, which already owns the faucet output
+    let prepared_transaction_data = client
+        .block()
+        .with_secret_manager(&secret_manager)
+        .with_outputs(vec![output.clone()])?
+        .with_address_reuse_check(true)
+        .prepare_transaction()
+        .await?;
+    assert!(prepared_transaction_data.remainder.is_some());
+    assert!(prepared_transaction_data.reuse_warning);
+
+    // Without the check, the warning is never set
+    let prepared_transaction_data = client
+        .block()
+        .with_secret_manager(&secret_manager)
+        .with_outputs(vec![output])?
+        .prepare_transaction()
+        .await?;
+    assert!(!prepared_transaction_data.reuse_warning);
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn export_utxo_snapshot() -> Result<()> {
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+
+    let snapshot = client
+        .export_utxo_snapshot(&secret_manager, SHIMMER_COIN_TYPE, 0, 20)
+        .await?;
+
+    // The faucet funds are on the first public address
+    let address = client.get_addresses(&secret_manager).with_range(0..1).finish().await?[0].clone();
+    let token_supply = client.get_token_supply().await?;
+    let output_ids_response = client
+        .basic_output_ids(vec![QueryParameter::Address(address.clone())])
+        .await?;
+    let balance = client
+        .get_outputs(output_ids_response.items)
+        .await?
+        .iter()
+        .map(|output_response| Ok(Output::try_from_dto(&output_response.output, token_supply)?.amount()))
+        .sum::<Result<u64>>()?;
+
+    assert_eq!(snapshot.addresses.len(), 1);
+    assert_eq!(snapshot.addresses[0].address, address);
+    assert_eq!(snapshot.addresses[0].amount, balance.to_string());
+    assert_eq!(snapshot.total_amount, balance.to_string());
+    assert!(snapshot.complete);
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn export_utxo_snapshot_with_deadline() -> Result<()> {
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+
+    // The deadline is already reached before the first address is scanned
+    let snapshot = client
+        .export_utxo_snapshot_with_deadline(&secret_manager, SHIMMER_COIN_TYPE, 0, 20, Duration::ZERO)
+        .await?;
+
+    assert!(!snapshot.complete);
+    assert!(snapshot.addresses.is_empty());
+    assert_eq!(snapshot.total_amount, "0");
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn computed_output_ids_match_ledger() -> Result<()> {
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+
+    let token_supply = client.get_token_supply().await?;
+
+    let second_address = client.get_addresses(&secret_manager).with_range(1..2).get_raw().await?[0];
+
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)?
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(second_address)))
+        .finish_output(token_supply)?;
+
+    let block = client
+        .block()
+        .with_secret_manager(&secret_manager)
+        .with_outputs(vec![output])?
+        .finish()
+        .await?;
+
+    let tx_payload = match block.payload().unwrap() {
+        Payload::Transaction(tx_payload) => tx_payload,
+        _ => panic!("missing transaction payload"),
+    };
+    let computed_output_ids = Client::compute_output_ids(tx_payload)?;
+    // provided output + remainder output
+    assert_eq!(computed_output_ids.len(), 2);
+
+    client.retry_until_included(&block.id(), None, None).await?;
+
+    let output_responses = client.get_outputs(computed_output_ids.clone()).await?;
+    let ledger_output_ids = output_responses
+        .iter()
+        .map(|output_response| output_response.metadata.output_id())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    assert_eq!(ledger_output_ids, computed_output_ids);
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn check_outputs_spent() -> Result<()> {
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+
+    let token_supply = client.get_token_supply().await?;
+
+    let second_address = client.get_addresses(&secret_manager).with_range(1..2).get_raw().await?[0];
+
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)?
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(second_address)))
+        .finish_output(token_supply)?;
+
+    let block = client
+        .block()
+        .with_secret_manager(&secret_manager)
+        .with_outputs(vec![output])?
+        .finish()
+        .await?;
+    client.retry_until_included(&block.id(), None, None).await?;
+
+    let tx_payload = match block.payload().unwrap() {
+        Payload::Transaction(tx_payload) => tx_payload,
+        _ => panic!("missing transaction payload"),
+    };
+    let TransactionEssence::Regular(essence) = tx_payload.essence();
+    let spent_output_ids = essence
+        .inputs()
+        .iter()
+        .map(|input| match input {
+            Input::Utxo(input) => *input.output_id(),
+            _ => panic!("unexpected input kind"),
+        })
+        .collect::<Vec<_>>();
+    let unspent_output_ids = Client::compute_output_ids(tx_payload)?;
+
+    let spent = client
+        .check_outputs_spent(spent_output_ids.iter().chain(&unspent_output_ids).copied().collect())
+        .await?
+        .into_items()?;
+
+    assert_eq!(spent.len(), spent_output_ids.len() + unspent_output_ids.len());
+    assert!(spent
+        .iter()
+        .all(|state| state.is_spent == spent_output_ids.contains(&state.output_id)));
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn get_creating_block() -> Result<()> {
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+
+    let address = client.get_addresses(&secret_manager).with_range(0..1).finish().await?[0].clone();
+    let output_ids_response = client.basic_output_ids(vec![QueryParameter::Address(address)]).await?;
+    let output_id = output_ids_response.items[0];
+
+    let block = client.get_creating_block(&output_id).await?;
+
+    let tx_payload = match block.payload().unwrap() {
+        Payload::Transaction(tx_payload) => tx_payload,
+        _ => panic!("missing transaction payload"),
+    };
+    assert_eq!(&tx_payload.id(), output_id.transaction_id());
+    assert!(Client::compute_output_ids(tx_payload)?.contains(&output_id));
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn get_address_balance_delta() -> Result<()> {
+    let checkpoint = setup_client_with_node_health_ignored()
+        .get_info()
+        .await?
+        .node_info
+        .status
+        .confirmed_milestone
+        .index;
+
+    // Receives funds from the faucet after the checkpoint
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+    let address = client.get_addresses(&secret_manager).with_range(0..1).finish().await?[0].clone();
+    let token_supply = client.get_token_supply().await?;
+    let output_ids_response = client
+        .basic_output_ids(vec![QueryParameter::Address(address.clone())])
+        .await?;
+    let received = client
+        .get_outputs(output_ids_response.items)
+        .await?
+        .iter()
+        .map(|output_response| Ok(Output::try_from_dto(&output_response.output, token_supply)?.amount()))
+        .sum::<Result<u64>>()?;
+
+    let balance_delta = client.get_address_balance_delta(&address, checkpoint, None).await?;
+
+    assert_eq!(balance_delta.delta, received.to_string());
+    assert!(balance_delta.milestone_index > checkpoint);
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn get_first_funded_address() -> Result<()> {
+    let client = setup_client_with_node_health_ignored();
+    let secret_manager = SecretManager::try_from_mnemonic(&Client::generate_mnemonic()?)?;
+
+    // Fund the address with index 3 only
+    let address = client
+        .get_addresses(&secret_manager)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_range(3..4)
+        .finish()
+        .await?[0]
+        .clone();
+    request_funds_from_faucet(FAUCET_URL, &address).await?;
+
+    for _ in 0..30 {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        if !client
+            .basic_output_ids(vec![QueryParameter::Address(address.clone())])
+            .await?
+            .is_empty()
+        {
+            break;
+        }
+    }
+
+    let funded_address = client
+        .get_first_funded_address(&secret_manager, SHIMMER_COIN_TYPE, 0, 20)
+        .await?
+        .expect("funded address not found");
+
+    assert_eq!(funded_address.address, address);
+    assert_eq!(funded_address.index, 3);
+    assert!(!funded_address.internal);
+    assert_ne!(funded_address.amount, "0");
+
+    // The funds are out of reach with a gap limit of 3
+    let funded_address = client
+        .get_first_funded_address(&secret_manager, SHIMMER_COIN_TYPE, 0, 3)
+        .await?;
+    assert_eq!(funded_address, None);
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn get_address_total() -> Result<()> {
+    let (client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+    let address = client.get_addresses(&secret_manager).with_range(0..1).finish().await?[0].clone();
+    let token_supply = client.get_token_supply().await?;
+
+    let output_ids_response = client
+        .basic_output_ids(vec![QueryParameter::Address(address.clone())])
+        .await?;
+    let output_count = output_ids_response.items.len();
+    let balance = client
+        .get_outputs(output_ids_response.items)
+        .await?
+        .iter()
+        .map(|output_response| Ok(Output::try_from_dto(&output_response.output, token_supply)?.amount()))
+        .sum::<Result<u64>>()?;
+
+    let address_total = client.get_address_total(&address).await?;
+
+    assert_eq!(address_total.balance, balance.to_string());
+    assert_eq!(address_total.output_count, output_count);
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn concurrent_transactions_reserve_inputs() -> Result<()> {
+    let (_client, secret_manager) = create_client_and_secret_manager_with_funds(None).await?;
+    let client = Client::builder()
+        .with_node(NODE_LOCAL)?
+        .with_output_reservation(true)
+        .finish()?;
+    let token_supply = client.get_token_supply().await?;
+    let addresses = client.get_addresses(&secret_manager).with_range(0..2).get_raw().await?;
+
+    // Request funds a second time, so each transaction can use another input
+    let first_address = addresses[0].to_bech32(client.get_bech32_hrp().await?);
+    request_funds_from_faucet(FAUCET_URL, &first_address).await?;
+    for _ in 0..30 {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        if client
+            .basic_output_ids(vec![QueryParameter::Address(first_address.clone())])
+            .await?
+            .len()
+            == 2
+        {
+            break;
+        }
+    }
+
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)?
+        .add_unlock_condition(UnlockCondition::Address(AddressUnlockCondition::new(addresses[1])))
+        .finish_output(token_supply)?;
+    let send = || async {
+        client
+            .block()
+            .with_secret_manager(&secret_manager)
+            .with_outputs(vec![output.clone()])?
+            .finish()
+            .await
+    };
+    let (first_block, second_block) = tokio::join!(send(), send());
+
+    let inputs = |block: iota_client::block::Block| match block.payload() {
+        Some(Payload::Transaction(tx_payload)) => {
+            let TransactionEssence::Regular(essence) = tx_payload.essence();
+            essence.inputs().to_vec()
+        }
+        _ => panic!("missing transaction payload"),
+    };
+    let first_inputs = inputs(first_block?);
+    let second_inputs = inputs(second_block?);
+    assert!(first_inputs.iter().all(|input| !second_inputs.contains(input)));
+
+    Ok(())
+}
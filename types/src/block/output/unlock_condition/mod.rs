@@ -73,7 +73,7 @@ impl UnlockCondition {
     }
 
     /// Returns the [`UnlockConditionFlags`] for the given [`UnlockCondition`].
-    pub(crate) fn flag(&self) -> UnlockConditionFlags {
+    pub fn flag(&self) -> UnlockConditionFlags {
         match self {
             Self::Address(_) => UnlockConditionFlags::ADDRESS,
             Self::StorageDepositReturn(_) => UnlockConditionFlags::STORAGE_DEPOSIT_RETURN,
@@ -87,7 +87,7 @@ pub mod dto {
         #[serde(rename = "tailTransactionHash")]
         pub tail_transaction_hash: String,
         pub address: AddressDto,
-        pub deposit: u64,
+        pub deposit: String,
     }
 
     impl From<&MigratedFundsEntry> for MigratedFundsEntryDto {
@@ -95,7 +95,7 @@ pub mod dto {
             Self {
                 tail_transaction_hash: prefix_hex::encode(value.tail_transaction_hash().as_ref()),
                 address: value.address().into(),
-                deposit: value.amount(),
+                deposit: value.amount().to_string(),
             }
         }
     }
@@ -108,7 +108,10 @@ pub mod dto {
             Ok(Self::new(
                 TailTransactionHash::new(tail_transaction_hash)?,
                 (&value.address).try_into()?,
-                value.deposit,
+                value
+                    .deposit
+                    .parse::<u64>()
+                    .map_err(|_| DtoError::InvalidField("deposit"))?,
                 token_supply,
             )?)
         }
@@ -119,7 +122,10 @@ pub mod dto {
 
             Ok(Self {
                 tail_transaction_hash: TailTransactionHash::new(tail_transaction_hash)?,
-                amount: value.deposit,
+                amount: value
+                    .deposit
+                    .parse::<u64>()
+                    .map_err(|_| DtoError::InvalidField("deposit"))?,
                 address: (&value.address).try_into()?,
             })
         }
@@ -237,3 +237,25 @@ fn getters() {
     assert_eq!(receipt.transaction(), &transaction);
     assert_eq!(receipt.amount(), AMOUNT);
 }
+
+#[test]
+fn migrated_funds_entry_dto() {
+    use iota_types::block::payload::milestone::option::dto::MigratedFundsEntryDto;
+
+    let token_supply = protocol_parameters().token_supply();
+    let migrated_funds = MigratedFundsEntry::new(
+        TailTransactionHash::new(TAIL_TRANSACTION_HASH_BYTES).unwrap(),
+        Address::from(Ed25519Address::from_str(ED25519_ADDRESS).unwrap()),
+        AMOUNT,
+        token_supply,
+    )
+    .unwrap();
+
+    // The deposit is a string, so large amounts don't lose precision in JSON
+    let dto = MigratedFundsEntryDto::from(&migrated_funds);
+    assert_eq!(serde_json::to_value(&dto).unwrap()["deposit"], AMOUNT.to_string());
+    assert_eq!(
+        MigratedFundsEntry::try_from_dto(&dto, token_supply).unwrap(),
+        migrated_funds
+    );
+}